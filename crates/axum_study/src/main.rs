@@ -6,24 +6,37 @@ use axum::{
         HeaderMap, HeaderName, HeaderValue, StatusCode,
     },
     response::IntoResponse,
-    routing::{get, post},
+    routing::post,
     Form, Router,
 };
 use axum_extra::{response::Html, TypedHeader};
 use headers::UserAgent;
+use hello_macro::SqlQuery;
+use hello_macro_derive::{route, routes, sql};
 use serde::{Deserialize, Serialize};
+use sqlx::{Any, Execute};
 use tokio::net::TcpListener;
 
 #[tokio::main]
 async fn main() {
-    let router = Router::new().route("/path/:id", get(path_request));
+    let router = Router::new().merge(routes![path_request, user_sql, status]);
     let address = ("0.0.0.0", 8080);
     let listener = TcpListener::bind(address).await.expect("bind fail");
     axum::serve(listener, router).await.expect("serve fail");
 }
 
+#[route(GET, "/path/:id")]
 async fn path_request(Path(id): Path<String>) {}
 
+const FIND_USER: SqlQuery<1> = sql!(select id, name from user where id = ?);
+
+// sql!在编译期检查语句，bind只接受一个参数；这里没有连接数据库，只返回交给sqlx的语句
+#[route(GET, "/user/:id/sql")]
+async fn user_sql(Path(id): Path<i64>) -> String {
+    let query = FIND_USER.bind::<Any, _>((id,));
+    query.sql().to_string()
+}
+
 // response: String will get a `text/plain; charset=utf-8` content-type
 async fn header_request(TypedHeader(user_agent): TypedHeader<UserAgent>) -> String {
     user_agent.to_string()
@@ -45,6 +58,7 @@ async fn json_request(Json(json): Json<SomeJson>) -> Json<String> {
 }
 
 // empty response with special status code
+#[route(GET, "/status")]
 async fn status() -> StatusCode {
     StatusCode::OK
 }
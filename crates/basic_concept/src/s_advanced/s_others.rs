@@ -1,3 +1,4 @@
+use basic_utils::data_struct::LruCache;
 use rand::Rng;
 use std::hash::Hash;
 use std::{collections::HashMap, env, ffi::OsString};
@@ -43,6 +44,28 @@ where
     return map.get_mut(&key).unwrap();
 }
 
+///
+/// [get_default]的有界版本，key超过容量时淘汰最久未使用的
+///
+fn get_default_bounded<'c, K, V>(cache: &'c mut LruCache<K, V>, key: K) -> &'c mut V
+where
+    K: Clone + Eq + Hash,
+    V: Default,
+{
+    cache.get_or_insert_with(key, V::default)
+}
+
+pub fn study_lru_cache() {
+    let mut counts = LruCache::new(2);
+    for word in ["a", "b", "a", "c", "a"] {
+        *get_default_bounded(&mut counts, word) += 1;
+    }
+    // b是最久未使用的，插入c时被淘汰
+    assert_eq!(Some(&3), counts.peek("a"));
+    assert_eq!(None, counts.peek("b"));
+    println!("counts {:?}", counts);
+}
+
 fn test() {
     let arr = vec![Box::new(1), Box::new(2)];
     let (first, second) = (&arr[0], &arr[1]);
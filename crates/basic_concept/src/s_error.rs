@@ -1,51 +1,29 @@
 use std::{error, num};
 use std::{
-    error::Error,
-    fmt::Display,
     fs::{self, File},
     io::{self, Read},
 };
 
 use anyhow::anyhow;
+use hello_macro_derive::ErrorCode;
 
-#[derive(Debug)]
-pub struct AppError {
-    code: i32,
-    message: String,
+/// 错误码由`#[code]`指定，`ErrorCode`生成code()、Display、Error和#[from]字段的From
+#[derive(Debug, ErrorCode)]
+pub enum AppError {
+    #[code(10000)]
+    Io(#[from] io::Error),
+    #[code(10001)]
+    NumberParse(#[from] num::ParseIntError),
 }
 
-impl Display for AppError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "code: {}, message: {}", self.code, self.message)
-    }
-}
-
-impl Error for AppError {}
-
-impl From<io::Error> for AppError {
-    fn from(value: io::Error) -> Self {
-        AppError {
-            code: IO_ERROR,
-            message: value.to_string(),
-        }
-    }
-}
-
-impl From<num::ParseIntError> for AppError {
-    fn from(value: num::ParseIntError) -> Self {
-        AppError {
-            code: NUMBER_PARSE_ERROR,
-            message: value.to_string(),
-        }
-    }
-}
-
-const IO_ERROR: i32 = 10000;
-const NUMBER_PARSE_ERROR: i32 = 10001;
-
 pub fn study_error() {
     let r = read_file();
     println!("result:{:?}", r);
+    if let Err(e) = parse() {
+        println!("error code: {}, {}", e.code(), e);
+    }
+    // 所有错误码，可以和其他crate的错误码表一起检查冲突
+    print!("{}", hello_macro::code_table(&[AppError::CODES]));
 }
 
 fn read_file() -> Result<String, AppError> {
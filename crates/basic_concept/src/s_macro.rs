@@ -1,4 +1,4 @@
-use hello_macro_derive::{route, sql, HelloMacro};
+use hello_macro_derive::{route, routes, sql, Decode, Encode, HelloMacro};
 
 ///
 /// 声明宏declarative macro
@@ -13,7 +13,7 @@ use hello_macro_derive::{route, sql, HelloMacro};
 /// 过程宏从输入中获得标记流，处理然后生成新的标记流，然后把标记流交给编译器继续处理。过程宏操作的是rust ast
 ///
 ///use crate::myvec;
-use hello_macro::HelloMacro;
+use hello_macro::{DecodeError, HelloMacro, SqlQuery};
 
 /// 声明宏：使用macro_rules!定义
 ///
@@ -57,10 +57,40 @@ macro_rules! myvec {
 #[derive(HelloMacro)]
 pub struct Pancakes;
 
-// 过程宏：attribute-like宏
-// #[route(GET, "/")]
-fn index() {
-    todo!()
+// 泛型参数和where子句会带到生成的impl上，#[hello]可以修改问候语
+#[derive(HelloMacro)]
+#[hello(greeting = "Hi")]
+pub struct Plate<T>
+where
+    T: HelloMacro,
+{
+    pub food: T,
+}
+
+// enum的每个variant可以有不同的问候语
+#[derive(HelloMacro)]
+pub enum Breakfast {
+    Pancakes,
+    #[hello(name = "French Toast", greeting = "Bonjour")]
+    Toast(u8),
+}
+
+// 编码成紧凑的二进制格式，since = 2的字段在版本1的数据中不存在
+#[derive(Debug, PartialEq, Encode, Decode)]
+pub enum Message {
+    Ping,
+    Chat {
+        from: String,
+        text: String,
+        #[encode(since = 2)]
+        reply_to: Option<u64>,
+    },
+}
+
+// 过程宏：attribute-like宏，同时生成把index注册到axum::Router上的函数
+#[route(GET, "/")]
+async fn index() -> &'static str {
+    "index"
 }
 
 pub fn study_macro() {
@@ -71,7 +101,24 @@ pub fn study_macro() {
 
     // derive macro
     Pancakes::hello_macro();
+    Plate::<Pancakes>::hello_macro();
+    println!("{}", Plate { food: Pancakes }.food.greeting());
+    println!("{}", Breakfast::Toast(2).greeting());
+    let chat = Message::Chat {
+        from: String::from("libai"),
+        text: String::from("hi"),
+        reply_to: Some(7),
+    };
+    let bytes = hello_macro::to_bytes(&chat, 2);
+    println!("{:?} is encoded as {:?}", chat, bytes);
+    let decoded: Result<Message, DecodeError> = hello_macro::from_bytes(&bytes[..bytes.len() - 1]);
+    println!("truncated input: {:?}", decoded);
+
+    // attribute-like macro, routes!把#[route]标注的handler挂到Router上
+    let router: axum::Router = routes![index];
+    println!("router is {:?}", router);
 
-    // function-like macro
-    // let sql = sql!(select * from user where id=1);
+    // function-like macro, 编译期检查SQL语法并统计?的个数
+    let sql = sql!(select * from user where id = ?);
+    println!("sql is {}, kind {:?}, {} params", sql, sql.kind(), SqlQuery::<1>::PARAMS);
 }
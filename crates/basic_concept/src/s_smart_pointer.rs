@@ -7,7 +7,7 @@ use std::{
     sync::Arc,
 };
 
-use basic_utils::data_struct::BinaryTreeNode;
+use basic_utils::data_struct::{BinaryTreeNode, InOrder, Levels, PostOrder, PreOrder};
 
 /// # Box<T>
 /// 适用如下场景：
@@ -212,131 +212,35 @@ fn modify(number: &mut i32) {
 }
 
 pub fn study_rc() {
-    let n6 = Rc::new(RefCell::new(BinaryTreeNode::new(6)));
+    // 按层序构建，等同于手工Rc::new(RefCell::new(BinaryTreeNode::from(Some(Rc::clone(&n2)), ...)))
+    let root = BinaryTreeNode::from_level_order_str("[1,2,3,4,5,6]")
+        .expect("invalid level order")
+        .expect("empty tree");
+    print!("{}", BinaryTreeNode::to_pretty_string(Some(&root)));
 
-    let n5 = Rc::new(RefCell::new(BinaryTreeNode::new(5)));
-    let n4 = Rc::new(RefCell::new(BinaryTreeNode::new(4)));
+    // 遍历算法(栈/队列实现的非递归遍历)见basic_utils::data_struct::traversal
+    let levels: Vec<Vec<i32>> = Levels::new(Some(&root)).values().collect();
+    assert_eq!(vec![vec![1], vec![2, 3], vec![4, 5, 6]], levels);
 
-    let n2 = Rc::new(RefCell::new(BinaryTreeNode::from(
-        Some(Rc::clone(&n4)),
-        Some(Rc::clone(&n5)),
-        2,
-    )));
+    let inorder: Vec<i32> = InOrder::new(Some(&root)).values().collect();
+    assert_eq!(vec![4, 2, 5, 1, 6, 3], inorder);
 
-    let n3 = Rc::new(RefCell::new(BinaryTreeNode::from(Some(Rc::clone(&n6)), None, 3)));
+    let preorder: Vec<i32> = PreOrder::new(Some(&root)).values().collect();
+    assert_eq!(vec![1, 2, 4, 5, 3, 6], preorder);
 
-    let root = Rc::new(RefCell::new(BinaryTreeNode::from(
-        Some(Rc::clone(&n2)),
-        Some(Rc::clone(&n3)),
-        1,
-    )));
+    let postorder: Vec<i32> = PostOrder::new(Some(&root)).values().collect();
+    assert_eq!(vec![4, 5, 2, 6, 3, 1], postorder);
+    println!("bfs: {:?}, dfs: {:?} {:?} {:?}", levels, inorder, preorder, postorder);
 
-    bfs_tree(Some(Rc::clone(&root)));
-    dfs_tree(Some(Rc::clone(&root)));
-    preorder_traversal(Some(Rc::clone(&root)));
-    postorder_traversal(Some(Rc::clone(&root)));
-}
-
-fn bfs_tree(root: Option<Rc<RefCell<BinaryTreeNode>>>) {
-    if root.is_none() {
-        return;
-    }
-    let mut q = Vec::new();
-    q.push(root.unwrap());
-    while !q.is_empty() {
-        let size = q.len();
-        for _i in 0..size {
-            let t = q.remove(0);
-            let t1 = t.borrow();
-            print!("\t {}", t1.val);
-
-            if let Some(l) = t1.left.as_ref() {
-                q.push(Rc::clone(l));
-            }
-            if let Some(r) = t1.right.as_ref() {
-                q.push(Rc::clone(r));
-            }
-        }
-        println!();
-    }
-    println!("bfs end");
-}
-
-// 等同
-fn dfs_tree(root: Option<Rc<RefCell<BinaryTreeNode>>>) {
-    if root.is_none() {
-        return;
-    }
-    let mut s = Vec::new();
-    let mut p = root;
-    while !s.is_empty() || !p.is_none() {
-        if let Some(t) = p {
-            s.push(Rc::clone(&t));
-            p = t.borrow().left.clone();
-        } else {
-            if let Some(node) = s.pop() {
-                print!("{} \t", node.borrow().val);
-                p = node.borrow().right.clone();
-            }
-        }
-    }
-    println!("dfs end");
-}
-
-// preorder
-fn preorder_traversal(root: Option<Rc<RefCell<BinaryTreeNode>>>) {
-    if root.is_none() {
-        return;
-    }
-    let mut s = Vec::new();
-    // s.push(root.unwrap());
-    // while !s.is_empty() {
-    //     let node = s.pop().unwrap();
-    //     print!("{} \t", node.borrow().val);
-    //     if let Some(r) = node.borrow().right {
-    //         s.push(Rc::clone(&r));
-    //     }
-    //     if let Some(l) = node.borrow().left {
-    //         s.push(Rc::clone(&l));
-    //     }
-    // }
-    s.push(root);
-    while !s.is_empty() {
-        if let Some(node) = s.pop().flatten() {
-            print!("{} \t", node.borrow().val);
-            s.push(node.borrow().right.clone());
-            s.push(node.borrow().left.clone());
-        }
+    // 迭代器返回的是节点的Rc，可以修改节点
+    for node in PreOrder::new(Some(&root)) {
+        node.borrow_mut().val *= 10;
     }
-
-    println!("preorder_traversal end");
-}
-
-fn postorder_traversal(root: Option<Rc<RefCell<BinaryTreeNode>>>) {
-    if root.is_none() {
-        return;
-    }
-    let mut s = Vec::new();
-    let mut p = root;
-    let mut visited = None;
-    while !s.is_empty() || !p.is_none() {
-        if let Some(t) = p {
-            s.push(Rc::clone(&t));
-            p = t.borrow().left.clone();
-        } else {
-            let t1 = s.last().unwrap();
-            if t1.borrow().right != visited {
-                p = t1.borrow().right.clone();
-                visited = None;
-            } else {
-                if let Some(node) = s.pop() {
-                    print!("{} \t", node.borrow().val);
-                    visited = Some(Rc::clone(&node));
-                }
-            }
-        }
-    }
-    println!("postorder_traversal end");
+    println!(
+        "root rc count = {}, val = {}",
+        Rc::strong_count(&root),
+        root.borrow().val
+    );
 }
 
 fn study_arc() {
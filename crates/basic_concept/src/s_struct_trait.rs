@@ -51,6 +51,7 @@
 /// 若是trait特型是[std::prelude]的一部分，这样rust就会默认自动将其引入
 ///
 ///
+use hello_macro_derive::Builder;
 use std::{
     fmt::{Debug, Display},
     time::SystemTime,
@@ -65,10 +66,13 @@ use std::{
  * 若在结构体中使用引用类型，必须增加生命周期
  */
 // Classic struct with name field
-#[derive(Debug)]
+// #[derive(Builder)]生成StudentBuilder，不用在构造时列出所有字段
+#[derive(Debug, Builder)]
 struct Student {
+    #[builder(into)]
     name: String,
     level: u8,
+    #[builder(default)]
     remote: bool,
 }
 
@@ -150,11 +154,12 @@ impl Area for Rectangle {
 }
 
 pub fn study_struct() {
-    let user_1 = Student {
-        name: String::from("libai"),
-        level: 1,
-        remote: true,
-    };
+    let user_1 = Student::builder()
+        .name("libai")
+        .level(1)
+        .remote(true)
+        .build()
+        .expect("name and level are set");
     let m1 = Grades('A', 'A', 'A', 'A', 3.75);
     let u_1 = Unit {};
     println!("{:?}, {:?}, {:?}", user_1, m1.4, u_1);
@@ -1,7 +1,8 @@
 use ahash::{AHashMap, AHasher, RandomState};
 use basic_utils;
+use hello_macro_derive::{Builder, IntEnum};
 use lazy_static::lazy_static;
-use std::{collections::HashMap, fmt::Display, sync::Mutex};
+use std::{collections::HashMap, sync::Mutex};
 use std::f32::NAN;
 use crate::s_pattern_match::Point;
 
@@ -36,9 +37,11 @@ pub const fn test_const_fn() -> &'static str {
 }
 
 /// 以上均为编译时初始化，若需要运行时初始化，则需要使用Box::leak
-#[derive(Debug)]
+#[derive(Debug, Builder)]
 struct Config {
+    #[builder(into)]
     a: String,
+    #[builder(into, default = String::from("world"))]
     b: String,
 }
 static mut CONFIG: Option<&mut Config> = None;
@@ -57,10 +60,7 @@ pub fn init() {
 
 /// 函数返回全局变量
 fn init_config() -> Option<&'static mut Config> {
-    let c = Box::new(Config {
-        a: String::from("hello"),
-        b: String::from("world"),
-    });
+    let c = Box::new(Config::builder().a("hello").build().ok()?);
     Some(Box::leak(c))
 }
 
@@ -314,12 +314,9 @@ fn study_map() {
     // 当k2不存在的时候才插入
     map2.entry("k2").or_insert(456);
 
-    let mut count_map = HashMap::new();
-    let text = "hello world wonderful world";
-    for i in text.split_whitespace() {
-        let count = count_map.entry(i).or_insert(0);
-        *count += 1;
-    }
+    // 单词计数, 忽略大小写和标点
+    let text = "Hello world, wonderful world!";
+    let count_map = basic_utils::tokenizer::word_frequency(text);
     println!("count map {:?}", count_map);
 
     // rust默认使用安全hash算法，性能会差一些, 可以用AHasher
@@ -438,34 +435,23 @@ pub fn study_enum_convert() {
     // 可以通过泛型为所有类型实现某个trait。TryInto trait就是。相当于如下代码。
     let e2 = TestEnum::try_from(v).expect("convert fail");
     println!("int to enum {0} {0:?} {1:?}", e, e2);
+
+    // 不合法的值返回的错误会列出所有合法的值
+    if let Err(err) = TestEnum::try_from(7) {
+        println!("{}", err);
+    }
+    let e3: TestEnum = "TestEnum-C".parse().expect("parse fail");
+    println!("str to enum {:?}, all {:?}, back to int {}", e3, TestEnum::ALL, i32::from(e3));
 }
 
-#[derive(Debug)]
+/// #[derive(IntEnum)]生成TryFrom<i32>、From<TestEnum> for i32、Display和FromStr
+#[derive(Debug, Clone, Copy, IntEnum)]
+#[repr(i32)]
 enum TestEnum {
+    #[int_enum(name = "TestEnum-A")]
     A,
+    #[int_enum(name = "TestEnum-B")]
     B,
+    #[int_enum(name = "TestEnum-C")]
     C,
 }
-
-impl TryFrom<i32> for TestEnum {
-    type Error = ();
-
-    fn try_from(value: i32) -> Result<Self, Self::Error> {
-        match value {
-            x if x == TestEnum::A as i32 => Ok(TestEnum::A),
-            y if y == TestEnum::B as i32 => Ok(TestEnum::B),
-            z if z == TestEnum::C as i32 => Ok(TestEnum::C),
-            _ => Err(()),
-        }
-    }
-}
-
-impl Display for TestEnum {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            TestEnum::A => write!(f, "TestEnum-A"),
-            TestEnum::B => write!(f, "TestEnum-B"),
-            TestEnum::C => write!(f, "TestEnum-C"),
-        }
-    }
-}
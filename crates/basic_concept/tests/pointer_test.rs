@@ -5,7 +5,7 @@ mod test {
         thread,
     };
 
-    use basic_concept::s_pointer;
+    use basic_concept::{s_pointer, s_smart_pointer};
 
     #[test]
     fn test1() {
@@ -48,4 +48,9 @@ mod test {
         let v = c.get().expect("get value fail");
         println!("{}", v);
     }
+
+    #[test]
+    fn test5() {
+        s_smart_pointer::study_rc();
+    }
 }
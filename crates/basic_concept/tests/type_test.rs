@@ -127,6 +127,11 @@ mod test {
         println!("{:?} {:?}", vec1, item2);
     }
 
+    #[test]
+    fn test_study_lru_cache() {
+        basic_concept::s_advanced::s_others::study_lru_cache();
+    }
+
     #[test]
     fn test9() {
 
@@ -0,0 +1,84 @@
+//!
+//! compare `Rc<RefCell<BinaryTreeNode>>` with [TreeArena] on a complete binary tree of a million nodes
+//!
+//! 运行: `cargo bench -p basic_utils --bench tree_arena`
+//!
+use std::{
+    cell::RefCell,
+    hint::black_box,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+use basic_utils::data_struct::{BinaryTreeNode, InOrder, LevelOrder, PostOrder, PreOrder, TreeArena, TreeId};
+
+const NODES: usize = 1_000_000;
+const ROUNDS: u32 = 5;
+
+type RcNode = Rc<RefCell<BinaryTreeNode<u64>>>;
+
+/// run `f` several times and report the best time, the result of the last run is returned
+fn measure<R>(name: &str, mut f: impl FnMut() -> R) -> R {
+    let mut best = Duration::MAX;
+    let mut r = None;
+    for _ in 0..ROUNDS {
+        let start = Instant::now();
+        r = Some(black_box(f()));
+        best = best.min(start.elapsed());
+    }
+    println!("{:<24}{:>10.2?}", name, best);
+    r.expect("ROUNDS is positive")
+}
+
+fn build_rc() -> Option<RcNode> {
+    let values: Vec<Option<u64>> = (0..NODES as u64).map(Some).collect();
+    BinaryTreeNode::from_level_order(&values).expect("a complete tree has no unattached values")
+}
+
+/// 和from_level_order相同的形状：节点i的子节点是2i+1和2i+2
+fn build_arena() -> (TreeArena<u64>, TreeId) {
+    let mut t = TreeArena::with_capacity(NODES);
+    let mut ids = Vec::with_capacity(NODES);
+    ids.push(t.add(0));
+    for i in 1..NODES {
+        let parent = ids[(i - 1) / 2];
+        let id = if i % 2 == 1 {
+            t.add_left(parent, i as u64)
+        } else {
+            t.add_right(parent, i as u64)
+        };
+        ids.push(id);
+    }
+    (t, ids[0])
+}
+
+fn main() {
+    println!("{} nodes, best of {} rounds", NODES, ROUNDS);
+
+    let rc = measure("rc build", build_rc);
+    let (arena, root) = measure("arena build", build_arena);
+
+    let sum = measure("rc pre-order", || PreOrder::new(rc.as_ref()).values().sum::<u64>());
+    assert_eq!(
+        sum,
+        measure("arena pre-order", || arena.pre_order(root).values().sum::<u64>())
+    );
+    let sum = measure("rc in-order", || InOrder::new(rc.as_ref()).values().sum::<u64>());
+    assert_eq!(
+        sum,
+        measure("arena in-order", || arena.in_order(root).values().sum::<u64>())
+    );
+    let sum = measure("rc post-order", || PostOrder::new(rc.as_ref()).values().sum::<u64>());
+    assert_eq!(
+        sum,
+        measure("arena post-order", || arena.post_order(root).values().sum::<u64>())
+    );
+    let depth = measure("rc level-order", || LevelOrder::new(rc.as_ref()).map(|(d, _)| d).max());
+    assert_eq!(
+        depth,
+        measure("arena level-order", || arena.level_order(root).map(|(d, _)| d).max())
+    );
+
+    measure("rc -> arena", || TreeArena::new().insert_rc(rc.as_ref()));
+    measure("arena -> rc", || arena.to_rc(root));
+}
@@ -0,0 +1,338 @@
+//!
+//! checked, saturating and wrapping arithmetic for all primitive number types
+//!
+//! 整数的四则运算在溢出或除0时会panic(debug)或者静默溢出(release)，这里统一提供3种语义:
+//! * try_*        溢出/除0时返回[ArithmeticError]
+//! * saturating_* 结果限制在[MIN, MAX]之间
+//! * wrapping_*   补码循环溢出
+//!
+use std::{error::Error, fmt};
+
+/// error returned by the `try_*` family of [Arithmetic]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ArithmeticError {
+    /// result is out of range of the type
+    Overflow,
+    /// divisor or modulus is zero
+    DivisionByZero,
+    /// result is NaN, only returned for float types
+    NotANumber,
+}
+
+impl fmt::Display for ArithmeticError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ArithmeticError::Overflow => write!(f, "arithmetic overflow"),
+            ArithmeticError::DivisionByZero => write!(f, "division by zero"),
+            ArithmeticError::NotANumber => write!(f, "result is not a number"),
+        }
+    }
+}
+
+impl Error for ArithmeticError {}
+
+///
+/// add/sub/mul/div/rem with checked, saturating and wrapping semantics
+///
+/// 实现了所有整数类型(i8..i128, u8..u128, isize, usize)以及f32, f64
+///
+/// # Examples
+///
+/// ```
+/// use basic_utils::arithmetic::{Arithmetic, ArithmeticError};
+///
+/// assert_eq!(Ok(3), 1i32.try_add(2));
+/// assert_eq!(Err(ArithmeticError::Overflow), i32::MIN.try_div(-1));
+/// assert_eq!(Err(ArithmeticError::DivisionByZero), 10u8.try_rem(0));
+///
+/// assert_eq!(i8::MAX, 100i8.saturating_add(100));
+/// assert_eq!(f64::MAX, f64::MAX.saturating_mul(2.0));
+/// assert_eq!(-56, 100i8.wrapping_add(100));
+/// ```
+///
+/// 整数类型的同名inherent方法(saturating_add, wrapping_add等)语义相同，可以直接调用；
+/// 在泛型代码中通过`T: Arithmetic`使用。
+///
+/// # Panics
+///
+/// 整数类型的`saturating_div`, `saturating_rem`, `wrapping_div`, `wrapping_rem`与std一致，除0时panic。
+/// 处理不可信的输入时请使用`try_*`。
+///
+pub trait Arithmetic: Copy + PartialOrd + Sized {
+    fn try_add(self, rhs: Self) -> Result<Self, ArithmeticError>;
+    fn try_sub(self, rhs: Self) -> Result<Self, ArithmeticError>;
+    fn try_mul(self, rhs: Self) -> Result<Self, ArithmeticError>;
+    fn try_div(self, rhs: Self) -> Result<Self, ArithmeticError>;
+    fn try_rem(self, rhs: Self) -> Result<Self, ArithmeticError>;
+
+    fn saturating_add(self, rhs: Self) -> Self;
+    fn saturating_sub(self, rhs: Self) -> Self;
+    fn saturating_mul(self, rhs: Self) -> Self;
+    fn saturating_div(self, rhs: Self) -> Self;
+    fn saturating_rem(self, rhs: Self) -> Self;
+
+    fn wrapping_add(self, rhs: Self) -> Self;
+    fn wrapping_sub(self, rhs: Self) -> Self;
+    fn wrapping_mul(self, rhs: Self) -> Self;
+    fn wrapping_div(self, rhs: Self) -> Self;
+    fn wrapping_rem(self, rhs: Self) -> Self;
+}
+
+// 和std中from_str_radix_int_impl一样，用声明宏为每个类型生成impl
+macro_rules! impl_int_arithmetic {
+    ($($t:ty)*) => {$(
+        impl Arithmetic for $t {
+            #[inline]
+            fn try_add(self, rhs: Self) -> Result<Self, ArithmeticError> {
+                <$t>::checked_add(self, rhs).ok_or(ArithmeticError::Overflow)
+            }
+
+            #[inline]
+            fn try_sub(self, rhs: Self) -> Result<Self, ArithmeticError> {
+                <$t>::checked_sub(self, rhs).ok_or(ArithmeticError::Overflow)
+            }
+
+            #[inline]
+            fn try_mul(self, rhs: Self) -> Result<Self, ArithmeticError> {
+                <$t>::checked_mul(self, rhs).ok_or(ArithmeticError::Overflow)
+            }
+
+            #[inline]
+            fn try_div(self, rhs: Self) -> Result<Self, ArithmeticError> {
+                if rhs == 0 {
+                    return Err(ArithmeticError::DivisionByZero);
+                }
+                // 此时只剩MIN / -1溢出
+                <$t>::checked_div(self, rhs).ok_or(ArithmeticError::Overflow)
+            }
+
+            #[inline]
+            fn try_rem(self, rhs: Self) -> Result<Self, ArithmeticError> {
+                if rhs == 0 {
+                    return Err(ArithmeticError::DivisionByZero);
+                }
+                <$t>::checked_rem(self, rhs).ok_or(ArithmeticError::Overflow)
+            }
+
+            #[inline]
+            fn saturating_add(self, rhs: Self) -> Self {
+                <$t>::saturating_add(self, rhs)
+            }
+
+            #[inline]
+            fn saturating_sub(self, rhs: Self) -> Self {
+                <$t>::saturating_sub(self, rhs)
+            }
+
+            #[inline]
+            fn saturating_mul(self, rhs: Self) -> Self {
+                <$t>::saturating_mul(self, rhs)
+            }
+
+            #[inline]
+            fn saturating_div(self, rhs: Self) -> Self {
+                <$t>::saturating_div(self, rhs)
+            }
+
+            #[inline]
+            fn saturating_rem(self, rhs: Self) -> Self {
+                // MIN % -1 数学上的结果就是0，不存在饱和的情况
+                <$t>::wrapping_rem(self, rhs)
+            }
+
+            #[inline]
+            fn wrapping_add(self, rhs: Self) -> Self {
+                <$t>::wrapping_add(self, rhs)
+            }
+
+            #[inline]
+            fn wrapping_sub(self, rhs: Self) -> Self {
+                <$t>::wrapping_sub(self, rhs)
+            }
+
+            #[inline]
+            fn wrapping_mul(self, rhs: Self) -> Self {
+                <$t>::wrapping_mul(self, rhs)
+            }
+
+            #[inline]
+            fn wrapping_div(self, rhs: Self) -> Self {
+                <$t>::wrapping_div(self, rhs)
+            }
+
+            #[inline]
+            fn wrapping_rem(self, rhs: Self) -> Self {
+                <$t>::wrapping_rem(self, rhs)
+            }
+        }
+    )*};
+}
+
+impl_int_arithmetic! { i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize }
+
+// 浮点数不会panic，溢出得到inf，非法运算得到NaN
+// try_*: 有限的输入得到inf视为溢出，得到NaN视为NotANumber
+// saturating_*: inf限制为MAX/MIN
+// wrapping_*: 浮点数没有补码溢出，等同于普通的IEEE 754运算
+macro_rules! impl_float_arithmetic {
+    ($($t:ty)*) => {$(
+        impl Arithmetic for $t {
+            #[inline]
+            fn try_add(self, rhs: Self) -> Result<Self, ArithmeticError> {
+                check_float!($t, self, rhs, self + rhs)
+            }
+
+            #[inline]
+            fn try_sub(self, rhs: Self) -> Result<Self, ArithmeticError> {
+                check_float!($t, self, rhs, self - rhs)
+            }
+
+            #[inline]
+            fn try_mul(self, rhs: Self) -> Result<Self, ArithmeticError> {
+                check_float!($t, self, rhs, self * rhs)
+            }
+
+            #[inline]
+            fn try_div(self, rhs: Self) -> Result<Self, ArithmeticError> {
+                if rhs == 0.0 {
+                    return Err(ArithmeticError::DivisionByZero);
+                }
+                check_float!($t, self, rhs, self / rhs)
+            }
+
+            #[inline]
+            fn try_rem(self, rhs: Self) -> Result<Self, ArithmeticError> {
+                if rhs == 0.0 {
+                    return Err(ArithmeticError::DivisionByZero);
+                }
+                check_float!($t, self, rhs, self % rhs)
+            }
+
+            #[inline]
+            fn saturating_add(self, rhs: Self) -> Self {
+                saturate_float!($t, self + rhs)
+            }
+
+            #[inline]
+            fn saturating_sub(self, rhs: Self) -> Self {
+                saturate_float!($t, self - rhs)
+            }
+
+            #[inline]
+            fn saturating_mul(self, rhs: Self) -> Self {
+                saturate_float!($t, self * rhs)
+            }
+
+            #[inline]
+            fn saturating_div(self, rhs: Self) -> Self {
+                saturate_float!($t, self / rhs)
+            }
+
+            #[inline]
+            fn saturating_rem(self, rhs: Self) -> Self {
+                saturate_float!($t, self % rhs)
+            }
+
+            #[inline]
+            fn wrapping_add(self, rhs: Self) -> Self {
+                self + rhs
+            }
+
+            #[inline]
+            fn wrapping_sub(self, rhs: Self) -> Self {
+                self - rhs
+            }
+
+            #[inline]
+            fn wrapping_mul(self, rhs: Self) -> Self {
+                self * rhs
+            }
+
+            #[inline]
+            fn wrapping_div(self, rhs: Self) -> Self {
+                self / rhs
+            }
+
+            #[inline]
+            fn wrapping_rem(self, rhs: Self) -> Self {
+                self % rhs
+            }
+        }
+    )*};
+}
+
+macro_rules! check_float {
+    ($t:ty, $a:expr, $b:expr, $r:expr) => {{
+        let r: $t = $r;
+        if r.is_nan() {
+            Err(ArithmeticError::NotANumber)
+        } else if r.is_infinite() && $a.is_finite() && $b.is_finite() {
+            Err(ArithmeticError::Overflow)
+        } else {
+            Ok(r)
+        }
+    }};
+}
+
+macro_rules! saturate_float {
+    ($t:ty, $r:expr) => {{
+        let r: $t = $r;
+        r.clamp(<$t>::MIN, <$t>::MAX)
+    }};
+}
+
+impl_float_arithmetic! { f32 f64 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sum<T: Arithmetic + Default>(list: &[T]) -> Result<T, ArithmeticError> {
+        list.iter().try_fold(T::default(), |acc, &x| acc.try_add(x))
+    }
+
+    #[test]
+    fn int_checked() {
+        assert_eq!(Ok(255u8), 200u8.try_add(55));
+        assert_eq!(Err(ArithmeticError::Overflow), 200u8.try_add(56));
+        assert_eq!(Err(ArithmeticError::Overflow), 0u32.try_sub(1));
+        assert_eq!(Err(ArithmeticError::Overflow), i64::MAX.try_mul(2));
+        assert_eq!(Err(ArithmeticError::Overflow), i32::MIN.try_div(-1));
+        assert_eq!(Err(ArithmeticError::Overflow), i32::MIN.try_rem(-1));
+        assert_eq!(Err(ArithmeticError::DivisionByZero), 10i32.try_div(0));
+        assert_eq!(Err(ArithmeticError::DivisionByZero), 10usize.try_rem(0));
+        assert_eq!(Ok(-3), (-7i16).try_div(2));
+        assert_eq!(Ok(-1), (-7i16).try_rem(2));
+    }
+
+    #[test]
+    fn int_saturating_and_wrapping() {
+        assert_eq!(i32::MAX, Arithmetic::saturating_div(i32::MIN, -1));
+        assert_eq!(0, Arithmetic::saturating_rem(i32::MIN, -1));
+        assert_eq!(0u8, Arithmetic::saturating_sub(1u8, 2));
+        assert_eq!(i32::MIN, Arithmetic::wrapping_div(i32::MIN, -1));
+        assert_eq!(0, Arithmetic::wrapping_rem(i32::MIN, -1));
+        assert_eq!(u128::MAX, Arithmetic::wrapping_sub(0u128, 1));
+    }
+
+    #[test]
+    fn float() {
+        assert_eq!(Ok(0.5), 1.0f32.try_div(2.0));
+        assert_eq!(Err(ArithmeticError::DivisionByZero), 1.0f64.try_div(0.0));
+        assert_eq!(Err(ArithmeticError::DivisionByZero), 1.0f64.try_rem(-0.0));
+        assert_eq!(Err(ArithmeticError::Overflow), f64::MAX.try_add(f64::MAX));
+        assert_eq!(Err(ArithmeticError::NotANumber), f64::INFINITY.try_sub(f64::INFINITY));
+        assert_eq!(Ok(f32::INFINITY), f32::INFINITY.try_add(1.0));
+        assert_eq!(f32::MIN, f32::MIN.saturating_mul(2.0));
+        assert_eq!(f64::MAX, 1.0f64.saturating_div(0.0));
+        assert!(0.0f64.saturating_div(0.0).is_nan());
+        assert_eq!(f64::INFINITY, f64::MAX.wrapping_add(f64::MAX));
+    }
+
+    #[test]
+    fn generic() {
+        assert_eq!(Ok(6), sum(&[1u8, 2, 3]));
+        assert_eq!(Err(ArithmeticError::Overflow), sum(&[100i8, 27, 1]));
+        assert_eq!(Ok(1.5), sum(&[1.0f32, 0.5]));
+    }
+}
@@ -0,0 +1,208 @@
+//!
+//! arbitrary-precision integers
+//!
+//! [crate::add]和[crate::devide]在i32范围外会溢出，这里的[BigUint]和[BigInt]没有上限。
+//! 数字按2³²进制从低位到高位存在`Vec<u32>`中，乘法在位数较多时使用Karatsuba算法，除法使用Knuth的算法D
+//!
+//! ```
+//! use basic_utils::bigint::{BigInt, BigUint};
+//!
+//! assert_eq!("2432902008176640000", BigUint::factorial(20).to_string());
+//! assert_eq!("100891344545564193334812497256", BigUint::binomial(100, 50).to_string());
+//!
+//! let a: BigInt = "-123456789012345678901234567890".parse().unwrap();
+//! let b = BigInt::from(987654321);
+//! assert_eq!("-121932631124828532112482853211126352690", (&a * &b).to_string());
+//! assert_eq!(BigInt::from(-124999998), &a / &b / BigInt::from(1_000_000_000_000i64));
+//! assert_eq!("-0x18ee90ff6c373e0ee4e3f0ad2", format!("{:#x}", a));
+//! ```
+//!
+use std::{error::Error, fmt};
+
+pub use signed::BigInt;
+pub use unsigned::BigUint;
+
+// 为`&a op &b`之外的3种组合以及`op=`生成转发的impl，宏需要在子模块之前定义
+macro_rules! forward_binop {
+    ($t:ty, $($imp:ident $method:ident $assign_imp:ident $assign_method:ident $f:path;)*) => {$(
+        impl std::ops::$imp<&$t> for &$t {
+            type Output = $t;
+
+            fn $method(self, rhs: &$t) -> $t {
+                $f(self, rhs)
+            }
+        }
+
+        impl std::ops::$imp<$t> for $t {
+            type Output = $t;
+
+            fn $method(self, rhs: $t) -> $t {
+                $f(&self, &rhs)
+            }
+        }
+
+        impl std::ops::$imp<&$t> for $t {
+            type Output = $t;
+
+            fn $method(self, rhs: &$t) -> $t {
+                $f(&self, rhs)
+            }
+        }
+
+        impl std::ops::$imp<$t> for &$t {
+            type Output = $t;
+
+            fn $method(self, rhs: $t) -> $t {
+                $f(self, &rhs)
+            }
+        }
+
+        impl std::ops::$assign_imp<&$t> for $t {
+            fn $assign_method(&mut self, rhs: &$t) {
+                *self = $f(self, rhs);
+            }
+        }
+
+        impl std::ops::$assign_imp<$t> for $t {
+            fn $assign_method(&mut self, rhs: $t) {
+                *self = $f(self, &rhs);
+            }
+        }
+    )*};
+}
+
+mod arith;
+pub mod signed;
+pub mod unsigned;
+
+/// error returned when parsing a [BigUint] or [BigInt]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseBigIntError {
+    /// no digits
+    Empty,
+    /// `ch` at byte offset `index` is not a digit of the radix
+    InvalidDigit { index: usize, ch: char },
+}
+
+impl fmt::Display for ParseBigIntError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseBigIntError::Empty => write!(f, "cannot parse integer from empty string"),
+            ParseBigIntError::InvalidDigit { index, ch } => {
+                write!(f, "invalid digit {:?} at index {}", ch, index)
+            }
+        }
+    }
+}
+
+impl Error for ParseBigIntError {}
+
+#[cfg(test)]
+mod tests {
+    use num::{bigint::Sign, Integer, Signed};
+
+    use super::*;
+
+    /// 用LCG生成各种长度的随机数，包含很多0和u32::MAX，容易触发进位和除法的边界情况
+    struct Gen(u32);
+
+    impl Gen {
+        fn next(&mut self) -> u32 {
+            self.0 = self.0.wrapping_mul(1103515245).wrapping_add(12345);
+            self.0.rotate_left(15)
+        }
+
+        fn biguint(&mut self) -> BigUint {
+            let len = match self.next() % 10 {
+                0 => 0,
+                1..=6 => self.next() as usize % 4,
+                _ => self.next() as usize % 90,
+            };
+            let mut s = String::from("0");
+            for _ in 0..len {
+                let limb = match self.next() % 4 {
+                    0 => u32::MAX,
+                    1 => 0,
+                    _ => self.next(),
+                };
+                s.push_str(&format!("{:08x}", limb));
+            }
+            BigUint::from_str_radix(&s, 16).unwrap()
+        }
+
+        fn bigint(&mut self) -> BigInt {
+            let m = self.biguint();
+            BigInt::from_biguint(self.next().is_multiple_of(2), m)
+        }
+    }
+
+    fn to_num(x: &BigInt) -> num::BigInt {
+        num::BigInt::parse_bytes(x.to_str_radix(16).as_bytes(), 16).unwrap()
+    }
+
+    fn check(expected: &num::BigInt, actual: &BigInt) {
+        assert_eq!(expected.to_str_radix(16), actual.to_str_radix(16));
+        assert_eq!(expected.to_string(), actual.to_string());
+    }
+
+    #[test]
+    fn matches_num_bigint() {
+        let mut g = Gen(42);
+        for _ in 0..400 {
+            let (a, b) = (g.bigint(), g.bigint());
+            let (na, nb) = (to_num(&a), to_num(&b));
+            check(&(&na + &nb), &(&a + &b));
+            check(&(&na - &nb), &(&a - &b));
+            check(&(&na * &nb), &(&a * &b));
+            assert_eq!(na.cmp(&nb), a.cmp(&b));
+            if !b.is_zero() {
+                // num的/和%同样向0截断
+                check(&(&na / &nb), &(&a / &b));
+                check(&(&na % &nb), &(&a % &b));
+            }
+            let e = g.next() % 5;
+            check(&na.pow(e), &a.pow(e));
+            check(&-&na, &-&a);
+            assert_eq!(na.sign() == Sign::Minus, a.is_negative());
+            assert_eq!(na.is_negative(), a < BigInt::default());
+        }
+    }
+
+    #[test]
+    fn unsigned_division_identity() {
+        let mut g = Gen(7);
+        for _ in 0..300 {
+            let (a, b) = (g.biguint(), g.biguint());
+            if b.is_zero() {
+                assert_eq!(None, a.checked_div(&b));
+                continue;
+            }
+            let (q, r) = a.div_rem(&b);
+            assert!(r < b);
+            assert_eq!(a, &q * &b + &r);
+            let (nq, nr) = to_num(&a.clone().into()).div_rem(&to_num(&b.clone().into()));
+            check(&nq, &q.into());
+            check(&nr, &r.into());
+        }
+    }
+
+    #[test]
+    fn combinatorics() {
+        let mut f = num::BigUint::from(1u32);
+        for n in 0..120u32 {
+            if n > 0 {
+                f *= n;
+            }
+            assert_eq!(f.to_string(), BigUint::factorial(n).to_string());
+        }
+        for (n, k) in [(0, 0), (10, 3), (60, 30), (200, 7), (5, 6)] {
+            let expected = if k > n {
+                num::BigUint::from(0u32)
+            } else {
+                let fact = |x: u32| (1..=x).map(num::BigUint::from).product::<num::BigUint>();
+                fact(n) / (fact(k) * fact(n - k))
+            };
+            assert_eq!(expected.to_string(), BigUint::binomial(n, k).to_string());
+        }
+    }
+}
@@ -0,0 +1,315 @@
+//!
+//! algorithms on little-endian base 2³² limbs
+//!
+//! 除非特别说明，输入可以带有高位的0，输出总是去掉了高位的0
+//!
+use std::cmp::Ordering;
+
+/// operands shorter than this are multiplied by the schoolbook method
+pub(super) const KARATSUBA_THRESHOLD: usize = 32;
+
+pub(super) fn normalize(v: &mut Vec<u32>) {
+    while v.last() == Some(&0) {
+        v.pop();
+    }
+}
+
+fn trim(v: &[u32]) -> &[u32] {
+    let len = v.iter().rposition(|&x| x != 0).map_or(0, |i| i + 1);
+    &v[..len]
+}
+
+pub(super) fn cmp(a: &[u32], b: &[u32]) -> Ordering {
+    let (a, b) = (trim(a), trim(b));
+    a.len().cmp(&b.len()).then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+pub(super) fn add(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let (a, b) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let mut r = Vec::with_capacity(a.len() + 1);
+    let mut carry = 0u64;
+    for (i, &x) in a.iter().enumerate() {
+        let s = x as u64 + b.get(i).copied().unwrap_or(0) as u64 + carry;
+        r.push(s as u32);
+        carry = s >> 32;
+    }
+    r.push(carry as u32);
+    normalize(&mut r);
+    r
+}
+
+/// `acc += x << (32 * offset)`, `acc` must be long enough to hold the result
+fn add_at(acc: &mut [u32], x: &[u32], offset: usize) {
+    let mut carry = 0u64;
+    let mut i = offset;
+    for &y in x {
+        let s = acc[i] as u64 + y as u64 + carry;
+        acc[i] = s as u32;
+        carry = s >> 32;
+        i += 1;
+    }
+    while carry != 0 {
+        let s = acc[i] as u64 + carry;
+        acc[i] = s as u32;
+        carry = s >> 32;
+        i += 1;
+    }
+}
+
+/// `a - b`, requires `a >= b`
+pub(super) fn sub(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut r = a.to_vec();
+    sub_assign(&mut r, b);
+    normalize(&mut r);
+    r
+}
+
+/// `a -= b`, requires `a >= b`
+fn sub_assign(a: &mut [u32], b: &[u32]) {
+    let mut borrow = false;
+    for (i, x) in a.iter_mut().enumerate() {
+        let y = b.get(i).copied().unwrap_or(0);
+        if i >= b.len() && !borrow {
+            break;
+        }
+        let (d, o1) = x.overflowing_sub(y);
+        let (d, o2) = d.overflowing_sub(borrow as u32);
+        *x = d;
+        borrow = o1 || o2;
+    }
+    debug_assert!(!borrow, "subtrahend is larger than minuend");
+}
+
+pub(super) fn mul(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let (a, b) = (trim(a), trim(b));
+    let (a, b) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    if b.is_empty() {
+        return Vec::new();
+    }
+    if b.len() < KARATSUBA_THRESHOLD {
+        return schoolbook(a, b);
+    }
+    if a.len() >= 2 * b.len() {
+        // 长度相差很大时，把长的按短的长度分块，每块和短的做Karatsuba
+        let mut r = vec![0; a.len() + b.len()];
+        for (i, chunk) in a.chunks(b.len()).enumerate() {
+            add_at(&mut r, &mul(chunk, b), i * b.len());
+        }
+        normalize(&mut r);
+        return r;
+    }
+    karatsuba(a, b)
+}
+
+fn schoolbook(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut r = vec![0u32; a.len() + b.len()];
+    for (i, &x) in a.iter().enumerate() {
+        if x == 0 {
+            continue;
+        }
+        let mut carry = 0u64;
+        for (j, &y) in b.iter().enumerate() {
+            // (2³² - 1)² + 2 * (2³² - 1) = 2⁶⁴ - 1，不会溢出
+            let t = r[i + j] as u64 + x as u64 * y as u64 + carry;
+            r[i + j] = t as u32;
+            carry = t >> 32;
+        }
+        r[i + b.len()] = carry as u32;
+    }
+    normalize(&mut r);
+    r
+}
+
+///
+/// `a * b` with 3 recursive multiplications of half size, O(n^1.585)
+///
+/// a = a1 * B + a0, b = b1 * B + b0，则
+/// a * b = z2 * B² + z1 * B + z0，其中z2 = a1 * b1, z0 = a0 * b0, z1 = (a0 + a1)(b0 + b1) - z2 - z0
+///
+/// 要求`a.len() >= b.len() > a.len() / 2`
+///
+fn karatsuba(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let m = a.len() / 2;
+    let (a0, a1) = a.split_at(m);
+    let (b0, b1) = b.split_at(m);
+    let z0 = mul(a0, b0);
+    let z2 = mul(a1, b1);
+    let mut z1 = mul(&add(a0, a1), &add(b0, b1));
+    sub_assign(&mut z1, &z0);
+    sub_assign(&mut z1, &z2);
+    normalize(&mut z1);
+
+    let mut r = vec![0; a.len() + b.len()];
+    add_at(&mut r, &z0, 0);
+    add_at(&mut r, &z1, m);
+    add_at(&mut r, &z2, 2 * m);
+    normalize(&mut r);
+    r
+}
+
+/// `v = v * m + a`
+pub(super) fn mul_add_small(v: &mut Vec<u32>, m: u32, a: u32) {
+    let mut carry = a as u64;
+    for x in v.iter_mut() {
+        let t = *x as u64 * m as u64 + carry;
+        *x = t as u32;
+        carry = t >> 32;
+    }
+    if carry != 0 {
+        v.push(carry as u32);
+    }
+    normalize(v);
+}
+
+/// `(a / d, a % d)`, `d` must not be 0
+pub(super) fn div_rem_small(a: &[u32], d: u32) -> (Vec<u32>, u32) {
+    let mut q = vec![0; a.len()];
+    let mut rem = 0u64;
+    for (i, &x) in a.iter().enumerate().rev() {
+        let cur = rem << 32 | x as u64;
+        q[i] = (cur / d as u64) as u32;
+        rem = cur % d as u64;
+    }
+    normalize(&mut q);
+    (q, rem as u32)
+}
+
+///
+/// `(a / b, a % b)` by Knuth's algorithm D, `b` must not be 0
+///
+/// 先把除数左移使最高位为1，这样每一步用被除数的前两位除以除数的最高位估计商，
+/// 估计值最多比真实值大2，再用除数的次高位修正，剩下极少数情况在相减为负时加回
+///
+pub(super) fn div_rem(a: &[u32], b: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    let (a, b) = (trim(a), trim(b));
+    assert!(!b.is_empty(), "attempt to divide by zero");
+    if cmp(a, b) == Ordering::Less {
+        return (Vec::new(), a.to_vec());
+    }
+    if b.len() == 1 {
+        let (q, r) = div_rem_small(a, b[0]);
+        let r = if r == 0 { Vec::new() } else { vec![r] };
+        return (q, r);
+    }
+
+    let shift = b[b.len() - 1].leading_zeros();
+    let mut b = shl_bits(b, shift);
+    b.pop();
+    let mut a = shl_bits(a, shift);
+    let n = b.len();
+    let m = a.len() - n;
+    let mut q = vec![0u32; m];
+    let (top, second) = (b[n - 1] as u64, b[n - 2] as u64);
+    for j in (0..m).rev() {
+        let num = (a[j + n] as u64) << 32 | a[j + n - 1] as u64;
+        let mut qhat = num / top;
+        let mut rhat = num % top;
+        while qhat > u32::MAX as u64 || qhat * second > (rhat << 32 | a[j + n - 2] as u64) {
+            qhat -= 1;
+            rhat += top;
+            if rhat > u32::MAX as u64 {
+                break;
+            }
+        }
+
+        // a[j..=j + n] -= qhat * b
+        let mut borrow = 0i64;
+        let mut carry = 0u64;
+        for i in 0..n {
+            let p = qhat * b[i] as u64 + carry;
+            carry = p >> 32;
+            let t = a[i + j] as i64 - borrow - (p & 0xffff_ffff) as i64;
+            a[i + j] = t as u32;
+            borrow = (t < 0) as i64;
+        }
+        let t = a[j + n] as i64 - borrow - carry as i64;
+        a[j + n] = t as u32;
+
+        if t < 0 {
+            // 估计值大了1，加回一个除数
+            qhat -= 1;
+            let mut c = 0u64;
+            for i in 0..n {
+                let s = a[i + j] as u64 + b[i] as u64 + c;
+                a[i + j] = s as u32;
+                c = s >> 32;
+            }
+            a[j + n] = a[j + n].wrapping_add(c as u32);
+        }
+        q[j] = qhat as u32;
+    }
+    normalize(&mut q);
+    let mut r = shr_bits(&a[..n], shift);
+    normalize(&mut r);
+    (q, r)
+}
+
+/// `v << shift` with one extra limb on top, `shift < 32`
+fn shl_bits(v: &[u32], shift: u32) -> Vec<u32> {
+    let mut r = Vec::with_capacity(v.len() + 1);
+    let mut carry = 0u32;
+    for &x in v {
+        if shift == 0 {
+            r.push(x);
+        } else {
+            r.push(x << shift | carry);
+            carry = x >> (32 - shift);
+        }
+    }
+    r.push(carry);
+    r
+}
+
+/// `v >> shift`, `shift < 32`
+fn shr_bits(v: &[u32], shift: u32) -> Vec<u32> {
+    if shift == 0 {
+        return v.to_vec();
+    }
+    let mut r = vec![0; v.len()];
+    for i in 0..v.len() {
+        let high = v.get(i + 1).map_or(0, |&h| h << (32 - shift));
+        r[i] = v[i] >> shift | high;
+    }
+    r
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn karatsuba_matches_schoolbook() {
+        let mut x: u32 = 7;
+        let mut limbs = |n: usize| -> Vec<u32> {
+            (0..n)
+                .map(|_| {
+                    x = x.wrapping_mul(1103515245).wrapping_add(12345);
+                    // 多取一些极端值
+                    match x % 5 {
+                        0 => u32::MAX,
+                        1 => 0,
+                        _ => x.rotate_left(13),
+                    }
+                })
+                .collect()
+        };
+        for (m, n) in [(32, 32), (33, 40), (100, 64), (200, 33), (64, 31)] {
+            let (a, b) = (limbs(m), limbs(n));
+            let mut expected = schoolbook(&a, &b);
+            normalize(&mut expected);
+            assert_eq!(expected, mul(&a, &b), "{}x{}", m, n);
+        }
+    }
+
+    #[test]
+    fn division_add_back() {
+        // Hacker's Delight中需要加回的例子，换成32位的limb
+        let a = [0, 0, 0x8000_0000, 0x7fff_ffff];
+        let b = [1, 0, 0x8000_0000];
+        let (q, r) = div_rem(&a, &b);
+        assert_eq!(vec![0xffff_fffe], q);
+        assert_eq!(Ordering::Less, cmp(&r, &b));
+        assert_eq!(Ordering::Equal, cmp(&add(&mul(&q, &b), &r), &a));
+        assert_eq!((Vec::new(), vec![5]), div_rem(&[5, 0], &[7]));
+    }
+}
@@ -0,0 +1,337 @@
+use std::{cmp::Ordering, fmt, iter, ops::Neg, str::FromStr};
+
+use super::{BigUint, ParseBigIntError};
+
+///
+/// signed integer of arbitrary size, stored as sign and magnitude
+///
+/// `/`和`%`与原生整数一样向0截断，余数的符号和被除数相同
+///
+/// ```
+/// use basic_utils::bigint::BigInt;
+///
+/// let a = BigInt::from(-7);
+/// let b = BigInt::from(2);
+/// assert_eq!(BigInt::from(-3), &a / &b);
+/// assert_eq!(BigInt::from(-1), &a % &b);
+/// assert_eq!("-170141183460469231731687303715884105728", BigInt::from(i128::MIN).to_string());
+/// assert_eq!(Some(i128::MIN), BigInt::from(i128::MIN).to_i128());
+/// ```
+///
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+pub struct BigInt {
+    /// always false for 0
+    negative: bool,
+    magnitude: BigUint,
+}
+
+impl BigInt {
+    pub const ZERO: BigInt = BigInt {
+        negative: false,
+        magnitude: BigUint::ZERO,
+    };
+
+    /// `-magnitude` if `negative`, otherwise `magnitude`
+    pub fn from_biguint(negative: bool, magnitude: BigUint) -> Self {
+        BigInt {
+            negative: negative && !magnitude.is_zero(),
+            magnitude,
+        }
+    }
+
+    pub fn one() -> Self {
+        BigInt::from(BigUint::one())
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.magnitude.is_zero()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    /// the absolute value
+    pub fn magnitude(&self) -> &BigUint {
+        &self.magnitude
+    }
+
+    pub fn into_magnitude(self) -> BigUint {
+        self.magnitude
+    }
+
+    pub fn abs(&self) -> BigInt {
+        BigInt::from(self.magnitude.clone())
+    }
+
+    /// -1, 0 or 1
+    pub fn signum(&self) -> i32 {
+        match (self.negative, self.is_zero()) {
+            (true, _) => -1,
+            (false, true) => 0,
+            (false, false) => 1,
+        }
+    }
+
+    pub fn to_i64(&self) -> Option<i64> {
+        let m = self.magnitude.to_u64()?;
+        if self.negative {
+            0i64.checked_sub_unsigned(m)
+        } else {
+            i64::try_from(m).ok()
+        }
+    }
+
+    pub fn to_i128(&self) -> Option<i128> {
+        let m = self.magnitude.to_u128()?;
+        if self.negative {
+            0i128.checked_sub_unsigned(m)
+        } else {
+            i128::try_from(m).ok()
+        }
+    }
+
+    ///
+    /// `(self / rhs, self % rhs)`, truncated toward zero
+    ///
+    /// # Panics
+    ///
+    /// panics if `rhs` is 0
+    ///
+    pub fn div_rem(&self, rhs: &BigInt) -> (BigInt, BigInt) {
+        let (q, r) = self.magnitude.div_rem(&rhs.magnitude);
+        (
+            BigInt::from_biguint(self.negative != rhs.negative, q),
+            BigInt::from_biguint(self.negative, r),
+        )
+    }
+
+    /// `self / rhs`, None if `rhs` is 0
+    pub fn checked_div(&self, rhs: &BigInt) -> Option<BigInt> {
+        (!rhs.is_zero()).then(|| self.div_rem(rhs).0)
+    }
+
+    /// `self % rhs`, None if `rhs` is 0
+    pub fn checked_rem(&self, rhs: &BigInt) -> Option<BigInt> {
+        (!rhs.is_zero()).then(|| self.div_rem(rhs).1)
+    }
+
+    /// `self` raised to the power `exp`, `0⁰ = 1`
+    pub fn pow(&self, exp: u32) -> BigInt {
+        BigInt::from_biguint(self.negative && exp % 2 == 1, self.magnitude.pow(exp))
+    }
+
+    ///
+    /// parse an optional sign followed by digits of `radix`
+    ///
+    /// # Panics
+    ///
+    /// panics if `radix` is not in `2..=36`
+    ///
+    pub fn from_str_radix(s: &str, radix: u32) -> Result<BigInt, ParseBigIntError> {
+        match s.strip_prefix('-') {
+            Some(rest) => {
+                let m = BigUint::from_str_radix(rest, radix).map_err(|e| match e {
+                    ParseBigIntError::Empty => ParseBigIntError::InvalidDigit { index: 0, ch: '-' },
+                    ParseBigIntError::InvalidDigit { index, ch } => {
+                        ParseBigIntError::InvalidDigit { index: index + 1, ch }
+                    }
+                })?;
+                Ok(BigInt::from_biguint(true, m))
+            }
+            None => BigUint::from_str_radix(s, radix).map(BigInt::from),
+        }
+    }
+
+    /// digits in `radix` with lowercase letters, prefixed by `-` if negative
+    pub fn to_str_radix(&self, radix: u32) -> String {
+        let digits = self.magnitude.to_str_radix(radix);
+        if self.negative {
+            format!("-{}", digits)
+        } else {
+            digits
+        }
+    }
+}
+
+fn add(a: &BigInt, b: &BigInt) -> BigInt {
+    if a.negative == b.negative {
+        return BigInt::from_biguint(a.negative, &a.magnitude + &b.magnitude);
+    }
+    // 符号不同时用绝对值大的减去小的，结果的符号和绝对值大的相同
+    match a.magnitude.cmp(&b.magnitude) {
+        Ordering::Less => BigInt::from_biguint(b.negative, &b.magnitude - &a.magnitude),
+        _ => BigInt::from_biguint(a.negative, &a.magnitude - &b.magnitude),
+    }
+}
+
+fn sub(a: &BigInt, b: &BigInt) -> BigInt {
+    add(a, &-b)
+}
+
+fn mul(a: &BigInt, b: &BigInt) -> BigInt {
+    BigInt::from_biguint(a.negative != b.negative, &a.magnitude * &b.magnitude)
+}
+
+fn div(a: &BigInt, b: &BigInt) -> BigInt {
+    a.div_rem(b).0
+}
+
+fn rem(a: &BigInt, b: &BigInt) -> BigInt {
+    a.div_rem(b).1
+}
+
+forward_binop!(BigInt,
+    Add add AddAssign add_assign add;
+    Sub sub SubAssign sub_assign sub;
+    Mul mul MulAssign mul_assign mul;
+    Div div DivAssign div_assign div;
+    Rem rem RemAssign rem_assign rem;
+);
+
+impl Neg for &BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        BigInt::from_biguint(!self.negative, self.magnitude.clone())
+    }
+}
+
+impl Neg for BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        BigInt::from_biguint(!self.negative, self.magnitude)
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, false) => self.magnitude.cmp(&other.magnitude),
+            (true, true) => other.magnitude.cmp(&self.magnitude),
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+        }
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl From<BigUint> for BigInt {
+    fn from(magnitude: BigUint) -> Self {
+        BigInt::from_biguint(false, magnitude)
+    }
+}
+
+macro_rules! impl_from_int {
+    ($($t:ty)*) => {$(
+        impl From<$t> for BigInt {
+            fn from(n: $t) -> Self {
+                let negative = n < 0;
+                BigInt::from_biguint(negative, BigUint::from(n.unsigned_abs()))
+            }
+        }
+    )*};
+}
+
+impl_from_int!(i8 i16 i32 i64 i128 isize);
+
+macro_rules! impl_from_uint {
+    ($($t:ty)*) => {$(
+        impl From<$t> for BigInt {
+            fn from(n: $t) -> Self {
+                BigInt::from(BigUint::from(n))
+            }
+        }
+    )*};
+}
+
+impl_from_uint!(u8 u16 u32 u64 u128 usize);
+
+impl FromStr for BigInt {
+    type Err = ParseBigIntError;
+
+    /// decimal digits with an optional sign
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        BigInt::from_str_radix(s, 10)
+    }
+}
+
+impl iter::Sum for BigInt {
+    fn sum<I: Iterator<Item = BigInt>>(iter: I) -> Self {
+        iter.fold(BigInt::ZERO, |acc, x| acc + x)
+    }
+}
+
+impl iter::Product for BigInt {
+    fn product<I: Iterator<Item = BigInt>>(iter: I) -> Self {
+        iter.fold(BigInt::one(), |acc, x| acc * x)
+    }
+}
+
+impl fmt::Display for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad_integral(!self.negative, "", &self.magnitude.to_str_radix(10))
+    }
+}
+
+impl fmt::Debug for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl fmt::LowerHex for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad_integral(!self.negative, "0x", &self.magnitude.to_str_radix(16))
+    }
+}
+
+impl fmt::UpperHex for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad_integral(!self.negative, "0x", &self.magnitude.to_str_radix(16).to_uppercase())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signs() {
+        assert_eq!(BigInt::ZERO, -BigInt::ZERO);
+        assert!(!(-BigInt::ZERO).is_negative());
+        assert_eq!(BigInt::ZERO, BigInt::from(5) - BigInt::from(5));
+        assert_eq!(BigInt::from(-2), BigInt::from(3) + BigInt::from(-5));
+        assert!(BigInt::from(-10) < BigInt::from(-9));
+        assert!(BigInt::from(-1) < BigInt::ZERO);
+        assert_eq!(-1, BigInt::from(-3).signum());
+        assert_eq!(BigInt::from(-8), BigInt::from(-2).pow(3));
+        assert_eq!(BigInt::from(16), BigInt::from(-2).pow(4));
+        assert_eq!(Some(i64::MIN), BigInt::from(i64::MIN).to_i64());
+        assert_eq!(None, (BigInt::from(i64::MAX) + BigInt::one()).to_i64());
+        assert_eq!(BigInt::from(120), (1..=5).map(BigInt::from).product());
+    }
+
+    #[test]
+    fn parse_and_format() {
+        assert_eq!(Ok(BigInt::from(-255)), BigInt::from_str_radix("-FF", 16));
+        assert_eq!(Ok(BigInt::from(7)), "+7".parse());
+        assert_eq!(
+            Err(ParseBigIntError::InvalidDigit { index: 0, ch: '-' }),
+            "-".parse::<BigInt>()
+        );
+        assert_eq!(
+            Err(ParseBigIntError::InvalidDigit { index: 2, ch: 'x' }),
+            "-1x".parse::<BigInt>()
+        );
+        assert_eq!("-0xff", format!("{:#x}", BigInt::from(-255)));
+        assert_eq!("-00042", format!("{:06}", BigInt::from(-42)));
+        assert_eq!("+42", format!("{:+}", BigInt::from(42)));
+    }
+}
@@ -0,0 +1,348 @@
+use std::{cmp::Ordering, fmt, iter, str::FromStr};
+
+use super::{arith, ParseBigIntError};
+
+///
+/// non-negative integer of arbitrary size
+///
+/// 运算符的行为和无符号整数一致：减法的结果为负或者除以0时panic，需要检查时使用`checked_*`
+///
+/// ```
+/// use basic_utils::bigint::BigUint;
+///
+/// let a = BigUint::from(u64::MAX);
+/// let b = &a * &a;
+/// assert_eq!("340282366920938463426481119284349108225", b.to_string());
+/// assert_eq!(Some(u64::MAX), (&b / &a).to_u64());
+/// assert_eq!(None, a.checked_sub(&b));
+/// assert_eq!(Ok(BigUint::from(255u8)), BigUint::from_str_radix("ff", 16));
+/// ```
+///
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+pub struct BigUint {
+    /// little-endian base 2³² digits without leading zeros, empty for 0
+    pub(super) digits: Vec<u32>,
+}
+
+impl BigUint {
+    pub const ZERO: BigUint = BigUint { digits: Vec::new() };
+
+    fn from_digits(mut digits: Vec<u32>) -> Self {
+        arith::normalize(&mut digits);
+        BigUint { digits }
+    }
+
+    pub fn one() -> Self {
+        BigUint { digits: vec![1] }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.digits.is_empty()
+    }
+
+    /// number of bits needed to represent the value, 0 for 0
+    pub fn bits(&self) -> u64 {
+        self.digits
+            .last()
+            .map_or(0, |&top| self.digits.len() as u64 * 32 - top.leading_zeros() as u64)
+    }
+
+    pub fn to_u64(&self) -> Option<u64> {
+        match self.digits[..] {
+            [] => Some(0),
+            [a] => Some(a as u64),
+            [a, b] => Some((b as u64) << 32 | a as u64),
+            _ => None,
+        }
+    }
+
+    pub fn to_u128(&self) -> Option<u128> {
+        if self.digits.len() > 4 {
+            return None;
+        }
+        Some(self.digits.iter().rev().fold(0, |acc, &d| acc << 32 | d as u128))
+    }
+
+    /// `self - rhs`, None if the result would be negative
+    pub fn checked_sub(&self, rhs: &BigUint) -> Option<BigUint> {
+        match arith::cmp(&self.digits, &rhs.digits) {
+            Ordering::Less => None,
+            _ => Some(BigUint::from_digits(arith::sub(&self.digits, &rhs.digits))),
+        }
+    }
+
+    ///
+    /// `(self / rhs, self % rhs)`
+    ///
+    /// # Panics
+    ///
+    /// panics if `rhs` is 0
+    ///
+    pub fn div_rem(&self, rhs: &BigUint) -> (BigUint, BigUint) {
+        let (q, r) = arith::div_rem(&self.digits, &rhs.digits);
+        (BigUint::from_digits(q), BigUint::from_digits(r))
+    }
+
+    /// `self / rhs`, None if `rhs` is 0
+    pub fn checked_div(&self, rhs: &BigUint) -> Option<BigUint> {
+        (!rhs.is_zero()).then(|| self.div_rem(rhs).0)
+    }
+
+    /// `self % rhs`, None if `rhs` is 0
+    pub fn checked_rem(&self, rhs: &BigUint) -> Option<BigUint> {
+        (!rhs.is_zero()).then(|| self.div_rem(rhs).1)
+    }
+
+    /// `self` raised to the power `exp` by repeated squaring, `0⁰ = 1`
+    pub fn pow(&self, mut exp: u32) -> BigUint {
+        let mut result = BigUint::one();
+        let mut base = self.clone();
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = &result * &base;
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base = &base * &base;
+            }
+        }
+        result
+    }
+
+    /// `n!`
+    pub fn factorial(n: u32) -> BigUint {
+        let mut digits = vec![1];
+        for i in 2..=n {
+            arith::mul_add_small(&mut digits, i, 0);
+        }
+        BigUint { digits }
+    }
+
+    ///
+    /// binomial coefficient `C(n, k)`, 0 if `k > n`
+    ///
+    /// 依次计算C(n, 1), C(n, 2) ... C(n, k)，`C(n, i + 1) = C(n, i) * (n - i) / (i + 1)`每一步都能整除
+    ///
+    pub fn binomial(n: u32, k: u32) -> BigUint {
+        if k > n {
+            return BigUint::ZERO;
+        }
+        let k = k.min(n - k);
+        let mut digits = vec![1];
+        for i in 0..k {
+            arith::mul_add_small(&mut digits, n - i, 0);
+            digits = arith::div_rem_small(&digits, i + 1).0;
+        }
+        BigUint { digits }
+    }
+
+    ///
+    /// parse digits of `radix`, letters are case-insensitive and a leading `+` is allowed
+    ///
+    /// # Panics
+    ///
+    /// panics if `radix` is not in `2..=36`
+    ///
+    pub fn from_str_radix(s: &str, radix: u32) -> Result<BigUint, ParseBigIntError> {
+        assert!((2..=36).contains(&radix), "radix must be in 2..=36, got {}", radix);
+        let (offset, body) = match s.strip_prefix('+') {
+            Some(rest) => (1, rest),
+            None => (0, s),
+        };
+        if body.is_empty() {
+            return Err(match s.is_empty() {
+                true => ParseBigIntError::Empty,
+                false => ParseBigIntError::InvalidDigit { index: 0, ch: '+' },
+            });
+        }
+        let mut digits = Vec::new();
+        for (i, ch) in body.char_indices() {
+            let d = ch
+                .to_digit(radix)
+                .ok_or(ParseBigIntError::InvalidDigit { index: offset + i, ch })?;
+            arith::mul_add_small(&mut digits, radix, d);
+        }
+        Ok(BigUint { digits })
+    }
+
+    ///
+    /// digits in `radix` with lowercase letters
+    ///
+    /// # Panics
+    ///
+    /// panics if `radix` is not in `2..=36`
+    ///
+    pub fn to_str_radix(&self, radix: u32) -> String {
+        assert!((2..=36).contains(&radix), "radix must be in 2..=36, got {}", radix);
+        if self.is_zero() {
+            return String::from("0");
+        }
+        // 每次除以u32范围内radix的最大次幂，一次得到多位
+        let (mut chunk, mut width) = (radix, 1);
+        while let Some(next) = chunk.checked_mul(radix) {
+            chunk = next;
+            width += 1;
+        }
+        let mut out = Vec::new();
+        let mut rest = self.digits.clone();
+        while !rest.is_empty() {
+            let (q, mut r) = arith::div_rem_small(&rest, chunk);
+            rest = q;
+            for _ in 0..width {
+                if rest.is_empty() && r == 0 {
+                    break;
+                }
+                out.push(char::from_digit(r % radix, radix).expect("digit < radix"));
+                r /= radix;
+            }
+        }
+        out.iter().rev().collect()
+    }
+}
+
+fn add(a: &BigUint, b: &BigUint) -> BigUint {
+    BigUint::from_digits(arith::add(&a.digits, &b.digits))
+}
+
+fn sub(a: &BigUint, b: &BigUint) -> BigUint {
+    a.checked_sub(b).expect("attempt to subtract with overflow")
+}
+
+fn mul(a: &BigUint, b: &BigUint) -> BigUint {
+    BigUint::from_digits(arith::mul(&a.digits, &b.digits))
+}
+
+fn div(a: &BigUint, b: &BigUint) -> BigUint {
+    a.div_rem(b).0
+}
+
+fn rem(a: &BigUint, b: &BigUint) -> BigUint {
+    a.div_rem(b).1
+}
+
+forward_binop!(BigUint,
+    Add add AddAssign add_assign add;
+    Sub sub SubAssign sub_assign sub;
+    Mul mul MulAssign mul_assign mul;
+    Div div DivAssign div_assign div;
+    Rem rem RemAssign rem_assign rem;
+);
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        arith::cmp(&self.digits, &other.digits)
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+macro_rules! impl_from_unsigned {
+    ($($t:ty)*) => {$(
+        impl From<$t> for BigUint {
+            fn from(n: $t) -> Self {
+                let mut n = n as u128;
+                let digits = iter::from_fn(|| {
+                    (n != 0).then(|| {
+                        let d = n as u32;
+                        n >>= 32;
+                        d
+                    })
+                });
+                BigUint { digits: digits.collect() }
+            }
+        }
+    )*};
+}
+
+impl_from_unsigned!(u8 u16 u32 u64 u128 usize);
+
+impl FromStr for BigUint {
+    type Err = ParseBigIntError;
+
+    /// decimal digits
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        BigUint::from_str_radix(s, 10)
+    }
+}
+
+impl iter::Sum for BigUint {
+    fn sum<I: Iterator<Item = BigUint>>(iter: I) -> Self {
+        iter.fold(BigUint::ZERO, |acc, x| acc + x)
+    }
+}
+
+impl iter::Product for BigUint {
+    fn product<I: Iterator<Item = BigUint>>(iter: I) -> Self {
+        iter.fold(BigUint::one(), |acc, x| acc * x)
+    }
+}
+
+impl fmt::Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad_integral(true, "", &self.to_str_radix(10))
+    }
+}
+
+impl fmt::Debug for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl fmt::LowerHex for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad_integral(true, "0x", &self.to_str_radix(16))
+    }
+}
+
+impl fmt::UpperHex for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad_integral(true, "0x", &self.to_str_radix(16).to_uppercase())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_and_format() {
+        let n: BigUint = "000123456789012345678901234567890".parse().unwrap();
+        assert_eq!("123456789012345678901234567890", n.to_string());
+        assert_eq!("18EE90FF6C373E0EE4E3F0AD2", format!("{:X}", n));
+        assert_eq!("0x0000ff", format!("{:#08x}", BigUint::from(255u32)));
+        assert_eq!("   42", format!("{:>5}", BigUint::from(42u8)));
+        assert_eq!("0", BigUint::ZERO.to_string());
+        assert_eq!("1010", BigUint::from(10u8).to_str_radix(2));
+        assert_eq!(Ok(BigUint::from(35u8)), BigUint::from_str_radix("+Z", 36));
+        assert_eq!(Err(ParseBigIntError::Empty), "".parse::<BigUint>());
+        assert_eq!(
+            Err(ParseBigIntError::InvalidDigit { index: 0, ch: '+' }),
+            "+".parse::<BigUint>()
+        );
+        assert_eq!(
+            Err(ParseBigIntError::InvalidDigit { index: 2, ch: '-' }),
+            "12-3".parse::<BigUint>()
+        );
+        let u = u128::MAX;
+        assert_eq!(Some(u), BigUint::from(u).to_u128());
+        assert_eq!(128, BigUint::from(u).bits());
+        assert_eq!(None, (BigUint::from(u) + BigUint::one()).to_u128());
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to subtract with overflow")]
+    fn negative_result() {
+        let _ = BigUint::from(1u8) - BigUint::from(2u8);
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to divide by zero")]
+    fn divide_by_zero() {
+        let _ = BigUint::from(1u8) / BigUint::ZERO;
+    }
+}
@@ -0,0 +1,606 @@
+//!
+//! parse config values and query parameters
+//!
+//! * [convert] / [try_convert] 任意实现了[FromStr]的类型，输入原样交给[FromStr]
+//! * [convert_int] / [try_convert_int] 整数，支持`0x` `0o` `0b`前缀以及`_`分隔符
+//! * [convert_count] `10k` `2.5M`
+//! * [convert_bytes] `512` `10KB` `2MiB`
+//! * [convert_duration] `1.5s` `250ms` `1h30m`
+//!
+//! 解析失败时[ConvertError]会给出出错的位置(相对于原始输入的字节偏移)以及原因
+//!
+use std::{
+    error::Error,
+    fmt,
+    num::{IntErrorKind, ParseIntError},
+    str::FromStr,
+    time::Duration,
+};
+
+/// the reason why a conversion failed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConvertErrorKind {
+    /// input is empty or only contains whitespace
+    Empty,
+    /// character is not a valid digit for the radix
+    InvalidDigit(char),
+    /// `_` is not between two digits
+    MisplacedSeparator,
+    /// value does not fit in the target type
+    Overflow,
+    /// unit suffix is missing or unknown
+    InvalidUnit(String),
+    /// value has a fractional part but an integer is required
+    Fractional,
+    /// error reported by [FromStr] of the target type
+    Parse(String),
+}
+
+impl fmt::Display for ConvertErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConvertErrorKind::Empty => write!(f, "empty input"),
+            ConvertErrorKind::InvalidDigit(c) => write!(f, "invalid digit {:?}", c),
+            ConvertErrorKind::MisplacedSeparator => write!(f, "separator `_` must be between digits"),
+            ConvertErrorKind::Overflow => write!(f, "number too large"),
+            ConvertErrorKind::InvalidUnit(u) if u.is_empty() => write!(f, "missing unit"),
+            ConvertErrorKind::InvalidUnit(u) => write!(f, "unknown unit {:?}", u),
+            ConvertErrorKind::Fractional => write!(f, "fractional value is not allowed"),
+            ConvertErrorKind::Parse(reason) => write!(f, "{}", reason),
+        }
+    }
+}
+
+/// error returned by [try_convert] and the unit-aware converters
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConvertError {
+    position: usize,
+    kind: ConvertErrorKind,
+}
+
+impl ConvertError {
+    fn new(position: usize, kind: ConvertErrorKind) -> Self {
+        ConvertError { position, kind }
+    }
+
+    /// byte offset in the original input where the error was detected
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn kind(&self) -> &ConvertErrorKind {
+        &self.kind
+    }
+}
+
+impl fmt::Display for ConvertError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at position {}", self.kind, self.position)
+    }
+}
+
+impl Error for ConvertError {}
+
+///
+/// convert &str to i32, if err exists return default
+///
+/// ```
+/// let r1 = basic_utils::convert_to_i32(0, "a");
+/// assert_eq!(0, r1);
+///
+/// let r2 = basic_utils::convert_to_i32(0, "123");
+/// assert_eq!(123, r2);
+///
+/// ```
+///
+pub fn convert_to_i32(default: i32, str: &str) -> i32 {
+    convert_int(str).unwrap_or(default)
+}
+
+///
+/// convert &str to any [FromStr] type, return None if failed
+///
+/// 输入原样交给[FromStr]，不会去掉空白或`_`，整数的前缀和分隔符见[convert_int]
+///
+/// ```
+/// use basic_utils::converter::convert;
+///
+/// assert_eq!(Some(2.5f64), convert("2.5"));
+/// assert_eq!(Some(String::from("0xff")), convert("0xff"));
+/// assert_eq!(None, convert::<u8>("256"));
+/// ```
+///
+pub fn convert<T: FromStr>(s: &str) -> Option<T> {
+    s.parse().ok()
+}
+
+///
+/// convert &str to any [FromStr] type, return default if failed
+///
+pub fn convert_or<T: FromStr>(default: T, s: &str) -> T {
+    convert(s).unwrap_or(default)
+}
+
+///
+/// convert &str to any [FromStr] type, the error of [FromStr] is reported at position 0
+///
+/// ```
+/// use basic_utils::converter::{try_convert, ConvertErrorKind};
+///
+/// assert_eq!(Ok(1.5f32), try_convert("1.5"));
+///
+/// let err = try_convert::<f32>("1.5x").unwrap_err();
+/// assert_eq!(&ConvertErrorKind::Parse(String::from("invalid float literal")), err.kind());
+/// ```
+///
+pub fn try_convert<T>(s: &str) -> Result<T, ConvertError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    s.parse()
+        .map_err(|e: T::Err| ConvertError::new(0, ConvertErrorKind::Parse(e.to_string())))
+}
+
+///
+/// convert &str to an integer, `0x` `0o` `0b` prefixes, `_` separators and surrounding whitespace are accepted.
+/// return None if failed
+///
+/// ```
+/// use basic_utils::converter::convert_int;
+///
+/// assert_eq!(Some(255u8), convert_int("0xff"));
+/// assert_eq!(Some(-5i64), convert_int("-0b101"));
+/// assert_eq!(Some(1_000_000u32), convert_int(" 1_000_000 "));
+/// assert_eq!(None, convert_int::<u8>("256"));
+/// ```
+///
+pub fn convert_int<T: FromStr<Err = ParseIntError>>(s: &str) -> Option<T> {
+    try_convert_int(s).ok()
+}
+
+///
+/// convert &str to an integer like [convert_int], report where and why it failed
+///
+/// ```
+/// use basic_utils::converter::{try_convert_int, ConvertErrorKind};
+///
+/// assert_eq!(Ok(0o17u16), try_convert_int("0o17"));
+///
+/// let err = try_convert_int::<i32>("0x1g").unwrap_err();
+/// assert_eq!(3, err.position());
+/// assert_eq!(&ConvertErrorKind::InvalidDigit('g'), err.kind());
+///
+/// // 溢出指向数字的开始
+/// let err = try_convert_int::<u8>("0x100").unwrap_err();
+/// assert_eq!((2, &ConvertErrorKind::Overflow), (err.position(), err.kind()));
+/// ```
+///
+pub fn try_convert_int<T: FromStr<Err = ParseIntError>>(s: &str) -> Result<T, ConvertError> {
+    let (text, start, digits_start) = normalize(s)?;
+    text.parse().map_err(|e: ParseIntError| {
+        if matches!(e.kind(), IntErrorKind::PosOverflow | IntErrorKind::NegOverflow) {
+            return ConvertError::new(digits_start, ConvertErrorKind::Overflow);
+        }
+        // 找到第一个不可能出现在数字中的字符，否则指向数字的开始
+        let bad = s[start..]
+            .char_indices()
+            .find(|&(_, c)| !(c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '_')));
+        match bad {
+            Some((i, c)) if !c.is_whitespace() => ConvertError::new(start + i, ConvertErrorKind::InvalidDigit(c)),
+            _ => ConvertError::new(start, ConvertErrorKind::Parse(e.to_string())),
+        }
+    })
+}
+
+///
+/// 去掉空白、`_`分隔符，并把带前缀的整数转换为10进制文本。
+/// 返回文本、去掉空白后在原始输入中的开始位置，以及跳过符号和前缀后数字的开始位置
+///
+fn normalize(s: &str) -> Result<(String, usize, usize), ConvertError> {
+    let start = s.len() - s.trim_start().len();
+    let trimmed = s.trim();
+    if trimmed.is_empty() {
+        return Err(ConvertError::new(0, ConvertErrorKind::Empty));
+    }
+
+    let (sign, unsigned) = match trimmed.as_bytes()[0] {
+        b'-' => ("-", &trimmed[1..]),
+        b'+' => ("", &trimmed[1..]),
+        _ => ("", trimmed),
+    };
+    let body_start = start + trimmed.len() - unsigned.len();
+    if let Some(c @ ('+' | '-')) = unsigned.chars().next() {
+        return Err(ConvertError::new(body_start, ConvertErrorKind::InvalidDigit(c)));
+    }
+
+    let radix = match unsigned.get(..2) {
+        Some("0x") | Some("0X") => 16,
+        Some("0o") | Some("0O") => 8,
+        Some("0b") | Some("0B") => 2,
+        _ => 10,
+    };
+    if radix == 10 {
+        let digits = strip_separators(unsigned, body_start)?;
+        return Ok((format!("{}{}", sign, digits), start, body_start));
+    }
+
+    let digits_start = body_start + 2;
+    let (value, end) = scan_integer(&unsigned[2..], digits_start, radix)?;
+    if end == digits_start {
+        let kind = match unsigned[2..].chars().next() {
+            Some(c) => ConvertErrorKind::InvalidDigit(c),
+            None => ConvertErrorKind::Empty,
+        };
+        return Err(ConvertError::new(digits_start, kind));
+    }
+    if end < start + trimmed.len() {
+        let c = s[end..].chars().next().unwrap_or_default();
+        return Err(ConvertError::new(end, ConvertErrorKind::InvalidDigit(c)));
+    }
+    Ok((format!("{}{}", sign, value), start, digits_start))
+}
+
+/// 删除`_`，要求`_`两侧都是数字
+fn strip_separators(s: &str, offset: usize) -> Result<String, ConvertError> {
+    let bytes = s.as_bytes();
+    let mut r = String::with_capacity(s.len());
+    for (i, c) in s.char_indices() {
+        if c == '_' {
+            let prev = i > 0 && bytes[i - 1].is_ascii_alphanumeric();
+            let next = i + 1 < bytes.len() && bytes[i + 1].is_ascii_alphanumeric();
+            if !prev || !next {
+                return Err(ConvertError::new(offset + i, ConvertErrorKind::MisplacedSeparator));
+            }
+        } else {
+            r.push(c);
+        }
+    }
+    Ok(r)
+}
+
+/// 从开头读取尽可能多的数字(允许`_`分隔符)，返回数值以及结束位置(相对于原始输入)
+fn scan_integer(s: &str, offset: usize, radix: u32) -> Result<(u128, usize), ConvertError> {
+    let bytes = s.as_bytes();
+    let mut value: u128 = 0;
+    let mut end = 0;
+    for (i, c) in s.char_indices() {
+        if c == '_' {
+            let next = bytes.get(i + 1).is_some_and(|b| (*b as char).is_digit(radix));
+            if i == 0 || !next {
+                return Err(ConvertError::new(offset + i, ConvertErrorKind::MisplacedSeparator));
+            }
+        } else if let Some(d) = c.to_digit(radix) {
+            value = value
+                .checked_mul(radix as u128)
+                .and_then(|v| v.checked_add(d as u128))
+                .ok_or(ConvertError::new(offset + i, ConvertErrorKind::Overflow))?;
+        } else {
+            break;
+        }
+        end = i + 1;
+    }
+    Ok((value, offset + end))
+}
+
+/// 数字部分：整数以及小数部分的数字
+struct Decimal<'a> {
+    whole: u128,
+    fraction: &'a str,
+    // 小数点的位置，用于报告Fractional错误
+    dot: usize,
+}
+
+impl Decimal<'_> {
+    /// whole.fraction * scale，截断多余的精度；exact为true时不允许截断
+    fn scale(&self, scale: u128, exact: bool) -> Result<u128, ConvertError> {
+        let overflow = || ConvertError::new(self.dot, ConvertErrorKind::Overflow);
+        let whole = self.whole.checked_mul(scale).ok_or_else(overflow)?;
+        // 只保留前38位小数，避免10^n溢出u128。不要求精确时，超过scale位数的小数乘以scale后不足1，
+        // 直接截断，避免numerator * scale溢出
+        let digits = match exact {
+            true => 38,
+            false => scale.ilog10() as usize + 1,
+        };
+        let fraction = &self.fraction[..self.fraction.len().min(digits)];
+        if fraction.is_empty() {
+            return Ok(whole);
+        }
+        let denominator = 10u128.pow(fraction.len() as u32);
+        let numerator: u128 = fraction.parse::<u128>().unwrap_or_default();
+        let scaled = numerator.checked_mul(scale).ok_or_else(overflow)?;
+        if exact && (scaled % denominator != 0 || fraction.len() < self.fraction.len()) {
+            return Err(ConvertError::new(self.dot, ConvertErrorKind::Fractional));
+        }
+        whole.checked_add(scaled / denominator).ok_or_else(overflow)
+    }
+}
+
+/// 读取`123`, `1_000`, `1.5`这样的数字，返回数字以及结束位置
+fn scan_decimal(s: &str, pos: usize) -> Result<(Decimal<'_>, usize), ConvertError> {
+    let (whole, end) = scan_integer(&s[pos..], pos, 10)?;
+    let mut decimal = Decimal {
+        whole,
+        fraction: "",
+        dot: end,
+    };
+    if end == pos {
+        let kind = match s[pos..].chars().next() {
+            Some(c) => ConvertErrorKind::InvalidDigit(c),
+            None => ConvertErrorKind::Empty,
+        };
+        return Err(ConvertError::new(pos, kind));
+    }
+    if s[end..].starts_with('.') {
+        let digits = s[end + 1..].bytes().take_while(u8::is_ascii_digit).count();
+        if digits == 0 {
+            return Err(ConvertError::new(end, ConvertErrorKind::InvalidDigit('.')));
+        }
+        decimal.fraction = &s[end + 1..end + 1 + digits];
+        return Ok((decimal, end + 1 + digits));
+    }
+    Ok((decimal, end))
+}
+
+/// 读取数字后面的单位，允许数字和单位之间有空格
+fn scan_unit(s: &str, pos: usize) -> (&str, usize, usize) {
+    let rest = &s[pos..];
+    let unit_start = pos + rest.len() - rest.trim_start().len();
+    let len = s[unit_start..]
+        .char_indices()
+        .find(|&(_, c)| !c.is_alphabetic())
+        .map_or(s.len() - unit_start, |(i, _)| i);
+    (&s[unit_start..unit_start + len], unit_start, unit_start + len)
+}
+
+fn checked_input(s: &str) -> Result<(), ConvertError> {
+    if s.trim().is_empty() {
+        Err(ConvertError::new(0, ConvertErrorKind::Empty))
+    } else {
+        Ok(())
+    }
+}
+
+fn trailing_error(s: &str, end: usize) -> Result<(), ConvertError> {
+    match s[end..].trim_end().chars().next() {
+        Some(c) => Err(ConvertError::new(end, ConvertErrorKind::InvalidDigit(c))),
+        None => Ok(()),
+    }
+}
+
+/// `(value, unit)` 对应的倍数
+fn unit_scale(units: &[(&str, u128)], unit: &str) -> Option<u128> {
+    units.iter().find(|(u, _)| *u == unit).map(|(_, scale)| *scale)
+}
+
+///
+/// convert a count with an optional SI suffix: `k`/`K`, `M`, `G`, `T`, `P`, `E`
+///
+/// ```
+/// use basic_utils::converter::convert_count;
+///
+/// assert_eq!(Ok(10_000), convert_count("10k"));
+/// assert_eq!(Ok(2_500_000), convert_count("2.5M"));
+/// assert_eq!(Ok(42), convert_count("42"));
+/// assert!(convert_count("1.5").is_err());
+/// ```
+///
+pub fn convert_count(s: &str) -> Result<u64, ConvertError> {
+    const UNITS: &[(&str, u128)] = &[
+        ("", 1),
+        ("k", 1_000),
+        ("K", 1_000),
+        ("M", 1_000_000),
+        ("G", 1_000_000_000),
+        ("T", 1_000_000_000_000),
+        ("P", 1_000_000_000_000_000),
+        ("E", 1_000_000_000_000_000_000),
+    ];
+    convert_scaled(s, UNITS, |u| u.to_string())
+}
+
+///
+/// convert a byte count, decimal (`KB`, `MB`, ...) and binary (`KiB`, `MiB`, ...) units are supported.
+/// units are case insensitive, a bare number means bytes.
+///
+/// ```
+/// use basic_utils::converter::convert_bytes;
+///
+/// assert_eq!(Ok(2 * 1024 * 1024), convert_bytes("2MiB"));
+/// assert_eq!(Ok(10_000), convert_bytes("10 kB"));
+/// assert_eq!(Ok(1536), convert_bytes("1.5KiB"));
+/// assert_eq!(Ok(512), convert_bytes("512"));
+/// ```
+///
+pub fn convert_bytes(s: &str) -> Result<u64, ConvertError> {
+    const UNITS: &[(&str, u128)] = &[
+        ("", 1),
+        ("b", 1),
+        ("kb", 1_000),
+        ("mb", 1_000_000),
+        ("gb", 1_000_000_000),
+        ("tb", 1_000_000_000_000),
+        ("pb", 1_000_000_000_000_000),
+        ("eb", 1_000_000_000_000_000_000),
+        ("kib", 1 << 10),
+        ("mib", 1 << 20),
+        ("gib", 1 << 30),
+        ("tib", 1 << 40),
+        ("pib", 1 << 50),
+        ("eib", 1 << 60),
+    ];
+    convert_scaled(s, UNITS, str::to_ascii_lowercase)
+}
+
+fn convert_scaled(s: &str, units: &[(&str, u128)], fold: impl Fn(&str) -> String) -> Result<u64, ConvertError> {
+    checked_input(s)?;
+    let start = s.len() - s.trim_start().len();
+    let (decimal, end) = scan_decimal(s, start)?;
+    let (unit, unit_start, unit_end) = scan_unit(s, end);
+    trailing_error(s, unit_end)?;
+
+    let scale = unit_scale(units, &fold(unit))
+        .ok_or_else(|| ConvertError::new(unit_start, ConvertErrorKind::InvalidUnit(unit.to_string())))?;
+    let value = decimal.scale(scale, true)?;
+    u64::try_from(value).map_err(|_| ConvertError::new(start, ConvertErrorKind::Overflow))
+}
+
+///
+/// convert a duration such as `1.5s`, `250ms` or `1h30m`.
+///
+/// supported units: `ns`, `us`/`µs`, `ms`, `s`, `m`, `h`, `d`
+///
+/// ```
+/// use std::time::Duration;
+/// use basic_utils::converter::convert_duration;
+///
+/// assert_eq!(Ok(Duration::from_millis(1500)), convert_duration("1.5s"));
+/// assert_eq!(Ok(Duration::from_millis(250)), convert_duration("250ms"));
+/// assert_eq!(Ok(Duration::from_secs(5400)), convert_duration("1h 30m"));
+///
+/// let err = convert_duration("10").unwrap_err();
+/// assert_eq!(2, err.position());
+/// ```
+///
+pub fn convert_duration(s: &str) -> Result<Duration, ConvertError> {
+    const UNITS: &[(&str, u128)] = &[
+        ("ns", 1),
+        ("us", 1_000),
+        ("µs", 1_000),
+        ("ms", 1_000_000),
+        ("s", 1_000_000_000),
+        ("m", 60 * 1_000_000_000),
+        ("h", 60 * 60 * 1_000_000_000),
+        ("d", 24 * 60 * 60 * 1_000_000_000),
+    ];
+    checked_input(s)?;
+
+    let mut nanos: u128 = 0;
+    let mut pos = s.len() - s.trim_start().len();
+    let end = s.trim_end().len();
+    while pos < end {
+        let (decimal, number_end) = scan_decimal(s, pos)?;
+        let (unit, unit_start, unit_end) = scan_unit(s, number_end);
+        let scale = unit_scale(UNITS, unit)
+            .ok_or_else(|| ConvertError::new(unit_start, ConvertErrorKind::InvalidUnit(unit.to_string())))?;
+        nanos = decimal
+            .scale(scale, false)?
+            .checked_add(nanos)
+            .ok_or(ConvertError::new(pos, ConvertErrorKind::Overflow))?;
+        pos = unit_end + s[unit_end..].len() - s[unit_end..].trim_start().len();
+    }
+
+    let secs = u64::try_from(nanos / 1_000_000_000).map_err(|_| ConvertError::new(0, ConvertErrorKind::Overflow))?;
+    Ok(Duration::new(secs, (nanos % 1_000_000_000) as u32))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn err<T>(r: Result<T, ConvertError>) -> (usize, ConvertErrorKind) {
+        match r {
+            Ok(_) => panic!("expect error"),
+            Err(e) => (e.position, e.kind),
+        }
+    }
+
+    #[test]
+    fn radix_and_separator() {
+        assert_eq!(Some(0xdead_beefu32), convert_int("0xDEAD_BEEF"));
+        assert_eq!(Some(-8i8), convert_int("-0o10"));
+        assert_eq!(
+            Some(i128::MIN),
+            convert_int("-0x8000_0000_0000_0000_0000_0000_0000_0000")
+        );
+        assert_eq!(None, convert_int::<u8>("-1"));
+        assert_eq!(None, convert_int::<i32>("+-1"));
+        assert_eq!(7, convert_or(7, "x"));
+        assert_eq!(12, convert_to_i32(0, " 1_2 "));
+    }
+
+    #[test]
+    fn pass_through() {
+        assert_eq!(Some(String::from("0xff")), convert("0xff"));
+        assert_eq!(Some(String::from(" 1_000 ")), convert(" 1_000 "));
+        assert_eq!(Some(12.5f32), convert("12.5"));
+        assert_eq!(None, convert::<f32>("1_2.5"));
+        assert_eq!(None, convert::<i32>(" 1"));
+        assert_eq!(
+            (0, ConvertErrorKind::Parse(String::from("invalid float literal"))),
+            err(try_convert::<f64>("0x1"))
+        );
+    }
+
+    #[test]
+    fn error_position() {
+        assert_eq!((0, ConvertErrorKind::Empty), err(try_convert_int::<i32>("   ")));
+        assert_eq!(
+            (3, ConvertErrorKind::MisplacedSeparator),
+            err(try_convert_int::<i32>(" 12_"))
+        );
+        assert_eq!(
+            (1, ConvertErrorKind::MisplacedSeparator),
+            err(try_convert_int::<i32>("1__2"))
+        );
+        assert_eq!(
+            (5, ConvertErrorKind::InvalidDigit('2')),
+            err(try_convert_int::<i32>("0b1012"))
+        );
+        assert_eq!(
+            (2, ConvertErrorKind::InvalidDigit('z')),
+            err(try_convert_int::<i32>("0xz"))
+        );
+        assert_eq!(
+            (2, ConvertErrorKind::InvalidDigit('?')),
+            err(try_convert_int::<i32>("12?"))
+        );
+        // FromStr报告的溢出指向数字的开始，跳过符号和前缀
+        assert_eq!((2, ConvertErrorKind::Overflow), err(try_convert_int::<u8>("0x100")));
+        assert_eq!((2, ConvertErrorKind::Overflow), err(try_convert_int::<i8>(" -200")));
+        assert_eq!((0, ConvertErrorKind::Overflow), err(try_convert_int::<u16>("70_000")));
+        assert_eq!(
+            (42, ConvertErrorKind::Overflow),
+            err(try_convert_int::<u128>("0x1_0000_0000_0000_0000_0000_0000_0000_0000"))
+        );
+    }
+
+    #[test]
+    fn units() {
+        assert_eq!(Ok(3_000_000_000), convert_count("3G"));
+        assert_eq!(
+            (1, ConvertErrorKind::InvalidUnit(String::from("x"))),
+            err(convert_count("1x"))
+        );
+        assert_eq!((1, ConvertErrorKind::Fractional), err(convert_count("1.0001k")));
+        assert_eq!((0, ConvertErrorKind::Overflow), err(convert_count("100E")));
+        assert_eq!(Ok(3 << 30), convert_bytes("3 gib"));
+        assert_eq!(Ok(1), convert_bytes("1B"));
+        assert_eq!((4, ConvertErrorKind::InvalidDigit('!')), err(convert_bytes("1 kb!")));
+    }
+
+    #[test]
+    fn duration() {
+        assert_eq!(Ok(Duration::from_nanos(1_500)), convert_duration("1.5us"));
+        assert_eq!(Ok(Duration::from_secs(86_400 + 1)), convert_duration(" 1d1s "));
+        assert_eq!(Ok(Duration::from_millis(100)), convert_duration("0.1s"));
+        assert_eq!(
+            (1, ConvertErrorKind::InvalidUnit(String::from("y"))),
+            err(convert_duration("1y"))
+        );
+        assert_eq!(
+            (4, ConvertErrorKind::InvalidUnit(String::new())),
+            err(convert_duration("1s 2"))
+        );
+        assert_eq!((1, ConvertErrorKind::InvalidDigit('.')), err(convert_duration("1.s")));
+        // 超过纳秒精度的小数被截断，不会溢出
+        assert_eq!(
+            Ok(Duration::from_nanos(123_456_789)),
+            convert_duration("0.1234567890123456789012345678901234s")
+        );
+        assert_eq!(
+            Ok(Duration::from_secs(5400)),
+            convert_duration("1.50000000000000000000000000000000001h")
+        );
+    }
+}
@@ -0,0 +1,45 @@
+use std::ops::{Bound, Range, RangeBounds};
+
+pub use binary_tree::{BinaryTreeNode, ParseTreeError};
+pub use bst::{BstMap, BstSet};
+pub use disjoint_set::{DenseDisjointSet, DisjointSet};
+pub use fenwick_tree::FenwickTree;
+pub use linked_list::{LinkedList, ListNode};
+pub use lru_cache::{EvictionReason, LruCache};
+pub use segment_tree::{Gcd, Max, Min, Operation, RangeAdd, SegmentTree, Sum};
+pub use traversal::{InOrder, LevelOrder, Levels, PostOrder, PreOrder};
+pub use tree_arena::{TreeArena, TreeId};
+pub use trie::{Symbols, Trie};
+
+pub mod binary_tree;
+pub mod bst;
+pub mod disjoint_set;
+pub mod fenwick_tree;
+pub mod linked_list;
+pub mod lru_cache;
+pub mod segment_tree;
+pub mod traversal;
+pub mod tree_arena;
+pub mod trie;
+
+/// convert `range` to `start..end`, panic like slice indexing if it is not within `0..len`
+fn index_range(range: impl RangeBounds<usize>, len: usize) -> Range<usize> {
+    let start = match range.start_bound() {
+        Bound::Included(&s) => s,
+        Bound::Excluded(&s) => s + 1,
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&e) => e + 1,
+        Bound::Excluded(&e) => e,
+        Bound::Unbounded => len,
+    };
+    assert!(
+        start <= end && end <= len,
+        "range {}..{} out of bounds for length {}",
+        start,
+        end,
+        len
+    );
+    start..end
+}
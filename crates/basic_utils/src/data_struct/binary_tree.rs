@@ -0,0 +1,398 @@
+use std::{cell::RefCell, collections::VecDeque, error::Error, fmt, rc::Rc, str::FromStr};
+
+use super::{InOrder, Levels};
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct BinaryTreeNode<T = i32> {
+    pub left: Option<Rc<RefCell<BinaryTreeNode<T>>>>,
+    pub right: Option<Rc<RefCell<BinaryTreeNode<T>>>>,
+    pub val: T,
+}
+
+impl<T> BinaryTreeNode<T> {
+    #[inline]
+    pub fn new(val: T) -> Self {
+        BinaryTreeNode {
+            left: None,
+            right: None,
+            val,
+        }
+    }
+
+    #[inline]
+    pub fn from(
+        left: Option<Rc<RefCell<BinaryTreeNode<T>>>>,
+        right: Option<Rc<RefCell<BinaryTreeNode<T>>>>,
+        val: T,
+    ) -> Self {
+        BinaryTreeNode { left, right, val }
+    }
+
+    #[inline]
+    pub fn set_left(&mut self, l: &Rc<RefCell<BinaryTreeNode<T>>>) {
+        self.left = Some(Rc::clone(l));
+    }
+
+    #[inline]
+    pub fn set_left_none(&mut self) {
+        self.left = None;
+    }
+
+    #[inline]
+    pub fn set_right(&mut self, l: &Rc<RefCell<BinaryTreeNode<T>>>) {
+        self.right = Some(Rc::clone(l));
+    }
+
+    #[inline]
+    pub fn set_right_none(&mut self) {
+        self.right = None;
+    }
+
+    /// number of nodes on the longest path from root to a leaf, 0 for an empty tree
+    pub fn height(root: Option<&Rc<RefCell<BinaryTreeNode<T>>>>) -> usize {
+        Levels::new(root).count()
+    }
+
+    ///
+    /// check the in-order values are strictly increasing, works for hand-built trees
+    ///
+    /// ```
+    /// use basic_utils::data_struct::BinaryTreeNode;
+    ///
+    /// let root = BinaryTreeNode::from_level_order(&[Some(5), Some(1), Some(4), None, None, Some(3), Some(6)]).unwrap();
+    /// assert!(!BinaryTreeNode::is_valid_bst(root.as_ref()));
+    ///
+    /// let root = BinaryTreeNode::from_level_order(&[Some(2), Some(1), Some(3)]).unwrap();
+    /// assert!(BinaryTreeNode::is_valid_bst(root.as_ref()));
+    /// assert_eq!(2, BinaryTreeNode::height(root.as_ref()));
+    /// ```
+    ///
+    pub fn is_valid_bst(root: Option<&Rc<RefCell<BinaryTreeNode<T>>>>) -> bool
+    where
+        T: Ord,
+    {
+        let mut prev: Option<Rc<RefCell<BinaryTreeNode<T>>>> = None;
+        for node in InOrder::new(root) {
+            if let Some(p) = prev {
+                if p.borrow().val >= node.borrow().val {
+                    return false;
+                }
+            }
+            prev = Some(node);
+        }
+        true
+    }
+
+    ///
+    /// render a tree as ascii art, a missing child of a node with only one child is shown as `null`
+    ///
+    /// ```text
+    /// 1
+    /// +-- 2
+    /// |   +-- null
+    /// |   `-- 5
+    /// `-- 3
+    /// ```
+    ///
+    pub fn to_pretty_string(root: Option<&Rc<RefCell<BinaryTreeNode<T>>>>) -> String
+    where
+        T: fmt::Display,
+    {
+        let mut r = String::new();
+        match root {
+            Some(node) => {
+                r.push_str(&node.borrow().val.to_string());
+                r.push('\n');
+                pretty_children(node, "", &mut r);
+            }
+            None => r.push_str("null\n"),
+        }
+        r
+    }
+}
+
+/// level-order (LeetCode style) construction and serialization
+impl<T: Clone> BinaryTreeNode<T> {
+    ///
+    /// build a tree from level-order values, None is a hole (LeetCode style)
+    ///
+    /// None节点没有子节点，所以不占用下一层的位置。挂不到任何父节点上的值(比如根是None后面还有值)
+    /// 返回[ParseTreeError::Unattached]，末尾多余的None会被忽略。
+    ///
+    /// ```
+    /// use basic_utils::data_struct::{BinaryTreeNode, ParseTreeError};
+    ///
+    /// let root = BinaryTreeNode::from_level_order(&[Some(1), Some(2), Some(3), None, Some(5)]).unwrap();
+    /// let root = root.unwrap();
+    /// assert_eq!(5, root.borrow().left.as_ref().unwrap().borrow().right.as_ref().unwrap().borrow().val);
+    ///
+    /// assert_eq!(
+    ///     Err(ParseTreeError::Unattached { index: 1 }),
+    ///     BinaryTreeNode::from_level_order(&[None, Some(1)])
+    /// );
+    /// ```
+    ///
+    pub fn from_level_order(values: &[Option<T>]) -> Result<Option<Rc<RefCell<BinaryTreeNode<T>>>>, ParseTreeError> {
+        let Some(Some(first)) = values.first() else {
+            return unattached(values, 1).map(|_| None);
+        };
+        let root = Rc::new(RefCell::new(BinaryTreeNode::new(first.clone())));
+
+        let mut parents = VecDeque::new();
+        parents.push_back(Rc::clone(&root));
+        let mut index = 1;
+        while index < values.len() {
+            let Some(parent) = parents.pop_front() else {
+                return unattached(values, index).map(|_| Some(root));
+            };
+            let mut parent = parent.borrow_mut();
+            if let Some(val) = values[index].clone() {
+                let node = Rc::new(RefCell::new(BinaryTreeNode::new(val)));
+                parent.set_left(&node);
+                parents.push_back(node);
+            }
+            if let Some(Some(val)) = values.get(index + 1).cloned() {
+                let node = Rc::new(RefCell::new(BinaryTreeNode::new(val)));
+                parent.set_right(&node);
+                parents.push_back(node);
+            }
+            index += 2;
+        }
+        Ok(Some(root))
+    }
+
+    ///
+    /// build a tree from a level-order string such as `[1,2,3,null,5]`
+    ///
+    /// ```
+    /// use basic_utils::data_struct::BinaryTreeNode;
+    ///
+    /// let root = BinaryTreeNode::<i32>::from_level_order_str("[1, 2, 3, null, 5]").unwrap();
+    /// assert_eq!("[1,2,3,null,5]", BinaryTreeNode::to_level_order_string(root.as_ref()));
+    ///
+    /// assert!(BinaryTreeNode::<i32>::from_level_order_str("[]").unwrap().is_none());
+    /// assert!(BinaryTreeNode::<i32>::from_level_order_str("[1,x]").is_err());
+    /// ```
+    ///
+    pub fn from_level_order_str(s: &str) -> Result<Option<Rc<RefCell<BinaryTreeNode<T>>>>, ParseTreeError>
+    where
+        T: FromStr,
+    {
+        let values = parse_level_order(s)?;
+        Self::from_level_order(&values)
+    }
+
+    ///
+    /// serialize a tree to level-order values, trailing holes are trimmed
+    ///
+    /// 和[BinaryTreeNode::from_level_order]互为逆操作
+    ///
+    pub fn to_level_order(root: Option<&Rc<RefCell<BinaryTreeNode<T>>>>) -> Vec<Option<T>> {
+        let mut r = Vec::new();
+        let mut q = VecDeque::new();
+        q.push_back(root.map(Rc::clone));
+        while let Some(item) = q.pop_front() {
+            match item {
+                Some(node) => {
+                    let node = node.borrow();
+                    r.push(Some(node.val.clone()));
+                    q.push_back(node.left.clone());
+                    q.push_back(node.right.clone());
+                }
+                None => r.push(None),
+            }
+        }
+        while let Some(None) = r.last() {
+            r.pop();
+        }
+        r
+    }
+
+    ///
+    /// serialize a tree to `[1,2,3,null,5]`
+    ///
+    pub fn to_level_order_string(root: Option<&Rc<RefCell<BinaryTreeNode<T>>>>) -> String
+    where
+        T: fmt::Display,
+    {
+        let items: Vec<String> = Self::to_level_order(root)
+            .into_iter()
+            .map(|v| v.map_or_else(|| String::from("null"), |v| v.to_string()))
+            .collect();
+        format!("[{}]", items.join(","))
+    }
+}
+
+fn pretty_children<T: fmt::Display>(node: &Rc<RefCell<BinaryTreeNode<T>>>, prefix: &str, out: &mut String) {
+    let node = node.borrow();
+    if node.left.is_none() && node.right.is_none() {
+        return;
+    }
+    for (child, last) in [(node.left.as_ref(), false), (node.right.as_ref(), true)] {
+        out.push_str(prefix);
+        out.push_str(if last { "`-- " } else { "+-- " });
+        let next_prefix = format!("{}{}", prefix, if last { "    " } else { "|   " });
+        match child {
+            Some(c) => {
+                out.push_str(&c.borrow().val.to_string());
+                out.push('\n');
+                pretty_children(c, &next_prefix, out);
+            }
+            None => out.push_str("null\n"),
+        }
+    }
+}
+
+/// Err if a value from `start` on is not None, those values have no parent left to attach to
+fn unattached<T>(values: &[Option<T>], start: usize) -> Result<(), ParseTreeError> {
+    match values.iter().skip(start).position(Option::is_some) {
+        Some(i) => Err(ParseTreeError::Unattached { index: start + i }),
+        None => Ok(()),
+    }
+}
+
+fn parse_level_order<T: FromStr>(s: &str) -> Result<Vec<Option<T>>, ParseTreeError> {
+    let body = s
+        .trim()
+        .strip_prefix('[')
+        .and_then(|s| s.strip_suffix(']'))
+        .ok_or(ParseTreeError::MissingBracket)?;
+    if body.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    body.split(',')
+        .enumerate()
+        .map(|(index, item)| match item.trim() {
+            "null" => Ok(None),
+            v => v.parse().map(Some).map_err(|_| ParseTreeError::InvalidValue {
+                index,
+                value: v.to_string(),
+            }),
+        })
+        .collect()
+}
+
+/// error returned by [BinaryTreeNode::from_level_order_str]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseTreeError {
+    /// input is not wrapped in `[` and `]`
+    MissingBracket,
+    /// item at index can not be parsed and is not `null`
+    InvalidValue { index: usize, value: String },
+    /// item at index has no parent, e.g. it follows a `null` root or every node of the previous level is full
+    Unattached { index: usize },
+}
+
+impl fmt::Display for ParseTreeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseTreeError::MissingBracket => write!(f, "level order must be wrapped in [ and ]"),
+            ParseTreeError::InvalidValue { index, value } => {
+                write!(f, "invalid value {:?} at index {}", value, index)
+            }
+            ParseTreeError::Unattached { index } => write!(f, "value at index {} has no parent", index),
+        }
+    }
+}
+
+impl Error for ParseTreeError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(s: &str) -> String {
+        let root = BinaryTreeNode::<i32>::from_level_order_str(s).unwrap();
+        BinaryTreeNode::to_level_order_string(root.as_ref())
+    }
+
+    #[test]
+    fn level_order_round_trip() {
+        for s in [
+            "[]",
+            "[1]",
+            "[1,2,3,4,5,6]",
+            "[1,null,2,null,3]",
+            "[5,4,8,11,null,13,4,7,2,null,null,null,1]",
+            "[-1,-2,null,-3]",
+        ] {
+            assert_eq!(s, round_trip(s));
+        }
+        assert_eq!("[1,2]", round_trip(" [ 1 , 2 , null , null ] "));
+        assert_eq!("[]", round_trip("[null,null]"));
+    }
+
+    #[test]
+    fn unattached_values() {
+        let parse = BinaryTreeNode::<i32>::from_level_order_str;
+        // 根是null，后面的值没有父节点
+        assert_eq!(Err(ParseTreeError::Unattached { index: 1 }), parse("[null,1]"));
+        assert_eq!(Err(ParseTreeError::Unattached { index: 2 }), parse("[null,null,1]"));
+        // 1的两个子节点都是null，3没有父节点
+        assert_eq!(Err(ParseTreeError::Unattached { index: 3 }), parse("[1,null,null,3]"));
+        // 2的两个子节点都是null，之后没有父节点了，5没有父节点
+        assert_eq!(
+            Err(ParseTreeError::Unattached { index: 5 }),
+            parse("[1,2,null,null,null,5]")
+        );
+        assert_eq!("[1,2,3,null,null,5,6]", round_trip("[1,2,3,null,null,5,6,null,null]"));
+        assert_eq!(
+            "value at index 3 has no parent",
+            parse("[1,null,null,3]").unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn slice_form() {
+        let values = [Some(3), Some(9), Some(20), None, None, Some(15), Some(7)];
+        let root = BinaryTreeNode::from_level_order(&values).unwrap();
+        assert_eq!(values.to_vec(), BinaryTreeNode::to_level_order(root.as_ref()));
+
+        let hand_built = Rc::new(RefCell::new(BinaryTreeNode::new(1)));
+        hand_built
+            .borrow_mut()
+            .set_right(&Rc::new(RefCell::new(BinaryTreeNode::new(2))));
+        assert_eq!(
+            BinaryTreeNode::from_level_order(&[Some(1), None, Some(2)]),
+            Ok(Some(hand_built))
+        );
+    }
+
+    #[test]
+    fn parse_error() {
+        assert_eq!(
+            Err(ParseTreeError::MissingBracket),
+            BinaryTreeNode::<i32>::from_level_order_str("1,2")
+        );
+        assert_eq!(
+            Err(ParseTreeError::InvalidValue {
+                index: 2,
+                value: String::from("nul")
+            }),
+            BinaryTreeNode::<i32>::from_level_order_str("[1,2,nul]")
+        );
+        assert_eq!(
+            Err(ParseTreeError::InvalidValue {
+                index: 1,
+                value: String::new()
+            }),
+            BinaryTreeNode::<i32>::from_level_order_str("[1,]")
+        );
+    }
+
+    #[test]
+    fn pretty() {
+        let root = BinaryTreeNode::<i32>::from_level_order_str("[1,2,3,null,5,6]").unwrap();
+        let expected = "\
+1
++-- 2
+|   +-- null
+|   `-- 5
+`-- 3
+    +-- 6
+    `-- null
+";
+        assert_eq!(expected, BinaryTreeNode::to_pretty_string(root.as_ref()));
+        assert_eq!("null\n", BinaryTreeNode::<i32>::to_pretty_string(None));
+    }
+}
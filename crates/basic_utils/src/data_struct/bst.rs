@@ -0,0 +1,881 @@
+//!
+//! ordered map and set, balanced as an AVL tree
+//!
+//! 节点除了key和value还记录了子树的高度。
+//! 每次insert/remove之后，沿途的节点通过旋转保证左右子树的高度差不超过1，所以树高是O(log n)。
+//!
+//! 节点用Box独占左右子树，不像[super::BinaryTreeNode]那样用Rc<RefCell>共享，
+//! get/get_mut/range可以直接返回节点内部的引用，由借用检查保证安全。
+//! 需要和手工搭建的[super::BinaryTreeNode]互相转换时，使用`to_tree`/`from_tree`，转换保持树的形状不变。
+//!
+use std::{
+    borrow::Borrow,
+    cell::RefCell,
+    cmp::Ordering,
+    fmt,
+    ops::{Bound, RangeBounds},
+    rc::Rc,
+};
+
+use super::{BinaryTreeNode, PostOrder};
+
+/// node of [BstMap]
+#[derive(Debug)]
+struct Node<K, V> {
+    key: K,
+    value: V,
+    height: usize,
+    left: Link<K, V>,
+    right: Link<K, V>,
+}
+
+type Link<K, V> = Option<Box<Node<K, V>>>;
+
+type TreeNode<T> = Rc<RefCell<BinaryTreeNode<T>>>;
+
+fn height<K, V>(link: &Link<K, V>) -> usize {
+    link.as_ref().map_or(0, |n| n.height)
+}
+
+fn update_height<K, V>(node: &mut Node<K, V>) {
+    node.height = 1 + height(&node.left).max(height(&node.right));
+}
+
+fn balance_factor<K, V>(node: &Node<K, V>) -> isize {
+    height(&node.left) as isize - height(&node.right) as isize
+}
+
+//       n            l
+//      / \          / \
+//     l   c   =>   a   n
+//    / \              / \
+//   a   b            b   c
+fn rotate_right<K, V>(mut node: Box<Node<K, V>>) -> Box<Node<K, V>> {
+    let mut left = node.left.take().expect("rotate right without left child");
+    node.left = left.right.take();
+    update_height(&mut node);
+    left.right = Some(node);
+    update_height(&mut left);
+    left
+}
+
+fn rotate_left<K, V>(mut node: Box<Node<K, V>>) -> Box<Node<K, V>> {
+    let mut right = node.right.take().expect("rotate left without right child");
+    node.right = right.left.take();
+    update_height(&mut node);
+    right.left = Some(node);
+    update_height(&mut right);
+    right
+}
+
+fn rebalance<K, V>(mut node: Box<Node<K, V>>) -> Box<Node<K, V>> {
+    update_height(&mut node);
+    let bf = balance_factor(&node);
+    if bf > 1 {
+        // LR: 先把左子树左旋成LL
+        let left = node.left.take().unwrap();
+        let left = if balance_factor(&left) < 0 {
+            rotate_left(left)
+        } else {
+            left
+        };
+        node.left = Some(left);
+        rotate_right(node)
+    } else if bf < -1 {
+        let right = node.right.take().unwrap();
+        let right = if balance_factor(&right) > 0 {
+            rotate_right(right)
+        } else {
+            right
+        };
+        node.right = Some(right);
+        rotate_left(node)
+    } else {
+        node
+    }
+}
+
+fn insert<K: Ord, V>(link: Link<K, V>, key: K, value: V) -> (Box<Node<K, V>>, Option<V>) {
+    let Some(mut node) = link else {
+        let node = Node {
+            key,
+            value,
+            height: 1,
+            left: None,
+            right: None,
+        };
+        return (Box::new(node), None);
+    };
+    let old = match key.cmp(&node.key) {
+        Ordering::Equal => {
+            let old = std::mem::replace(&mut node.value, value);
+            return (node, Some(old));
+        }
+        Ordering::Less => {
+            let (left, old) = insert(node.left.take(), key, value);
+            node.left = Some(left);
+            old
+        }
+        Ordering::Greater => {
+            let (right, old) = insert(node.right.take(), key, value);
+            node.right = Some(right);
+            old
+        }
+    };
+    (rebalance(node), old)
+}
+
+/// remove the smallest node of the subtree, return (rest of the subtree, removed node)
+fn remove_min<K, V>(mut node: Box<Node<K, V>>) -> (Link<K, V>, Box<Node<K, V>>) {
+    match node.left.take() {
+        None => (node.right.take(), node),
+        Some(left) => {
+            let (left, min) = remove_min(left);
+            node.left = left;
+            (Some(rebalance(node)), min)
+        }
+    }
+}
+
+/// remove `key` from the subtree, return (rest of the subtree, removed node)
+fn remove<K, V, Q>(link: Link<K, V>, key: &Q) -> (Link<K, V>, Link<K, V>)
+where
+    K: Borrow<Q>,
+    Q: Ord + ?Sized,
+{
+    let Some(mut node) = link else {
+        return (None, None);
+    };
+    let removed = match key.cmp(node.key.borrow()) {
+        Ordering::Less => {
+            let (left, removed) = remove(node.left.take(), key);
+            node.left = left;
+            removed
+        }
+        Ordering::Greater => {
+            let (right, removed) = remove(node.right.take(), key);
+            node.right = right;
+            removed
+        }
+        Ordering::Equal => {
+            let replacement = match (node.left.take(), node.right.take()) {
+                (None, child) | (child, None) => child,
+                (Some(left), Some(right)) => {
+                    // 用右子树的最小节点替换被删除的节点
+                    let (right, mut min) = remove_min(right);
+                    min.left = Some(left);
+                    min.right = right;
+                    Some(rebalance(min))
+                }
+            };
+            return (replacement, Some(node));
+        }
+    };
+    (Some(rebalance(node)), removed)
+}
+
+/// copy the subtree into [BinaryTreeNode]s of the same shape
+fn to_tree<K, V, T>(link: &Link<K, V>, f: &impl Fn(&K, &V) -> T) -> Option<TreeNode<T>> {
+    let node = link.as_deref()?;
+    let tree = BinaryTreeNode::from(
+        to_tree(&node.left, f),
+        to_tree(&node.right, f),
+        f(&node.key, &node.value),
+    );
+    Some(Rc::new(RefCell::new(tree)))
+}
+
+///
+/// copy a [BinaryTreeNode] tree into nodes of the same shape, None if some node is not balanced
+///
+/// 按后序遍历，子树总是先于父节点建好并压栈，不会递归；遇到不平衡的节点立即返回，
+/// 所以一条很长的链也只会建到第三个节点
+///
+fn from_tree<K, V, T>(root: Option<&TreeNode<T>>, f: impl Fn(&T) -> (K, V)) -> Option<(Link<K, V>, usize)> {
+    let mut built: Vec<Box<Node<K, V>>> = Vec::new();
+    let mut len = 0;
+    for tree in PostOrder::new(root) {
+        let tree = RefCell::borrow(&tree);
+        let right = tree.right.as_ref().and_then(|_| built.pop());
+        let left = tree.left.as_ref().and_then(|_| built.pop());
+        let (key, value) = f(&tree.val);
+        let mut node = Box::new(Node {
+            key,
+            value,
+            height: 0,
+            left,
+            right,
+        });
+        update_height(&mut node);
+        if balance_factor(&node).abs() > 1 {
+            return None;
+        }
+        built.push(node);
+        len += 1;
+    }
+    Some((built.pop(), len))
+}
+
+///
+/// ordered map, all operations are O(log n)
+///
+/// ```
+/// use basic_utils::data_struct::BstMap;
+///
+/// let mut map = BstMap::new();
+/// for (i, name) in ["a", "b", "c", "d", "e"].into_iter().enumerate() {
+///     map.insert(i * 10, name);
+/// }
+/// assert_eq!(Some(&"c"), map.get(&20));
+/// assert_eq!(Some((&10, &"b")), map.floor(&15));
+/// assert_eq!(Some((&20, &"c")), map.ceiling(&15));
+/// assert_eq!(vec![&"b", &"c", &"d"], map.range(5..=30).map(|(_, v)| v).collect::<Vec<_>>());
+///
+/// assert_eq!(Some("a"), map.remove(&0));
+/// assert_eq!(Some((&10, &"b")), map.min());
+/// assert!(map.is_valid_bst());
+/// ```
+///
+pub struct BstMap<K, V> {
+    root: Link<K, V>,
+    len: usize,
+}
+
+impl<K, V> BstMap<K, V> {
+    pub fn new() -> Self {
+        BstMap { root: None, len: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.root = None;
+        self.len = 0;
+    }
+
+    /// height of the tree, 0 for an empty tree
+    pub fn height(&self) -> usize {
+        height(&self.root)
+    }
+
+    pub fn min(&self) -> Option<(&K, &V)> {
+        let mut node = self.root.as_deref()?;
+        while let Some(left) = node.left.as_deref() {
+            node = left;
+        }
+        Some((&node.key, &node.value))
+    }
+
+    pub fn max(&self) -> Option<(&K, &V)> {
+        let mut node = self.root.as_deref()?;
+        while let Some(right) = node.right.as_deref() {
+            node = right;
+        }
+        Some((&node.key, &node.value))
+    }
+
+    /// iterate all entries in key order
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter::new(self.root.as_deref())
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.iter().map(|(k, _)| k)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.iter().map(|(_, v)| v)
+    }
+}
+
+impl<K: Ord, V> BstMap<K, V> {
+    /// insert a key, return the old value if the key exists
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let (root, old) = insert(self.root.take(), key, value);
+        self.root = Some(root);
+        if old.is_none() {
+            self.len += 1;
+        }
+        old
+    }
+
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let (root, removed) = remove(self.root.take(), key);
+        self.root = root;
+        let removed = removed?;
+        self.len -= 1;
+        Some(removed.value)
+    }
+
+    fn find<Q>(&self, key: &Q) -> Option<&Node<K, V>>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut link = self.root.as_deref();
+        while let Some(node) = link {
+            link = match key.cmp(node.key.borrow()) {
+                Ordering::Less => node.left.as_deref(),
+                Ordering::Greater => node.right.as_deref(),
+                Ordering::Equal => return Some(node),
+            };
+        }
+        None
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.find(key).map(|n| &n.value)
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut link = self.root.as_deref_mut();
+        while let Some(node) = link {
+            link = match key.cmp(node.key.borrow()) {
+                Ordering::Less => node.left.as_deref_mut(),
+                Ordering::Greater => node.right.as_deref_mut(),
+                Ordering::Equal => return Some(&mut node.value),
+            };
+        }
+        None
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.find(key).is_some()
+    }
+
+    /// the greatest entry whose key is less than or equal to `key`
+    pub fn floor<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut r = None;
+        let mut link = self.root.as_deref();
+        while let Some(node) = link {
+            link = match key.cmp(node.key.borrow()) {
+                Ordering::Less => node.left.as_deref(),
+                Ordering::Greater => {
+                    r = Some(node);
+                    node.right.as_deref()
+                }
+                Ordering::Equal => return Some((&node.key, &node.value)),
+            };
+        }
+        r.map(|n| (&n.key, &n.value))
+    }
+
+    /// the least entry whose key is greater than or equal to `key`
+    pub fn ceiling<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut r = None;
+        let mut link = self.root.as_deref();
+        while let Some(node) = link {
+            link = match key.cmp(node.key.borrow()) {
+                Ordering::Less => {
+                    r = Some(node);
+                    node.left.as_deref()
+                }
+                Ordering::Greater => node.right.as_deref(),
+                Ordering::Equal => return Some((&node.key, &node.value)),
+            };
+        }
+        r.map(|n| (&n.key, &n.value))
+    }
+
+    /// iterate entries whose key is in the range, in key order
+    pub fn range<Q, R>(&self, range: R) -> Range<'_, K, V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        Range::new(self.root.as_deref(), range.start_bound(), range.end_bound())
+    }
+
+    ///
+    /// check the tree invariants: keys are strictly increasing in order,
+    /// recorded heights are correct and every node is balanced
+    ///
+    pub fn is_valid_bst(&self) -> bool {
+        fn check<K, V>(link: &Link<K, V>) -> Option<usize> {
+            let Some(node) = link else {
+                return Some(0);
+            };
+            let (l, r) = (check(&node.left)?, check(&node.right)?);
+            let h = 1 + l.max(r);
+            (l.abs_diff(r) <= 1 && h == node.height).then_some(h)
+        }
+        let ordered = self.keys().zip(self.keys().skip(1)).all(|(a, b)| a < b);
+        ordered && check(&self.root).is_some()
+    }
+}
+
+/// conversion from and to hand-built [BinaryTreeNode] trees
+impl<K: Ord + Clone, V: Clone> BstMap<K, V> {
+    /// copy the map into a [BinaryTreeNode] tree of the same shape, each node holds `(key, value)`
+    pub fn to_tree(&self) -> Option<TreeNode<(K, V)>> {
+        to_tree(&self.root, &|k: &K, v: &V| (k.clone(), v.clone()))
+    }
+
+    ///
+    /// load a hand-built tree whose nodes hold `(key, value)`, the shape is kept as it is
+    ///
+    /// 不会重新平衡，key不是严格递增或者某个节点的左右子树高度差超过1时返回None
+    ///
+    /// ```
+    /// use std::{cell::RefCell, rc::Rc};
+    ///
+    /// use basic_utils::data_struct::{BinaryTreeNode, BstMap};
+    ///
+    /// let root = Rc::new(RefCell::new(BinaryTreeNode::new((2, "b"))));
+    /// let left = Rc::new(RefCell::new(BinaryTreeNode::new((1, "a"))));
+    /// root.borrow_mut().set_left(&left);
+    /// let map = BstMap::from_tree(Some(&root)).unwrap();
+    /// assert_eq!(Some(&"a"), map.get(&1));
+    ///
+    /// // 左子节点的key比根大，不是合法的二叉搜索树
+    /// left.borrow_mut().val = (3, "c");
+    /// assert!(BstMap::from_tree(Some(&root)).is_none());
+    /// ```
+    ///
+    pub fn from_tree(root: Option<&TreeNode<(K, V)>>) -> Option<Self> {
+        let (root, len) = from_tree(root, |(k, v)| (k.clone(), v.clone()))?;
+        let map = BstMap { root, len };
+        map.is_valid_bst().then_some(map)
+    }
+}
+
+impl<K, V> Default for BstMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for BstMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K: Ord, V> FromIterator<(K, V)> for BstMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = BstMap::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K: Ord, V> Extend<(K, V)> for BstMap<K, V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (k, v) in iter {
+            self.insert(k, v);
+        }
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a BstMap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// in-order iterator over all entries of [BstMap]
+pub struct Iter<'a, K, V> {
+    stack: Vec<&'a Node<K, V>>,
+}
+
+impl<'a, K, V> Iter<'a, K, V> {
+    fn new(root: Option<&'a Node<K, V>>) -> Self {
+        let mut iter = Iter { stack: Vec::new() };
+        iter.push_left(root);
+        iter
+    }
+
+    fn push_left(&mut self, mut link: Option<&'a Node<K, V>>) {
+        while let Some(node) = link {
+            self.stack.push(node);
+            link = node.left.as_deref();
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        self.push_left(node.right.as_deref());
+        Some((&node.key, &node.value))
+    }
+}
+
+/// in-order iterator over a key range of [BstMap]
+pub struct Range<'a, K, V> {
+    stack: Vec<&'a Node<K, V>>,
+    // 范围内的最后一个节点，返回它之后迭代结束
+    last: Option<&'a Node<K, V>>,
+}
+
+impl<'a, K, V> Range<'a, K, V> {
+    fn new<Q>(root: Option<&'a Node<K, V>>, start: Bound<&Q>, end: Bound<&Q>) -> Self
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut range = Range {
+            stack: Vec::new(),
+            last: None,
+        };
+        // 从根节点向下，只把不小于下界的节点入栈
+        let mut link = root;
+        while let Some(node) = link {
+            let key = node.key.borrow();
+            let below = match start {
+                Bound::Included(s) => key < s,
+                Bound::Excluded(s) => key <= s,
+                Bound::Unbounded => false,
+            };
+            if below {
+                link = node.right.as_deref();
+            } else {
+                range.stack.push(node);
+                link = node.left.as_deref();
+            }
+        }
+        // 找到不大于上界的最大节点
+        let mut link = root;
+        while let Some(node) = link {
+            let key = node.key.borrow();
+            let beyond = match end {
+                Bound::Included(e) => key > e,
+                Bound::Excluded(e) => key >= e,
+                Bound::Unbounded => false,
+            };
+            if beyond {
+                link = node.left.as_deref();
+            } else {
+                range.last = Some(node);
+                link = node.right.as_deref();
+            }
+        }
+        let empty = match (range.stack.last(), range.last) {
+            (Some(first), Some(last)) => first.key.borrow() > last.key.borrow(),
+            _ => true,
+        };
+        if empty {
+            range.stack.clear();
+        }
+        range
+    }
+}
+
+impl<'a, K, V> Iterator for Range<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        if self.last.is_some_and(|last| std::ptr::eq(last, node)) {
+            self.stack.clear();
+        } else {
+            let mut link = node.right.as_deref();
+            while let Some(n) = link {
+                self.stack.push(n);
+                link = n.left.as_deref();
+            }
+        }
+        Some((&node.key, &node.value))
+    }
+}
+
+///
+/// ordered set backed by [BstMap]
+///
+/// ```
+/// use basic_utils::data_struct::BstSet;
+///
+/// let set: BstSet<i32> = [5, 1, 9, 3, 7].into_iter().collect();
+/// assert!(set.contains(&3));
+/// assert_eq!(Some(&3), set.floor(&4));
+/// assert_eq!(Some(&5), set.ceiling(&4));
+/// assert_eq!(vec![&3, &5, &7], set.range(2..8).collect::<Vec<_>>());
+/// ```
+///
+pub struct BstSet<T> {
+    map: BstMap<T, ()>,
+}
+
+impl<T> BstSet<T> {
+    pub fn new() -> Self {
+        BstSet { map: BstMap::new() }
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.map.clear();
+    }
+
+    pub fn height(&self) -> usize {
+        self.map.height()
+    }
+
+    pub fn min(&self) -> Option<&T> {
+        self.map.min().map(|(k, _)| k)
+    }
+
+    pub fn max(&self) -> Option<&T> {
+        self.map.max().map(|(k, _)| k)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.map.keys()
+    }
+}
+
+impl<T: Ord> BstSet<T> {
+    /// return false if the value exists
+    pub fn insert(&mut self, value: T) -> bool {
+        self.map.insert(value, ()).is_none()
+    }
+
+    pub fn remove<Q>(&mut self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.remove(value).is_some()
+    }
+
+    pub fn contains<Q>(&self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.contains_key(value)
+    }
+
+    pub fn floor<Q>(&self, value: &Q) -> Option<&T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.floor(value).map(|(k, _)| k)
+    }
+
+    pub fn ceiling<Q>(&self, value: &Q) -> Option<&T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.ceiling(value).map(|(k, _)| k)
+    }
+
+    pub fn range<Q, R>(&self, range: R) -> impl Iterator<Item = &T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        self.map.range(range).map(|(k, _)| k)
+    }
+
+    pub fn is_valid_bst(&self) -> bool {
+        self.map.is_valid_bst()
+    }
+}
+
+/// conversion from and to hand-built [BinaryTreeNode] trees
+impl<T: Ord + Clone> BstSet<T> {
+    ///
+    /// copy the set into a [BinaryTreeNode] tree of the same shape
+    ///
+    /// ```
+    /// use basic_utils::data_struct::{BinaryTreeNode, BstSet};
+    ///
+    /// let set: BstSet<i32> = (1..=5).collect();
+    /// let root = set.to_tree();
+    /// assert_eq!("[2,1,4,null,null,3,5]", BinaryTreeNode::to_level_order_string(root.as_ref()));
+    /// ```
+    ///
+    pub fn to_tree(&self) -> Option<TreeNode<T>> {
+        to_tree(&self.map.root, &|k: &T, _: &()| k.clone())
+    }
+
+    /// load a hand-built tree, the shape is kept, see [BstMap::from_tree]
+    pub fn from_tree(root: Option<&TreeNode<T>>) -> Option<Self> {
+        let (root, len) = from_tree(root, |v| (v.clone(), ()))?;
+        let map = BstMap { root, len };
+        map.is_valid_bst().then_some(BstSet { map })
+    }
+}
+
+impl<T> Default for BstSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for BstSet<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<T: Ord> FromIterator<T> for BstSet<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut set = BstSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T: Ord> Extend<T> for BstSet<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for v in iter {
+            self.insert(v);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+
+    #[test]
+    fn balanced_after_sorted_insert() {
+        let mut set = BstSet::new();
+        for i in 0..1024 {
+            assert!(set.insert(i));
+        }
+        assert!(!set.insert(0));
+        assert_eq!(1024, set.len());
+        // 完全平衡的AVL树高度不超过1.44*log2(n)
+        assert!(set.height() <= 14, "height {}", set.height());
+        assert!(set.is_valid_bst());
+        assert_eq!(Some(&0), set.min());
+        assert_eq!(Some(&1023), set.max());
+    }
+
+    #[test]
+    fn same_as_btree_map() {
+        let mut map = BstMap::new();
+        let mut expected = BTreeMap::new();
+        // 简单的线性同余生成伪随机序列
+        let mut x: u64 = 42;
+        for i in 0..2000 {
+            x = x.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            let key = (x >> 33) % 300;
+            if i % 3 == 0 {
+                assert_eq!(expected.remove(&key), map.remove(&key));
+            } else {
+                assert_eq!(expected.insert(key, i), map.insert(key, i));
+            }
+            assert_eq!(expected.len(), map.len());
+        }
+        assert!(map.is_valid_bst());
+        assert!(map.iter().eq(expected.iter()));
+        assert!(map.range(100..200).eq(expected.range(100..200)));
+        assert!(map.range(..=50).eq(expected.range(..=50)));
+        assert!(map
+            .range((Bound::Excluded(10), Bound::Unbounded))
+            .eq(expected.range(11..)));
+
+        for key in 0..310 {
+            assert_eq!(expected.range(..=key).next_back(), map.floor(&key));
+            assert_eq!(expected.range(key..).next(), map.ceiling(&key));
+            assert_eq!(expected.get(&key), map.get(&key));
+        }
+    }
+
+    #[test]
+    fn borrow_and_get_mut() {
+        let mut map: BstMap<String, usize> = BstMap::new();
+        map.insert(String::from("b"), 2);
+        map.insert(String::from("a"), 1);
+        *map.get_mut("a").unwrap() += 10;
+        assert_eq!(Some(&11), map.get("a"));
+        assert!(map.contains_key("b"));
+        assert_eq!(Some(2), map.remove("b"));
+        assert_eq!(None, map.remove("b"));
+        assert_eq!("{\"a\": 11}", format!("{:?}", map));
+    }
+
+    #[test]
+    fn convert_tree() {
+        let set: BstSet<i32> = (0..100).collect();
+        let root = set.to_tree();
+        assert!(BinaryTreeNode::is_valid_bst(root.as_ref()));
+        assert_eq!(set.height(), BinaryTreeNode::height(root.as_ref()));
+        let loaded = BstSet::from_tree(root.as_ref()).unwrap();
+        assert!(loaded.iter().eq(set.iter()));
+        assert_eq!(
+            BinaryTreeNode::to_level_order(root.as_ref()),
+            BinaryTreeNode::to_level_order(loaded.to_tree().as_ref())
+        );
+
+        let map: BstMap<_, _> = [(1, 'a'), (2, 'b')].into_iter().collect();
+        let root = map.to_tree();
+        assert_eq!(
+            vec![Some((1, 'a')), None, Some((2, 'b'))],
+            BinaryTreeNode::to_level_order(root.as_ref())
+        );
+
+        // 顺序正确但不平衡
+        let chain = BinaryTreeNode::from_level_order(&[Some(1), None, Some(2), None, Some(3)]).unwrap();
+        assert!(BinaryTreeNode::is_valid_bst(chain.as_ref()));
+        assert!(BstSet::from_tree(chain.as_ref()).is_none());
+        // 平衡但顺序错误
+        let unordered = BinaryTreeNode::from_level_order_str("[2,3,1]").unwrap();
+        assert!(BstSet::<i32>::from_tree(unordered.as_ref()).is_none());
+
+        let empty = BstSet::<i32>::from_tree(None).unwrap();
+        assert!(empty.is_empty());
+        assert_eq!(None, empty.to_tree());
+    }
+
+    #[test]
+    fn empty() {
+        let set: BstSet<i32> = BstSet::default();
+        assert_eq!(None, set.min());
+        assert_eq!(None, set.floor(&1));
+        assert_eq!(0, set.height());
+        assert!(set.is_valid_bst());
+        assert_eq!(0, set.range(..).count());
+    }
+}
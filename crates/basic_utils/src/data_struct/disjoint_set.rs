@@ -0,0 +1,322 @@
+use std::{borrow::Borrow, collections::HashMap, fmt, hash::Hash};
+
+///
+/// union-find over dense indices `0..len`
+///
+/// 按集合大小合并(小的挂到大的下面)，find时路径压缩，两者结合后单次操作的均摊复杂度接近O(1)
+///
+/// ```
+/// use basic_utils::data_struct::DenseDisjointSet;
+///
+/// let mut s = DenseDisjointSet::new(5);
+/// s.union(0, 1);
+/// s.union(3, 4);
+/// assert!(s.connected(1, 0));
+/// assert!(!s.connected(1, 3));
+/// assert_eq!(3, s.set_count());
+/// assert_eq!(vec![vec![0, 1], vec![2], vec![3, 4]], s.groups().collect::<Vec<_>>());
+/// ```
+///
+#[derive(Clone)]
+pub struct DenseDisjointSet {
+    parent: Vec<usize>,
+    /// only valid for roots
+    size: Vec<usize>,
+    sets: usize,
+}
+
+impl DenseDisjointSet {
+    /// `len` singleton sets
+    pub fn new(len: usize) -> Self {
+        DenseDisjointSet {
+            parent: (0..len).collect(),
+            size: vec![1; len],
+            sets: len,
+        }
+    }
+
+    /// add a singleton set, return its index
+    pub fn make_set(&mut self) -> usize {
+        self.parent.push(self.parent.len());
+        self.size.push(1);
+        self.sets += 1;
+        self.parent.len() - 1
+    }
+
+    /// number of elements
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// number of disjoint sets
+    pub fn set_count(&self) -> usize {
+        self.sets
+    }
+
+    ///
+    /// the representative of the set containing `x`
+    ///
+    /// # Panics
+    ///
+    /// panics if `x` is out of range
+    ///
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        let mut x = x;
+        while self.parent[x] != root {
+            x = std::mem::replace(&mut self.parent[x], root);
+        }
+        root
+    }
+
+    /// merge the sets containing `a` and `b`, return false if they were already the same set
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        if self.size[a] < self.size[b] {
+            std::mem::swap(&mut a, &mut b);
+        }
+        self.parent[b] = a;
+        self.size[a] += self.size[b];
+        self.sets -= 1;
+        true
+    }
+
+    pub fn connected(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// size of the set containing `x`
+    pub fn set_size(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.size[root]
+    }
+
+    /// every set in ascending order, sets are ordered by their smallest element
+    pub fn groups(&mut self) -> impl Iterator<Item = Vec<usize>> {
+        // slot[root]是这个集合在结果中的位置
+        let mut slot = vec![usize::MAX; self.len()];
+        let mut groups: Vec<Vec<usize>> = Vec::with_capacity(self.sets);
+        for x in 0..self.len() {
+            let root = self.find(x);
+            if slot[root] == usize::MAX {
+                slot[root] = groups.len();
+                groups.push(Vec::with_capacity(self.size[root]));
+            }
+            groups[slot[root]].push(x);
+        }
+        groups.into_iter()
+    }
+}
+
+impl fmt::Debug for DenseDisjointSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone().groups()).finish()
+    }
+}
+
+///
+/// union-find over hashable keys, backed by [DenseDisjointSet]
+///
+/// ```
+/// use basic_utils::data_struct::DisjointSet;
+///
+/// // group duplicate records: records sharing an email or a phone are the same person
+/// let records = [("ann", "a@x.com", "111"), ("anne", "a@x.com", "222"), ("bob", "b@x.com", "333"), ("an", "c@x.com", "222")];
+/// let mut s = DisjointSet::new();
+/// for (name, email, phone) in records {
+///     s.union(name, email);
+///     s.union(name, phone);
+/// }
+/// assert!(s.connected("ann", "an"));
+/// assert!(!s.connected("ann", "bob"));
+/// assert_eq!(Some(7), s.set_size("an"));
+/// assert_eq!(2, s.set_count());
+/// ```
+///
+pub struct DisjointSet<K> {
+    index: HashMap<K, usize>,
+    keys: Vec<K>,
+    sets: DenseDisjointSet,
+}
+
+impl<K: Hash + Eq + Clone> DisjointSet<K> {
+    pub fn new() -> Self {
+        DisjointSet {
+            index: HashMap::new(),
+            keys: Vec::new(),
+            sets: DenseDisjointSet::new(0),
+        }
+    }
+
+    /// add `key` as a singleton set, return false if it is already present
+    pub fn make_set(&mut self, key: K) -> bool {
+        if self.index.contains_key(&key) {
+            return false;
+        }
+        self.index.insert(key.clone(), self.sets.make_set());
+        self.keys.push(key);
+        true
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    pub fn set_count(&self) -> usize {
+        self.sets.set_count()
+    }
+
+    pub fn contains<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.index.contains_key(key)
+    }
+
+    /// the representative key of the set containing `key`, None if `key` is absent
+    pub fn find<Q>(&mut self, key: &Q) -> Option<&K>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let i = *self.index.get(key)?;
+        Some(&self.keys[self.sets.find(i)])
+    }
+
+    /// merge the sets containing `a` and `b`, absent keys are added first. return false if they were already the same set
+    pub fn union(&mut self, a: K, b: K) -> bool {
+        let a = self.index_of(a);
+        let b = self.index_of(b);
+        self.sets.union(a, b)
+    }
+
+    fn index_of(&mut self, key: K) -> usize {
+        if let Some(&i) = self.index.get(&key) {
+            return i;
+        }
+        self.make_set(key);
+        self.keys.len() - 1
+    }
+
+    /// false if either key is absent
+    pub fn connected<Q>(&mut self, a: &Q, b: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        match (self.index.get(a), self.index.get(b)) {
+            (Some(&a), Some(&b)) => self.sets.connected(a, b),
+            _ => false,
+        }
+    }
+
+    /// size of the set containing `key`, None if `key` is absent
+    pub fn set_size<Q>(&mut self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let i = *self.index.get(key)?;
+        Some(self.sets.set_size(i))
+    }
+
+    /// every set with keys in insertion order, sets are ordered by their first inserted key
+    pub fn groups(&mut self) -> impl Iterator<Item = Vec<&K>> {
+        let keys = &self.keys;
+        self.sets
+            .groups()
+            .map(move |g| g.into_iter().map(|i| &keys[i]).collect())
+    }
+}
+
+impl<K: Hash + Eq + Clone> Default for DisjointSet<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: fmt::Debug> fmt::Debug for DisjointSet<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let groups = self.sets.clone().groups();
+        f.debug_list()
+            .entries(groups.map(|g| g.into_iter().map(|i| &self.keys[i]).collect::<Vec<_>>()))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_naive_labels() {
+        let n = 60;
+        let mut s = DenseDisjointSet::new(n);
+        // label[i]是i所在集合的编号，合并时整体改写
+        let mut label: Vec<usize> = (0..n).collect();
+        let mut x: u32 = 3;
+        for _ in 0..200 {
+            x = x.wrapping_mul(1103515245).wrapping_add(12345);
+            let (a, b) = ((x >> 8) as usize % n, (x >> 20) as usize % n);
+            let (la, lb) = (label[a], label[b]);
+            assert_eq!(la != lb, s.union(a, b));
+            for l in label.iter_mut().filter(|l| **l == lb) {
+                *l = la;
+            }
+            let (c, d) = ((x >> 4) as usize % n, (x >> 14) as usize % n);
+            assert_eq!(label[c] == label[d], s.connected(c, d));
+            assert_eq!(label.iter().filter(|&&l| l == label[c]).count(), s.set_size(c));
+        }
+        let mut distinct = label.clone();
+        distinct.sort();
+        distinct.dedup();
+        assert_eq!(distinct.len(), s.set_count());
+        assert_eq!(n, s.groups().map(|g| g.len()).sum());
+    }
+
+    #[test]
+    fn path_is_compressed() {
+        let mut s = DenseDisjointSet::new(4);
+        s.parent = vec![0, 0, 1, 2];
+        s.size[0] = 4;
+        assert_eq!(0, s.find(3));
+        assert_eq!(vec![0, 0, 0, 0], s.parent);
+        assert_eq!(4, s.make_set());
+        assert_eq!(5, s.len());
+        assert_eq!("[[0, 1, 2, 3], [4]]", format!("{:?}", s));
+    }
+
+    #[test]
+    fn keyed() {
+        let mut s: DisjointSet<String> = DisjointSet::new();
+        assert!(s.make_set("a".to_string()));
+        assert!(!s.make_set("a".to_string()));
+        assert_eq!(None, s.find("b"));
+        assert!(!s.connected("a", "b"));
+        s.union("b".to_string(), "c".to_string());
+        s.union("d".to_string(), "b".to_string());
+        assert!(!s.union("c".to_string(), "d".to_string()));
+        assert_eq!(Some(&"b".to_string()), s.find("d"));
+        assert_eq!(None, s.set_size("e"));
+        assert_eq!(4, s.len());
+        assert!(s.contains("d"));
+        let groups: Vec<Vec<&String>> = s.groups().collect();
+        assert_eq!(vec![vec!["a"], vec!["b", "c", "d"]], groups);
+        assert_eq!(r#"[["a"], ["b", "c", "d"]]"#, format!("{:?}", s));
+    }
+}
@@ -0,0 +1,218 @@
+use std::{
+    fmt,
+    ops::{Add, RangeBounds, Sub},
+};
+
+use super::index_range;
+
+///
+/// Fenwick tree (binary indexed tree): point update and prefix sum in O(log n)
+///
+/// `tree[i - 1]`保存原数组`(i - lowbit(i), i]`这一段的和(下标从1开始)，lowbit(i)是i最低位的1。
+/// `T::default()`被当作0，区间和通过两个前缀和相减得到
+///
+/// ```
+/// use basic_utils::data_struct::FenwickTree;
+///
+/// // 每分钟的请求数
+/// let mut requests = FenwickTree::from(vec![3, 0, 5, 2, 7]);
+/// assert_eq!(8, requests.prefix_sum(3));
+/// assert_eq!(14, requests.range_sum(2..));
+/// requests.add(1, 4);
+/// requests.set(4, 1);
+/// assert_eq!(vec![3, 4, 5, 2, 1], (0..requests.len()).map(|i| requests.get(i)).collect::<Vec<_>>());
+/// // 第10个请求落在哪一分钟
+/// assert_eq!(Some(2), requests.lower_bound(10));
+/// ```
+///
+#[derive(Clone)]
+pub struct FenwickTree<T> {
+    tree: Vec<T>,
+}
+
+#[inline]
+fn lowbit(i: usize) -> usize {
+    i & i.wrapping_neg()
+}
+
+impl<T> FenwickTree<T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T>,
+{
+    /// `len` zeros
+    pub fn new(len: usize) -> Self {
+        FenwickTree {
+            tree: vec![T::default(); len],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.tree.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tree.is_empty()
+    }
+
+    ///
+    /// add `delta` to the element at `index`
+    ///
+    /// # Panics
+    ///
+    /// panics if `index` is out of range
+    ///
+    pub fn add(&mut self, index: usize, delta: T) {
+        assert!(
+            index < self.len(),
+            "index {} out of range for length {}",
+            index,
+            self.len()
+        );
+        let mut i = index + 1;
+        while i <= self.len() {
+            self.tree[i - 1] = self.tree[i - 1] + delta;
+            i += lowbit(i);
+        }
+    }
+
+    /// replace the element at `index`
+    pub fn set(&mut self, index: usize, value: T) {
+        self.add(index, value - self.get(index));
+    }
+
+    pub fn get(&self, index: usize) -> T {
+        self.range_sum(index..=index)
+    }
+
+    ///
+    /// sum of the first `end` elements
+    ///
+    /// # Panics
+    ///
+    /// panics if `end > len`
+    ///
+    pub fn prefix_sum(&self, end: usize) -> T {
+        assert!(end <= self.len(), "end {} out of range for length {}", end, self.len());
+        let mut sum = T::default();
+        let mut i = end;
+        while i > 0 {
+            sum = sum + self.tree[i - 1];
+            i -= lowbit(i);
+        }
+        sum
+    }
+
+    pub fn range_sum(&self, range: impl RangeBounds<usize>) -> T {
+        let r = index_range(range, self.len());
+        self.prefix_sum(r.end) - self.prefix_sum(r.start)
+    }
+
+    ///
+    /// the smallest index whose prefix sum (inclusive) reaches `target`, None if the total is less than `target`
+    ///
+    /// 要求所有元素非负，这样前缀和单调不减。从高位到低位倍增，O(log n)
+    ///
+    pub fn lower_bound(&self, target: T) -> Option<usize>
+    where
+        T: PartialOrd,
+    {
+        // pos是前缀和小于target的最长前缀
+        let mut pos = 0;
+        let mut rest = target;
+        let mut step = self.len().checked_next_power_of_two()?;
+        while step > 0 {
+            if pos + step <= self.len() && self.tree[pos + step - 1] < rest {
+                pos += step;
+                rest = rest - self.tree[pos - 1];
+            }
+            step /= 2;
+        }
+        (pos < self.len()).then_some(pos)
+    }
+}
+
+impl<T> From<Vec<T>> for FenwickTree<T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T>,
+{
+    /// build in O(n): every node adds itself to its parent once
+    fn from(mut tree: Vec<T>) -> Self {
+        for i in 1..=tree.len() {
+            let parent = i + lowbit(i);
+            if parent <= tree.len() {
+                tree[parent - 1] = tree[parent - 1] + tree[i - 1];
+            }
+        }
+        FenwickTree { tree }
+    }
+}
+
+impl<T> FromIterator<T> for FenwickTree<T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T>,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::from(iter.into_iter().collect::<Vec<_>>())
+    }
+}
+
+impl<T> fmt::Debug for FenwickTree<T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T> + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries((0..self.len()).map(|i| self.get(i))).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_naive_sums() {
+        let n = 37;
+        let mut model = vec![0i64; n];
+        let mut t = FenwickTree::new(n);
+        let mut x: u32 = 5;
+        for _ in 0..500 {
+            x = x.wrapping_mul(1103515245).wrapping_add(12345);
+            let i = (x >> 8) as usize % n;
+            let v = (x >> 16) as i64 % 100;
+            if x.is_multiple_of(3) {
+                model[i] = v;
+                t.set(i, v);
+            } else {
+                model[i] += v;
+                t.add(i, v);
+            }
+            let (a, b) = ((x >> 4) as usize % (n + 1), (x >> 20) as usize % (n + 1));
+            let (a, b) = (a.min(b), a.max(b));
+            assert_eq!(model[a..b].iter().sum::<i64>(), t.range_sum(a..b));
+            assert_eq!(model[..b].iter().sum::<i64>(), t.prefix_sum(b));
+        }
+        // O(n)建树和逐个add得到的内部数组相同
+        assert_eq!(t.tree, FenwickTree::from(model.clone()).tree);
+        assert_eq!(format!("{:?}", model), format!("{:?}", t));
+    }
+
+    #[test]
+    fn lower_bound() {
+        let t: FenwickTree<u32> = [2, 0, 3, 0, 0, 1].into_iter().collect();
+        assert_eq!(Some(0), t.lower_bound(0));
+        assert_eq!(Some(0), t.lower_bound(2));
+        assert_eq!(Some(2), t.lower_bound(3));
+        assert_eq!(Some(2), t.lower_bound(5));
+        assert_eq!(Some(5), t.lower_bound(6));
+        assert_eq!(None, t.lower_bound(7));
+        assert_eq!(None, FenwickTree::<u32>::new(0).lower_bound(0));
+        assert_eq!(4.0, FenwickTree::from(vec![1.5, 2.5]).range_sum(..));
+    }
+
+    #[test]
+    #[should_panic(expected = "range 2..1 out of bounds for length 3")]
+    #[allow(clippy::reversed_empty_ranges)]
+    fn bad_range() {
+        FenwickTree::<i32>::new(3).range_sum(2..1);
+    }
+}
@@ -0,0 +1,381 @@
+use std::{
+    cell::{Ref, RefCell, RefMut},
+    collections::{vec_deque, VecDeque},
+    fmt,
+    iter::FusedIterator,
+    rc::Rc,
+};
+
+type Link<T> = Option<Rc<RefCell<ListNode<T>>>>;
+
+#[derive(Debug)]
+pub struct ListNode<T> {
+    pub val: T,
+    pub next: Option<Rc<RefCell<ListNode<T>>>>,
+}
+
+impl<T> ListNode<T> {
+    #[inline]
+    pub fn new(val: T) -> Self {
+        ListNode { val, next: None }
+    }
+
+    #[inline]
+    pub fn from(val: T, next: Option<Rc<RefCell<ListNode<T>>>>) -> Self {
+        ListNode { val, next }
+    }
+
+    #[inline]
+    pub fn set_next(&mut self, n: &Rc<RefCell<ListNode<T>>>) {
+        self.next = Some(Rc::clone(n));
+    }
+
+    #[inline]
+    pub fn set_next_none(&mut self) {
+        self.next = None;
+    }
+}
+
+///
+/// singly linked list built from [ListNode]
+///
+/// push_front/pop_front/push_back和get(n)都是O(1)
+///
+/// ```
+/// use basic_utils::data_struct::LinkedList;
+///
+/// let mut list: LinkedList<i32> = vec![1, 2, 3].into();
+/// list.push_front(0);
+/// list.push_back(4);
+/// assert_eq!("0 -> 1 -> 2 -> 3 -> 4", list.to_string());
+///
+/// list.reverse();
+/// assert_eq!(Some(4), list.get(0).as_deref().copied());
+/// for mut v in &mut list {
+///     *v *= 10;
+/// }
+/// assert_eq!(vec![40, 30, 20, 10, 0], Vec::from(list));
+/// ```
+///
+/// 节点之间依然通过[ListNode::next]相连，同时按顺序把每个节点的Rc放在VecDeque里。
+/// 迭代和get直接拿到每个节点的&RefCell，返回用`Ref::map`/`RefMut::map`得到的[Ref]/[RefMut]，
+/// 不需要T: Clone，也不会绕过RefCell的借用检查
+///
+pub struct LinkedList<T> {
+    nodes: VecDeque<Rc<RefCell<ListNode<T>>>>,
+}
+
+impl<T> LinkedList<T> {
+    pub fn new() -> Self {
+        LinkedList { nodes: VecDeque::new() }
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn push_front(&mut self, val: T) {
+        let node = Rc::new(RefCell::new(ListNode::from(val, self.nodes.front().cloned())));
+        self.nodes.push_front(node);
+    }
+
+    pub fn push_back(&mut self, val: T) {
+        let node = Rc::new(RefCell::new(ListNode::new(val)));
+        if let Some(tail) = self.nodes.back() {
+            tail.borrow_mut().set_next(&node);
+        }
+        self.nodes.push_back(node);
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        let node = self.nodes.pop_front()?;
+        node.borrow_mut().set_next_none();
+        match Rc::try_unwrap(node) {
+            Ok(cell) => Some(cell.into_inner().val),
+            Err(_) => unreachable!("list node is shared"),
+        }
+    }
+
+    pub fn front(&self) -> Option<Ref<'_, T>> {
+        self.get(0)
+    }
+
+    /// borrow the n-th element
+    pub fn get(&self, n: usize) -> Option<Ref<'_, T>> {
+        self.nodes.get(n).map(|node| Ref::map(node.borrow(), |node| &node.val))
+    }
+
+    pub fn get_mut(&mut self, n: usize) -> Option<RefMut<'_, T>> {
+        self.nodes
+            .get(n)
+            .map(|node| RefMut::map(node.borrow_mut(), |node| &mut node.val))
+    }
+
+    /// reverse the list in place, no node is reallocated
+    pub fn reverse(&mut self) {
+        self.nodes.make_contiguous().reverse();
+        let mut next: Link<T> = None;
+        for node in self.nodes.iter().rev() {
+            node.borrow_mut().next = next.take();
+            next = Some(Rc::clone(node));
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter(self.nodes.iter())
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut(self.nodes.iter())
+    }
+
+    /// consume the list and return the head node, for code which works on raw [ListNode]s
+    pub fn into_head(self) -> Option<Rc<RefCell<ListNode<T>>>> {
+        self.nodes.front().cloned()
+    }
+}
+
+impl<T> Default for LinkedList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone> Clone for LinkedList<T> {
+    fn clone(&self) -> Self {
+        self.iter().map(|val| T::clone(&val)).collect()
+    }
+}
+
+impl<T: PartialEq> PartialEq for LinkedList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().zip(other.iter()).all(|(a, b)| *a == *b)
+    }
+}
+
+impl<T: Eq> Eq for LinkedList<T> {}
+
+impl<T: fmt::Debug> fmt::Debug for LinkedList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/// `1 -> 2 -> 3`
+impl<T: fmt::Display> fmt::Display for LinkedList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, val) in self.iter().enumerate() {
+            if i > 0 {
+                write!(f, " -> ")?;
+            }
+            write!(f, "{}", val)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T> From<Vec<T>> for LinkedList<T> {
+    fn from(value: Vec<T>) -> Self {
+        value.into_iter().collect()
+    }
+}
+
+impl<T> From<LinkedList<T>> for Vec<T> {
+    fn from(value: LinkedList<T>) -> Self {
+        value.into_iter().collect()
+    }
+}
+
+impl<T> FromIterator<T> for LinkedList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = LinkedList::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<T> Extend<T> for LinkedList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for val in iter {
+            self.push_back(val);
+        }
+    }
+}
+
+pub struct IntoIter<T>(LinkedList<T>);
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.len(), Some(self.0.len()))
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+impl<T> FusedIterator for IntoIter<T> {}
+
+impl<T> IntoIterator for LinkedList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self)
+    }
+}
+
+pub struct Iter<'a, T>(vec_deque::Iter<'a, Rc<RefCell<ListNode<T>>>>);
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = Ref<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.0.next()?;
+        Some(Ref::map(node.borrow(), |node| &node.val))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+impl<T> FusedIterator for Iter<'_, T> {}
+
+impl<'a, T> IntoIterator for &'a LinkedList<T> {
+    type Item = Ref<'a, T>;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// 每个节点只会被返回一次，同时存在的RefMut属于不同的RefCell
+pub struct IterMut<'a, T>(vec_deque::Iter<'a, Rc<RefCell<ListNode<T>>>>);
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = RefMut<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.0.next()?;
+        Some(RefMut::map(node.borrow_mut(), |node| &mut node.val))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+impl<T> FusedIterator for IterMut<'_, T> {}
+
+impl<'a, T> IntoIterator for &'a mut LinkedList<T> {
+    type Item = RefMut<'a, T>;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn push_and_pop() {
+        let mut list = LinkedList::new();
+        assert!(list.is_empty());
+        assert_eq!(None, list.pop_front());
+
+        list.push_back(2);
+        list.push_front(1);
+        list.push_back(3);
+        assert_eq!(3, list.len());
+        assert_eq!(Some(&1), list.front().as_deref());
+        assert_eq!(Some(1), list.pop_front());
+        assert_eq!(Some(2), list.pop_front());
+        assert_eq!(Some(3), list.pop_front());
+        assert_eq!(None, list.pop_front());
+
+        // tail被重置后push_back依然正确
+        list.push_back(4);
+        list.push_back(5);
+        assert_eq!(vec![4, 5], Vec::from(list));
+    }
+
+    #[test]
+    fn reverse_and_get() {
+        let mut list: LinkedList<_> = (1..=4).collect();
+        list.reverse();
+        assert_eq!("4 -> 3 -> 2 -> 1", list.to_string());
+        list.push_back(0);
+        assert_eq!(Some(&0), list.get(4).as_deref());
+        assert!(list.get(5).is_none());
+
+        *list.get_mut(0).unwrap() = 40;
+        for mut v in &mut list {
+            *v *= 10;
+        }
+        assert_eq!(vec![400, 30, 20, 10, 0], list.iter().map(|v| *v).collect::<Vec<_>>());
+        assert_eq!(5, list.iter_mut().len());
+
+        // 反转后next也指向新的顺序
+        list.reverse();
+        let head = list.into_head().unwrap();
+        let next = head.borrow().next.clone().unwrap();
+        assert_eq!((0, 10), (head.borrow().val, next.borrow().val));
+
+        let mut empty: LinkedList<i32> = LinkedList::new();
+        empty.reverse();
+        assert_eq!("", empty.to_string());
+    }
+
+    #[test]
+    fn conversion() {
+        let list = LinkedList::from(vec!["a", "b"]);
+        assert_eq!("[\"a\", \"b\"]", format!("{:?}", list));
+        assert_eq!(list, list.clone());
+        assert_eq!(2, list.iter().len());
+
+        let head = list.into_head().unwrap();
+        assert_eq!("a", head.borrow().val);
+        let next = head.borrow().next.clone().unwrap();
+        assert_eq!("b", next.borrow().val);
+    }
+
+    #[test]
+    fn not_clone() {
+        #[derive(Debug, PartialEq)]
+        struct Token(String);
+
+        let mut list: LinkedList<_> = ["a", "b"].into_iter().map(|s| Token(s.to_string())).collect();
+        for mut token in list.iter_mut() {
+            token.0.push('!');
+        }
+        assert_eq!("[Token(\"a!\"), Token(\"b!\")]", format!("{:?}", list));
+        assert_eq!("b!", list.get(1).unwrap().0);
+        // 同时持有多个元素的Ref
+        let all: Vec<_> = list.iter().collect();
+        assert_eq!(Token(String::from("a!")), *all[0]);
+    }
+
+    #[test]
+    fn drop_long_list() {
+        let list: LinkedList<u32> = (0..1_000_000).collect();
+        assert_eq!(1_000_000, list.len());
+    }
+}
@@ -0,0 +1,511 @@
+use std::{
+    borrow::Borrow,
+    collections::HashMap,
+    fmt,
+    hash::Hash,
+    time::{Duration, Instant},
+};
+
+/// marks the end of the list
+const NIL: usize = usize::MAX;
+
+/// why an entry was passed to the eviction callback
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EvictionReason {
+    /// the least recently used entry made room for a new one
+    Capacity,
+    /// the entry outlived its time-to-live
+    Expired,
+}
+
+type EvictCallback<K, V> = Box<dyn FnMut(K, V, EvictionReason)>;
+type Clock = Box<dyn Fn() -> Instant>;
+
+struct Node<K, V> {
+    key: K,
+    value: V,
+    expires: Option<Instant>,
+    prev: usize,
+    next: usize,
+}
+
+impl<K, V> Node<K, V> {
+    fn is_expired(&self, now: Instant) -> bool {
+        self.expires.is_some_and(|t| t <= now)
+    }
+}
+
+///
+/// least recently used cache with a capacity bound and optional per-entry time-to-live
+///
+/// 和[ListNode](super::ListNode)一样是双向链表，只是节点放在Vec里，用下标代替`Rc<RefCell<..>>`，
+/// 被删除节点的下标放进free复用。map从key找到下标，所以get/put/remove都是O(1)。
+/// head是最近使用的，tail是最久未使用的。过期的节点在被访问或者[LruCache::purge_expired]时才删除，
+/// 容量满时只看tail，所以可能先淘汰一个没过期的tail，而不是中间已经过期的节点
+///
+/// ```
+/// use basic_utils::data_struct::{EvictionReason, LruCache};
+/// use std::{cell::RefCell, rc::Rc};
+///
+/// let evicted = Rc::new(RefCell::new(Vec::new()));
+/// let log = Rc::clone(&evicted);
+/// let mut cache = LruCache::new(2).on_evict(move |k, _v, reason| log.borrow_mut().push((k, reason)));
+/// cache.put("a", 1);
+/// cache.put("b", 2);
+/// assert_eq!(Some(&1), cache.get("a"));
+/// cache.put("c", 3);
+/// assert_eq!(None, cache.get("b"));
+/// assert_eq!(vec![("b", EvictionReason::Capacity)], *evicted.borrow());
+/// assert_eq!(vec![(&"c", &3), (&"a", &1)], cache.iter().collect::<Vec<_>>());
+/// ```
+///
+pub struct LruCache<K, V> {
+    map: HashMap<K, usize>,
+    nodes: Vec<Option<Node<K, V>>>,
+    free: Vec<usize>,
+    head: usize,
+    tail: usize,
+    capacity: usize,
+    on_evict: Option<EvictCallback<K, V>>,
+    clock: Clock,
+}
+
+impl<K: Hash + Eq + Clone, V> LruCache<K, V> {
+    ///
+    /// # Panics
+    ///
+    /// panics if capacity is 0
+    ///
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "capacity must be positive");
+        LruCache {
+            map: HashMap::with_capacity(capacity),
+            nodes: Vec::with_capacity(capacity),
+            free: Vec::new(),
+            head: NIL,
+            tail: NIL,
+            capacity,
+            on_evict: None,
+            clock: Box::new(Instant::now),
+        }
+    }
+
+    ///
+    /// called with entries removed by the cache itself, not with live entries removed by [LruCache::remove]
+    /// or replaced by `put`. 过期的条目无论在哪里被发现(包括remove)都会以[EvictionReason::Expired]通知
+    ///
+    pub fn on_evict(mut self, f: impl FnMut(K, V, EvictionReason) + 'static) -> Self {
+        self.on_evict = Some(Box::new(f));
+        self
+    }
+
+    /// the time source for TTLs, `Instant::now` by default
+    pub fn with_clock(mut self, now: impl Fn() -> Instant + 'static) -> Self {
+        self.clock = Box::new(now);
+        self
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// number of entries, expired entries which are not purged yet are counted
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// insert or replace an entry which never expires, the old value is returned if it is still live
+    pub fn put(&mut self, key: K, value: V) -> Option<V> {
+        self.insert(key, value, None)
+    }
+
+    /// insert or replace an entry which expires after `ttl`, the old value is returned if it is still live
+    pub fn put_with_ttl(&mut self, key: K, value: V, ttl: Duration) -> Option<V> {
+        self.insert(key, value, Some(self.now() + ttl))
+    }
+
+    fn insert(&mut self, key: K, value: V, expires: Option<Instant>) -> Option<V> {
+        if let Some(&i) = self.map.get(&key) {
+            // 过期的旧值先按过期淘汰，再当作新key插入
+            if self.node(i).is_expired(self.now()) {
+                self.evict(i, EvictionReason::Expired);
+            } else {
+                self.detach(i);
+                self.attach_front(i);
+                let node = self.node_mut(i);
+                node.expires = expires;
+                return Some(std::mem::replace(&mut node.value, value));
+            }
+        }
+        if self.map.len() == self.capacity {
+            let reason = if self.node(self.tail).is_expired(self.now()) {
+                EvictionReason::Expired
+            } else {
+                EvictionReason::Capacity
+            };
+            self.evict(self.tail, reason);
+        }
+        let node = Node {
+            key: key.clone(),
+            value,
+            expires,
+            prev: NIL,
+            next: NIL,
+        };
+        let i = match self.free.pop() {
+            Some(i) => {
+                self.nodes[i] = Some(node);
+                i
+            }
+            None => {
+                self.nodes.push(Some(node));
+                self.nodes.len() - 1
+            }
+        };
+        self.attach_front(i);
+        self.map.insert(key, i);
+        None
+    }
+
+    /// get a live entry and mark it as most recently used, an expired entry is evicted
+    pub fn get<Q>(&mut self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let i = self.touch(key)?;
+        Some(&self.node(i).value)
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let i = self.touch(key)?;
+        Some(&mut self.node_mut(i).value)
+    }
+
+    ///
+    /// get a live entry or insert one which never expires, the entry becomes most recently used
+    ///
+    /// ```
+    /// use basic_utils::data_struct::LruCache;
+    ///
+    /// let mut hits = LruCache::new(8);
+    /// *hits.get_or_insert_with("/", || 0) += 1;
+    /// *hits.get_or_insert_with("/", || 0) += 1;
+    /// assert_eq!(Some(&2), hits.peek("/"));
+    /// ```
+    ///
+    pub fn get_or_insert_with(&mut self, key: K, f: impl FnOnce() -> V) -> &mut V {
+        let i = match self.touch(&key) {
+            Some(i) => i,
+            None => {
+                self.insert(key.clone(), f(), None);
+                self.map[&key]
+            }
+        };
+        &mut self.node_mut(i).value
+    }
+
+    /// get a live entry without changing the order
+    pub fn peek<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let node = self.node(*self.map.get(key)?);
+        (!node.is_expired(self.now())).then_some(&node.value)
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.peek(key).is_some()
+    }
+
+    /// remove an entry, None if it is absent or expired, an expired entry is evicted
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let i = *self.map.get(key)?;
+        if self.node(i).is_expired(self.now()) {
+            self.evict(i, EvictionReason::Expired);
+            return None;
+        }
+        Some(self.take(i).value)
+    }
+
+    /// remove the least recently used live entry, expired entries on the way are evicted
+    pub fn pop_lru(&mut self) -> Option<(K, V)> {
+        let now = self.now();
+        while self.tail != NIL {
+            if !self.node(self.tail).is_expired(now) {
+                let node = self.take(self.tail);
+                return Some((node.key, node.value));
+            }
+            self.evict(self.tail, EvictionReason::Expired);
+        }
+        None
+    }
+
+    /// evict all expired entries, return how many were evicted
+    pub fn purge_expired(&mut self) -> usize {
+        let now = self.now();
+        let expired: Vec<usize> = self
+            .map
+            .values()
+            .copied()
+            .filter(|&i| self.node(i).is_expired(now))
+            .collect();
+        for &i in &expired {
+            self.evict(i, EvictionReason::Expired);
+        }
+        expired.len()
+    }
+
+    /// remove all entries without calling the eviction callback
+    pub fn clear(&mut self) {
+        self.map.clear();
+        self.nodes.clear();
+        self.free.clear();
+        self.head = NIL;
+        self.tail = NIL;
+    }
+
+    /// live entries from the most to the least recently used
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        let now = self.now();
+        let mut i = self.head;
+        std::iter::from_fn(move || {
+            while i != NIL {
+                let node = self.node(i);
+                i = node.next;
+                if !node.is_expired(now) {
+                    return Some((&node.key, &node.value));
+                }
+            }
+            None
+        })
+    }
+
+    /// move a live entry to the front, evict it if it is expired
+    fn touch<Q>(&mut self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let i = *self.map.get(key)?;
+        if self.node(i).is_expired(self.now()) {
+            self.evict(i, EvictionReason::Expired);
+            return None;
+        }
+        self.detach(i);
+        self.attach_front(i);
+        Some(i)
+    }
+
+    fn now(&self) -> Instant {
+        (self.clock)()
+    }
+
+    fn evict(&mut self, i: usize, reason: EvictionReason) {
+        let node = self.take(i);
+        if let Some(f) = self.on_evict.as_mut() {
+            f(node.key, node.value, reason);
+        }
+    }
+
+    /// unlink a node and free its slot
+    fn take(&mut self, i: usize) -> Node<K, V> {
+        self.detach(i);
+        let node = self.nodes[i].take().expect("linked slot is occupied");
+        self.map.remove(&node.key);
+        self.free.push(i);
+        node
+    }
+
+    fn node(&self, i: usize) -> &Node<K, V> {
+        self.nodes[i].as_ref().expect("linked slot is occupied")
+    }
+
+    fn node_mut(&mut self, i: usize) -> &mut Node<K, V> {
+        self.nodes[i].as_mut().expect("linked slot is occupied")
+    }
+
+    fn detach(&mut self, i: usize) {
+        let (prev, next) = {
+            let node = self.node(i);
+            (node.prev, node.next)
+        };
+        match prev {
+            NIL => self.head = next,
+            p => self.node_mut(p).next = next,
+        }
+        match next {
+            NIL => self.tail = prev,
+            n => self.node_mut(n).prev = prev,
+        }
+    }
+
+    fn attach_front(&mut self, i: usize) {
+        let head = self.head;
+        let node = self.node_mut(i);
+        node.prev = NIL;
+        node.next = head;
+        match head {
+            NIL => self.tail = i,
+            h => self.node_mut(h).prev = i,
+        }
+        self.head = i;
+    }
+}
+
+impl<K: Hash + Eq + Clone + fmt::Debug, V: fmt::Debug> fmt::Debug for LruCache<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    };
+
+    use super::*;
+
+    #[test]
+    fn lru_order() {
+        let mut cache = LruCache::new(3);
+        for i in 0..3 {
+            assert_eq!(None, cache.put(i, i * 10));
+        }
+        assert_eq!(Some(&0), cache.get(&0));
+        assert_eq!(Some(10), cache.put(1, 11));
+        // 2 is the least recently used
+        cache.put(3, 30);
+        assert!(!cache.contains_key(&2));
+        assert_eq!(vec![(&3, &30), (&1, &11), (&0, &0)], cache.iter().collect::<Vec<_>>());
+
+        // peek does not change the order
+        assert_eq!(Some(&0), cache.peek(&0));
+        assert_eq!(Some((0, 0)), cache.pop_lru());
+        *cache.get_mut(&1).unwrap() += 1;
+        assert_eq!(Some(12), cache.remove(&1));
+        assert_eq!(None, cache.remove(&1));
+        assert_eq!(1, cache.len());
+
+        // freed slots are reused
+        cache.put(4, 40);
+        cache.put(5, 50);
+        assert_eq!(3, cache.nodes.len());
+        cache.clear();
+        assert!(cache.is_empty());
+        assert_eq!(None, cache.pop_lru());
+    }
+
+    #[test]
+    fn matches_model() {
+        let mut cache = LruCache::new(8);
+        // most recently used at the end
+        let mut model: Vec<(u32, u32)> = Vec::new();
+        let mut x: u32 = 99;
+        for step in 0..5000 {
+            x = x.wrapping_mul(1103515245).wrapping_add(12345);
+            let key = (x >> 16) % 12;
+            let pos = model.iter().position(|&(k, _)| k == key);
+            if x.is_multiple_of(3) {
+                let expected = pos.map(|p| model.remove(p).1);
+                assert_eq!(expected, cache.remove(&key));
+            } else if x % 3 == 1 {
+                let expected = pos.map(|p| model.remove(p).1);
+                assert_eq!(expected, cache.put(key, step));
+                if model.len() == 8 {
+                    model.remove(0);
+                }
+                model.push((key, step));
+            } else {
+                let expected = pos.map(|p| {
+                    let e = model.remove(p);
+                    model.push(e);
+                    e.1
+                });
+                assert_eq!(expected.as_ref(), cache.get(&key));
+            }
+            let entries: Vec<(u32, u32)> = cache.iter().map(|(k, v)| (*k, *v)).collect();
+            assert_eq!(model.iter().rev().copied().collect::<Vec<_>>(), entries);
+        }
+    }
+
+    #[test]
+    fn ttl() {
+        let evicted = Rc::new(RefCell::new(Vec::new()));
+        let log = Rc::clone(&evicted);
+        let now = Rc::new(Cell::new(Instant::now()));
+        let clock = Rc::clone(&now);
+        let mut cache = LruCache::new(3)
+            .with_clock(move || clock.get())
+            .on_evict(move |k, v, reason| log.borrow_mut().push((k, v, reason)));
+        let last = || RefCell::borrow(&evicted).last().copied();
+        let advance = |ms| now.set(now.get() + Duration::from_millis(ms));
+        cache.put_with_ttl("gone", 4, Duration::ZERO);
+        cache.put_with_ttl("short", 1, Duration::from_millis(20));
+        cache.put("forever", 3);
+        assert_eq!(None, cache.peek("gone"));
+        assert_eq!(3, cache.len());
+        cache.put_with_ttl("long", 2, Duration::from_secs(60));
+        assert_eq!(Some(("gone", 4, EvictionReason::Expired)), last());
+
+        advance(30);
+        assert!(!cache.contains_key("short"));
+        assert_eq!(None, cache.get("short"));
+        assert_eq!(Some(("short", 1, EvictionReason::Expired)), last());
+        assert_eq!(Some(&2), cache.get("long"));
+        assert_eq!(0, cache.purge_expired());
+        assert_eq!(2, cache.len());
+
+        // put without ttl makes an entry permanent
+        cache.put_with_ttl("tmp", 6, Duration::from_millis(20));
+        assert_eq!(Some(6), cache.put("tmp", 7));
+        advance(30);
+        assert_eq!(Some(&7), cache.peek("tmp"));
+
+        cache.put("x", 0);
+        assert_eq!(Some(("forever", 3, EvictionReason::Capacity)), last());
+        cache.put_with_ttl("a", 8, Duration::ZERO);
+        assert_eq!(Some(("long", 2, EvictionReason::Capacity)), last());
+        assert_eq!(1, cache.purge_expired());
+        assert_eq!(Some(("a", 8, EvictionReason::Expired)), last());
+        assert_eq!(Some(("tmp", 7)), cache.pop_lru());
+        assert_eq!(5, RefCell::borrow(&evicted).len());
+
+        // remove只通知过期的条目
+        cache.put_with_ttl("b", 9, Duration::from_millis(20));
+        advance(19);
+        assert_eq!(Some(&9), cache.peek("b"));
+        advance(1);
+        assert_eq!(None, cache.remove("b"));
+        assert_eq!(Some(("b", 9, EvictionReason::Expired)), last());
+        assert_eq!(None, cache.remove("b"));
+        assert_eq!(Some(0), cache.remove("x"));
+        assert_eq!(6, RefCell::borrow(&evicted).len());
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity must be positive")]
+    fn zero_capacity() {
+        let _: LruCache<i32, i32> = LruCache::new(0);
+    }
+}
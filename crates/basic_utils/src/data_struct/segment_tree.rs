@@ -0,0 +1,451 @@
+//!
+//! segment tree over any associative operation, with lazy range updates
+//!
+//! ```
+//! use basic_utils::data_struct::{Max, SegmentTree};
+//!
+//! // 最近8个采样点的延迟，查询任意窗口内的最大值
+//! let mut latency = SegmentTree::new(&[12, 7, 30, 9, 15, 8, 22, 11], Max);
+//! assert_eq!(30, latency.query(0..4));
+//! assert_eq!(22, latency.query(4..));
+//! latency.set(2, 5);
+//! assert_eq!(12, latency.query(..4));
+//! latency.add(3..6, 10);
+//! assert_eq!(25, latency.query(..));
+//! ```
+//!
+use std::{
+    fmt,
+    ops::{Add, RangeBounds},
+};
+
+use super::index_range;
+
+///
+/// an associative operation with an identity element (a monoid)
+///
+/// 要求`combine(a, combine(b, c)) == combine(combine(a, b), c)`，
+/// 并且`combine(identity(), a) == combine(a, identity()) == a`。不要求交换律
+///
+pub trait Operation<T> {
+    fn identity(&self) -> T;
+
+    fn combine(&self, a: &T, b: &T) -> T;
+
+    /// `value` combined with itself `n` times, the identity for `n == 0`
+    ///
+    /// 默认按二进制拆分，调用O(log n)次combine。幂等的操作(min, max, gcd)可以直接返回value
+    fn repeat(&self, value: &T, mut n: usize) -> T {
+        let mut r = self.identity();
+        let mut base = self.combine(&r, value);
+        while n > 0 {
+            if n & 1 == 1 {
+                r = self.combine(&r, &base);
+            }
+            n >>= 1;
+            if n > 0 {
+                base = self.combine(&base, &base);
+            }
+        }
+        r
+    }
+}
+
+///
+/// marker for operations which support [SegmentTree::add]
+///
+/// 要求`combine(a + d, b + d) == combine(a, b) + combine(d, d)`，
+/// 这样给区间内每个元素加d之后，区间的结果等于原结果加上`repeat(d, 区间长度)`。sum, min, max满足，gcd不满足
+///
+pub trait RangeAdd<T>: Operation<T> {}
+
+/// `a + b`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Sum;
+
+/// the smaller one, NaN is ignored for floats
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Min;
+
+/// the larger one, NaN is ignored for floats
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Max;
+
+/// greatest common divisor, always non-negative
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Gcd;
+
+macro_rules! impl_ordered_ops {
+    ($zero:literal, $min:expr, $max:expr, $($t:ty)*) => {$(
+        impl Operation<$t> for Sum {
+            fn identity(&self) -> $t {
+                $zero
+            }
+
+            fn combine(&self, a: &$t, b: &$t) -> $t {
+                a + b
+            }
+        }
+
+        impl RangeAdd<$t> for Sum {}
+
+        impl Operation<$t> for Min {
+            fn identity(&self) -> $t {
+                $max
+            }
+
+            fn combine(&self, a: &$t, b: &$t) -> $t {
+                (*a).min(*b)
+            }
+
+            fn repeat(&self, value: &$t, n: usize) -> $t {
+                if n == 0 { $max } else { *value }
+            }
+        }
+
+        impl RangeAdd<$t> for Min {}
+
+        impl Operation<$t> for Max {
+            fn identity(&self) -> $t {
+                $min
+            }
+
+            fn combine(&self, a: &$t, b: &$t) -> $t {
+                (*a).max(*b)
+            }
+
+            fn repeat(&self, value: &$t, n: usize) -> $t {
+                if n == 0 { $min } else { *value }
+            }
+        }
+
+        impl RangeAdd<$t> for Max {}
+    )*};
+}
+
+macro_rules! impl_int_ops {
+    ($abs:ident, $($t:ident)*) => {$(
+        impl_ordered_ops!(0, $t::MIN, $t::MAX, $t);
+
+        impl Operation<$t> for Gcd {
+            fn identity(&self) -> $t {
+                0
+            }
+
+            fn combine(&self, a: &$t, b: &$t) -> $t {
+                let (mut a, mut b) = (*a, *b);
+                while b != 0 {
+                    (a, b) = (b, a % b);
+                }
+                $abs(a)
+            }
+        }
+    )*};
+}
+
+fn signed_abs<T: PartialOrd + Default + std::ops::Neg<Output = T>>(a: T) -> T {
+    if a < T::default() {
+        -a
+    } else {
+        a
+    }
+}
+
+fn unsigned_abs<T>(a: T) -> T {
+    a
+}
+
+impl_int_ops!(signed_abs, i8 i16 i32 i64 i128 isize);
+impl_int_ops!(unsigned_abs, u8 u16 u32 u64 u128 usize);
+impl_ordered_ops!(0.0, f32::NEG_INFINITY, f32::INFINITY, f32);
+impl_ordered_ops!(0.0, f64::NEG_INFINITY, f64::INFINITY, f64);
+
+/// update waiting to be pushed down to the children
+#[derive(Clone)]
+enum Pending<T> {
+    Assign(T),
+    Add(T),
+}
+
+///
+/// segment tree: range query, point update and lazy range update in O(log n)
+///
+/// 节点1是根，节点i的子节点是2i和2i+1。`tree[i]`总是包含了`lazy[i]`的结果，`lazy[i]`还没有下推给子节点。
+/// 查询只需要`&self`：遇到带lazy标记的节点时，部分覆盖的结果可以由标记直接算出
+///
+/// ```
+/// use basic_utils::data_struct::{Gcd, SegmentTree, Sum};
+///
+/// let mut t = SegmentTree::new(&[1, 2, 3, 4, 5], Sum);
+/// t.assign(1..4, 10);
+/// assert_eq!(vec![1, 10, 10, 10, 5], t.to_vec());
+/// t.add(..2, -1);
+/// assert_eq!(34, t.query(1..));
+///
+/// let mut t = SegmentTree::new(&[12, 18, 24, 7], Gcd);
+/// assert_eq!(6, t.query(..3));
+/// t.assign(3.., 30);
+/// assert_eq!(6, t.query(..));
+/// ```
+///
+pub struct SegmentTree<T, Op> {
+    len: usize,
+    tree: Vec<T>,
+    lazy: Vec<Option<Pending<T>>>,
+    op: Op,
+    /// `T`的加法，第一次调用[SegmentTree::add]时设置，只有那之后才会出现[Pending::Add]
+    plus: Option<fn(&T, &T) -> T>,
+}
+
+impl<T: Clone, Op: Operation<T>> SegmentTree<T, Op> {
+    pub fn new(values: &[T], op: Op) -> Self {
+        let nodes = if values.is_empty() {
+            0
+        } else {
+            2 * values.len().next_power_of_two()
+        };
+        let mut t = SegmentTree {
+            len: values.len(),
+            tree: vec![op.identity(); nodes],
+            lazy: vec![None; nodes],
+            op,
+            plus: None,
+        };
+        if !values.is_empty() {
+            t.build(1, 0, values.len(), values);
+        }
+        t
+    }
+
+    fn build(&mut self, node: usize, lo: usize, hi: usize, values: &[T]) {
+        if hi - lo == 1 {
+            // 和set一样经过repeat，gcd的负数叶子会变成绝对值
+            self.tree[node] = self.op.repeat(&values[lo], 1);
+            return;
+        }
+        let mid = (lo + hi) / 2;
+        self.build(2 * node, lo, mid, values);
+        self.build(2 * node + 1, mid, hi, values);
+        self.pull(node);
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// combine all elements in `range`, the identity for an empty range
+    pub fn query(&self, range: impl RangeBounds<usize>) -> T {
+        let r = index_range(range, self.len);
+        if r.is_empty() {
+            return self.op.identity();
+        }
+        self.query_node(1, 0, self.len, r.start, r.end)
+    }
+
+    fn query_node(&self, node: usize, lo: usize, hi: usize, l: usize, r: usize) -> T {
+        if l <= lo && hi <= r {
+            return self.tree[node].clone();
+        }
+        let covered = r.min(hi) - l.max(lo);
+        if let Some(Pending::Assign(v)) = &self.lazy[node] {
+            return self.op.repeat(v, covered);
+        }
+        let mid = (lo + hi) / 2;
+        let result = match (l < mid, r > mid) {
+            (true, true) => self.op.combine(
+                &self.query_node(2 * node, lo, mid, l, r),
+                &self.query_node(2 * node + 1, mid, hi, l, r),
+            ),
+            (true, false) => self.query_node(2 * node, lo, mid, l, r),
+            _ => self.query_node(2 * node + 1, mid, hi, l, r),
+        };
+        match &self.lazy[node] {
+            Some(Pending::Add(d)) => self.plus()(&result, &self.op.repeat(d, covered)),
+            _ => result,
+        }
+    }
+
+    pub fn get(&self, index: usize) -> T {
+        self.query(index..=index)
+    }
+
+    /// all elements in order
+    pub fn to_vec(&self) -> Vec<T> {
+        (0..self.len).map(|i| self.get(i)).collect()
+    }
+
+    ///
+    /// replace the element at `index`
+    ///
+    /// # Panics
+    ///
+    /// panics if `index` is out of range
+    ///
+    pub fn set(&mut self, index: usize, value: T) {
+        assert!(index < self.len, "index {} out of range for length {}", index, self.len);
+        self.update(1, 0, self.len, index, index + 1, Pending::Assign(value));
+    }
+
+    /// set every element in `range` to `value`
+    pub fn assign(&mut self, range: impl RangeBounds<usize>, value: T) {
+        let r = index_range(range, self.len);
+        if !r.is_empty() {
+            self.update(1, 0, self.len, r.start, r.end, Pending::Assign(value));
+        }
+    }
+
+    /// add `delta` to every element in `range`
+    pub fn add(&mut self, range: impl RangeBounds<usize>, delta: T)
+    where
+        T: Add<Output = T>,
+        Op: RangeAdd<T>,
+    {
+        let r = index_range(range, self.len);
+        if !r.is_empty() {
+            self.plus.get_or_insert(|a, b| a.clone() + b.clone());
+            self.update(1, 0, self.len, r.start, r.end, Pending::Add(delta));
+        }
+    }
+
+    fn plus(&self) -> fn(&T, &T) -> T {
+        self.plus.expect("Pending::Add only exists after SegmentTree::add")
+    }
+
+    fn update(&mut self, node: usize, lo: usize, hi: usize, l: usize, r: usize, p: Pending<T>) {
+        if l <= lo && hi <= r {
+            self.apply(node, hi - lo, p);
+            return;
+        }
+        let mid = (lo + hi) / 2;
+        self.push_down(node, lo, mid, hi);
+        if l < mid {
+            self.update(2 * node, lo, mid, l, r, p.clone());
+        }
+        if r > mid {
+            self.update(2 * node + 1, mid, hi, l, r, p);
+        }
+        self.pull(node);
+    }
+
+    /// apply `p` to every element under `node`, which covers `len` elements
+    fn apply(&mut self, node: usize, len: usize, p: Pending<T>) {
+        let pending = match p {
+            Pending::Assign(v) => {
+                self.tree[node] = self.op.repeat(&v, len);
+                Pending::Assign(v)
+            }
+            Pending::Add(d) => {
+                let plus = self.plus();
+                self.tree[node] = plus(&self.tree[node], &self.op.repeat(&d, len));
+                // 和已有的标记合并
+                match self.lazy[node].take() {
+                    Some(Pending::Assign(v)) => Pending::Assign(plus(&v, &d)),
+                    Some(Pending::Add(e)) => Pending::Add(plus(&e, &d)),
+                    None => Pending::Add(d),
+                }
+            }
+        };
+        // 叶子节点没有子节点，不需要标记
+        if len > 1 {
+            self.lazy[node] = Some(pending);
+        }
+    }
+
+    fn push_down(&mut self, node: usize, lo: usize, mid: usize, hi: usize) {
+        if let Some(p) = self.lazy[node].take() {
+            self.apply(2 * node, mid - lo, p.clone());
+            self.apply(2 * node + 1, hi - mid, p);
+        }
+    }
+
+    fn pull(&mut self, node: usize) {
+        self.tree[node] = self.op.combine(&self.tree[2 * node], &self.tree[2 * node + 1]);
+    }
+}
+
+impl<T: Clone + fmt::Debug, Op: Operation<T>> fmt::Debug for SegmentTree<T, Op> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.to_vec()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 用LCG生成随机操作，和直接操作Vec的结果比较
+    fn check<Op: Operation<i64>>(op: Op, with_add: impl Fn(&mut SegmentTree<i64, Op>, usize, usize, i64) -> bool) {
+        let n = 45;
+        let mut model: Vec<i64> = (0..n as i64).map(|i| i * 7 % 11 - 3).collect();
+        let mut t = SegmentTree::new(&model, op);
+        let mut x: u32 = 17;
+        for _ in 0..800 {
+            x = x.wrapping_mul(1103515245).wrapping_add(12345);
+            let (a, b) = ((x >> 4) as usize % (n + 1), (x >> 20) as usize % (n + 1));
+            let (a, b) = (a.min(b), a.max(b));
+            let v = (x >> 12) as i64 % 50 - 20;
+            match x % 4 {
+                0 => {
+                    model[a..b].iter_mut().for_each(|m| *m = v);
+                    t.assign(a..b, v);
+                }
+                1 if a < n => {
+                    model[a] = v;
+                    t.set(a, v);
+                }
+                2 if with_add(&mut t, a, b, v) => {
+                    model[a..b].iter_mut().for_each(|m| *m += v);
+                }
+                _ => {}
+            }
+            let (c, d) = ((x >> 8) as usize % (n + 1), (x >> 24) as usize % (n + 1));
+            let (c, d) = (c.min(d), c.max(d));
+            let expected = model[c..d].iter().fold(t.op.identity(), |acc, m| t.op.combine(&acc, m));
+            assert_eq!(expected, t.query(c..d));
+        }
+        assert_eq!(model, t.to_vec());
+    }
+
+    #[test]
+    fn matches_naive() {
+        check(Sum, |t, a, b, v| {
+            t.add(a..b, v);
+            true
+        });
+        check(Min, |t, a, b, v| {
+            t.add(a..b, v);
+            true
+        });
+        check(Max, |t, a, b, v| {
+            t.add(a..b, v);
+            true
+        });
+        check(Gcd, |_, _, _, _| false);
+    }
+
+    #[test]
+    fn repeat_and_identity() {
+        assert_eq!(0, Operation::<i32>::repeat(&Sum, &5, 0));
+        assert_eq!(35, Operation::<i32>::repeat(&Sum, &5, 7));
+        assert_eq!(5, Operation::<u8>::repeat(&Gcd, &5, 7));
+        assert_eq!(4, Operation::<i32>::combine(&Gcd, &-12, &8));
+        assert_eq!(f64::INFINITY, Operation::<f64>::repeat(&Min, &1.0, 0));
+
+        let t = SegmentTree::new(&[1.5, f64::NAN, -2.0], Min);
+        assert_eq!(-2.0, t.query(..));
+        let t: SegmentTree<i32, Sum> = SegmentTree::new(&[], Sum);
+        assert_eq!(0, t.query(..));
+        assert!(t.is_empty());
+        assert_eq!("[]", format!("{:?}", t));
+    }
+
+    #[test]
+    #[should_panic(expected = "index 3 out of range for length 3")]
+    fn set_out_of_range() {
+        SegmentTree::new(&[1, 2, 3], Max).set(3, 0);
+    }
+}
@@ -0,0 +1,287 @@
+//!
+//! lazy traversal of [BinaryTreeNode]
+//!
+//! 所有迭代器都使用显式的栈或队列，不会递归，每次next只访问必要的节点。
+//! 迭代器返回节点的Rc，调用values()只返回节点的值。
+//!
+//! ```
+//! use basic_utils::data_struct::{BinaryTreeNode, InOrder, Levels};
+//!
+//! let root = BinaryTreeNode::<i32>::from_level_order_str("[4,2,6,1,3,5]").unwrap();
+//! assert_eq!(vec![1, 2, 3, 4, 5, 6], InOrder::new(root.as_ref()).values().collect::<Vec<_>>());
+//! assert_eq!(vec![vec![4], vec![2, 6], vec![1, 3, 5]], Levels::new(root.as_ref()).values().collect::<Vec<_>>());
+//! ```
+//!
+use std::{cell::RefCell, collections::VecDeque, iter::FusedIterator, rc::Rc};
+
+use super::BinaryTreeNode;
+
+type Node<T> = Rc<RefCell<BinaryTreeNode<T>>>;
+
+fn node_val<T: Clone>(node: Node<T>) -> T {
+    node.borrow().val.clone()
+}
+
+/// root -> left -> right
+pub struct PreOrder<T = i32> {
+    stack: Vec<Node<T>>,
+}
+
+impl<T> PreOrder<T> {
+    pub fn new(root: Option<&Node<T>>) -> Self {
+        PreOrder {
+            stack: root.into_iter().cloned().collect(),
+        }
+    }
+
+    pub fn values(self) -> impl Iterator<Item = T>
+    where
+        T: Clone,
+    {
+        self.map(node_val)
+    }
+}
+
+impl<T> Iterator for PreOrder<T> {
+    type Item = Node<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        {
+            let n = node.borrow();
+            // 先压右节点，保证左节点先出栈
+            self.stack.extend(n.right.iter().cloned());
+            self.stack.extend(n.left.iter().cloned());
+        }
+        Some(node)
+    }
+}
+
+impl<T> FusedIterator for PreOrder<T> {}
+
+/// left -> root -> right
+pub struct InOrder<T = i32> {
+    stack: Vec<Node<T>>,
+    cur: Option<Node<T>>,
+}
+
+impl<T> InOrder<T> {
+    pub fn new(root: Option<&Node<T>>) -> Self {
+        InOrder {
+            stack: Vec::new(),
+            cur: root.cloned(),
+        }
+    }
+
+    pub fn values(self) -> impl Iterator<Item = T>
+    where
+        T: Clone,
+    {
+        self.map(node_val)
+    }
+}
+
+impl<T> Iterator for InOrder<T> {
+    type Item = Node<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // 一直向左走到底，沿途节点入栈
+        while let Some(node) = self.cur.take() {
+            self.cur = node.borrow().left.clone();
+            self.stack.push(node);
+        }
+        let node = self.stack.pop()?;
+        self.cur = node.borrow().right.clone();
+        Some(node)
+    }
+}
+
+impl<T> FusedIterator for InOrder<T> {}
+
+/// left -> right -> root
+pub struct PostOrder<T = i32> {
+    // bool代表子节点是否已经入栈
+    stack: Vec<(Node<T>, bool)>,
+}
+
+impl<T> PostOrder<T> {
+    pub fn new(root: Option<&Node<T>>) -> Self {
+        PostOrder {
+            stack: root.into_iter().map(|n| (Rc::clone(n), false)).collect(),
+        }
+    }
+
+    pub fn values(self) -> impl Iterator<Item = T>
+    where
+        T: Clone,
+    {
+        self.map(node_val)
+    }
+}
+
+impl<T> Iterator for PostOrder<T> {
+    type Item = Node<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (node, expanded) = self.stack.pop()?;
+            if expanded {
+                return Some(node);
+            }
+            let (left, right) = {
+                let n = node.borrow();
+                (n.left.clone(), n.right.clone())
+            };
+            self.stack.push((node, true));
+            self.stack.extend(right.map(|n| (n, false)));
+            self.stack.extend(left.map(|n| (n, false)));
+        }
+    }
+}
+
+impl<T> FusedIterator for PostOrder<T> {}
+
+/// breadth first, yield `(depth, node)`, the depth of root is 0
+pub struct LevelOrder<T = i32> {
+    queue: VecDeque<(usize, Node<T>)>,
+}
+
+impl<T> LevelOrder<T> {
+    pub fn new(root: Option<&Node<T>>) -> Self {
+        LevelOrder {
+            queue: root.into_iter().map(|n| (0, Rc::clone(n))).collect(),
+        }
+    }
+
+    pub fn values(self) -> impl Iterator<Item = (usize, T)>
+    where
+        T: Clone,
+    {
+        self.map(|(depth, node)| (depth, node_val(node)))
+    }
+}
+
+impl<T> Iterator for LevelOrder<T> {
+    type Item = (usize, Node<T>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (depth, node) = self.queue.pop_front()?;
+        {
+            let n = node.borrow();
+            for child in [&n.left, &n.right].into_iter().flatten() {
+                self.queue.push_back((depth + 1, Rc::clone(child)));
+            }
+        }
+        Some((depth, node))
+    }
+}
+
+impl<T> FusedIterator for LevelOrder<T> {}
+
+/// breadth first, yield all nodes of one depth at a time
+pub struct Levels<T = i32> {
+    level: Vec<Node<T>>,
+}
+
+impl<T> Levels<T> {
+    pub fn new(root: Option<&Node<T>>) -> Self {
+        Levels {
+            level: root.into_iter().cloned().collect(),
+        }
+    }
+
+    pub fn values(self) -> impl Iterator<Item = Vec<T>>
+    where
+        T: Clone,
+    {
+        self.map(|level| level.into_iter().map(node_val).collect())
+    }
+}
+
+impl<T> Iterator for Levels<T> {
+    type Item = Vec<Node<T>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.level.is_empty() {
+            return None;
+        }
+        let mut next = Vec::new();
+        for node in &self.level {
+            let n = node.borrow();
+            next.extend(n.left.iter().cloned());
+            next.extend(n.right.iter().cloned());
+        }
+        Some(std::mem::replace(&mut self.level, next))
+    }
+}
+
+impl<T> FusedIterator for Levels<T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    //        1
+    //      /   \
+    //     2     3
+    //    / \   /
+    //   4   5 6
+    //        \
+    //         7
+    const TREE: &str = "[1,2,3,4,5,6,null,null,null,null,7]";
+
+    fn tree() -> Option<Node<i32>> {
+        BinaryTreeNode::from_level_order_str(TREE).unwrap()
+    }
+
+    #[test]
+    fn depth_first() {
+        let root = tree();
+        assert_eq!(
+            vec![1, 2, 4, 5, 7, 3, 6],
+            PreOrder::new(root.as_ref()).values().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![4, 2, 5, 7, 1, 6, 3],
+            InOrder::new(root.as_ref()).values().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![4, 7, 5, 2, 6, 3, 1],
+            PostOrder::new(root.as_ref()).values().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn breadth_first() {
+        let root = tree();
+        assert_eq!(
+            vec![(0, 1), (1, 2), (1, 3), (2, 4), (2, 5), (2, 6), (3, 7)],
+            LevelOrder::new(root.as_ref()).values().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![vec![1], vec![2, 3], vec![4, 5, 6], vec![7]],
+            Levels::new(root.as_ref()).values().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn lazy_and_handles() {
+        let root = tree();
+        // 只访问前两个节点
+        let mut it = PreOrder::new(root.as_ref());
+        let first = it.next().unwrap();
+        assert!(Rc::ptr_eq(root.as_ref().unwrap(), &first));
+        first.borrow_mut().val = 10;
+        assert_eq!(Some(2), it.next().map(node_val));
+        assert_eq!(10, root.unwrap().borrow().val);
+    }
+
+    #[test]
+    fn empty_tree() {
+        assert_eq!(0, PreOrder::<i32>::new(None).count());
+        assert_eq!(0, InOrder::<i32>::new(None).count());
+        assert_eq!(0, PostOrder::<i32>::new(None).count());
+        assert_eq!(0, LevelOrder::<i32>::new(None).count());
+        assert_eq!(0, Levels::<i32>::new(None).count());
+    }
+}
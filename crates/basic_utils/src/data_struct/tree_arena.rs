@@ -0,0 +1,598 @@
+//!
+//! binary trees stored in one `Vec`, an alternative to `Rc<RefCell<BinaryTreeNode>>`
+//!
+//! 所有节点放在同一个Vec中，节点之间用下标连接：创建节点不需要单独的堆分配，
+//! 遍历时也没有Rc的引用计数和RefCell的借用检查。节点同时记录父节点，可以向上走。
+//!
+//! ```
+//! use basic_utils::data_struct::{BinaryTreeNode, TreeArena};
+//!
+//! let rc = BinaryTreeNode::<i32>::from_level_order_str("[4,2,6,1,3,5]").unwrap();
+//! let mut arena = TreeArena::new();
+//! let root = arena.insert_rc(rc.as_ref()).unwrap();
+//! assert_eq!(vec![&1, &2, &3, &4, &5, &6], arena.in_order(root).values().collect::<Vec<_>>());
+//!
+//! let five = arena.in_order(root).nth(4).unwrap();
+//! assert_eq!(Some(6), arena.parent(five).map(|p| arena[p]));
+//! assert_eq!(rc, Some(arena.to_rc(root)));
+//! ```
+//!
+use std::{cell::RefCell, collections::VecDeque, fmt, iter::FusedIterator, ops, rc::Rc};
+
+use super::BinaryTreeNode;
+
+type RcNode<T> = Rc<RefCell<BinaryTreeNode<T>>>;
+
+/// handle of a node, only valid for the arena which created it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TreeId(usize);
+
+impl TreeId {
+    /// nodes are numbered from 0 in insertion order
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+impl fmt::Display for TreeId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+struct Node<T> {
+    val: T,
+    parent: Option<TreeId>,
+    left: Option<TreeId>,
+    right: Option<TreeId>,
+}
+
+///
+/// arena of binary tree nodes, it may hold several trees at once
+///
+/// 节点只增不删，[TreeArena::set_left]等方法断开的子树仍然留在arena中，成为一棵独立的树
+///
+/// ```
+/// use basic_utils::data_struct::TreeArena;
+///
+/// //     1
+/// //    / \
+/// //   2   3
+/// //        \
+/// //         4
+/// let mut t = TreeArena::new();
+/// let root = t.add(1);
+/// t.add_left(root, 2);
+/// let three = t.add_right(root, 3);
+/// let four = t.add_right(three, 4);
+/// assert_eq!(vec![&1, &2, &3, &4], t.pre_order(root).values().collect::<Vec<_>>());
+/// assert_eq!(vec![vec![&1], vec![&2, &3], vec![&4]], t.levels(root).values().collect::<Vec<_>>());
+/// assert_eq!(root, t.root_of(four));
+/// assert_eq!(3, t.height(root));
+/// ```
+///
+pub struct TreeArena<T> {
+    nodes: Vec<Node<T>>,
+}
+
+impl<T> TreeArena<T> {
+    pub fn new() -> Self {
+        TreeArena { nodes: Vec::new() }
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        TreeArena {
+            nodes: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// number of nodes of all trees
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// add a node without parent or children, it is the root of a new tree
+    pub fn add(&mut self, val: T) -> TreeId {
+        self.nodes.push(Node {
+            val,
+            parent: None,
+            left: None,
+            right: None,
+        });
+        TreeId(self.nodes.len() - 1)
+    }
+
+    ///
+    /// add a node as the left child of `parent`
+    ///
+    /// # Panics
+    ///
+    /// panics if `parent` already has a left child
+    ///
+    pub fn add_left(&mut self, parent: TreeId, val: T) -> TreeId {
+        assert!(self.nodes[parent.0].left.is_none(), "left child already exists");
+        let child = self.add(val);
+        self.attach(parent, child, true);
+        child
+    }
+
+    ///
+    /// add a node as the right child of `parent`
+    ///
+    /// # Panics
+    ///
+    /// panics if `parent` already has a right child
+    ///
+    pub fn add_right(&mut self, parent: TreeId, val: T) -> TreeId {
+        assert!(self.nodes[parent.0].right.is_none(), "right child already exists");
+        let child = self.add(val);
+        self.attach(parent, child, false);
+        child
+    }
+
+    ///
+    /// replace the left subtree of `parent` with the tree rooted at `child`, return the old left child
+    ///
+    /// 旧的左子树成为独立的树
+    ///
+    /// # Panics
+    ///
+    /// panics if `child` is not a root, or `child` is the root of the tree containing `parent`
+    ///
+    pub fn set_left(&mut self, parent: TreeId, child: Option<TreeId>) -> Option<TreeId> {
+        self.replace_child(parent, child, true)
+    }
+
+    ///
+    /// replace the right subtree of `parent` with the tree rooted at `child`, return the old right child
+    ///
+    /// # Panics
+    ///
+    /// panics if `child` is not a root, or `child` is the root of the tree containing `parent`
+    ///
+    pub fn set_right(&mut self, parent: TreeId, child: Option<TreeId>) -> Option<TreeId> {
+        self.replace_child(parent, child, false)
+    }
+
+    fn replace_child(&mut self, parent: TreeId, child: Option<TreeId>, left: bool) -> Option<TreeId> {
+        if let Some(c) = child {
+            assert!(self.nodes[c.0].parent.is_none(), "child already has a parent");
+            // child是根，只有当它是parent所在树的根时才会成环
+            assert!(self.root_of(parent) != c, "child is an ancestor of parent");
+        }
+        let slot = if left {
+            &mut self.nodes[parent.0].left
+        } else {
+            &mut self.nodes[parent.0].right
+        };
+        let old = slot.take();
+        if let Some(o) = old {
+            self.nodes[o.0].parent = None;
+        }
+        if let Some(c) = child {
+            self.attach(parent, c, left);
+        }
+        old
+    }
+
+    fn attach(&mut self, parent: TreeId, child: TreeId, left: bool) {
+        self.nodes[child.0].parent = Some(parent);
+        let p = &mut self.nodes[parent.0];
+        if left {
+            p.left = Some(child);
+        } else {
+            p.right = Some(child);
+        }
+    }
+
+    pub fn get(&self, id: TreeId) -> Option<&T> {
+        self.nodes.get(id.0).map(|n| &n.val)
+    }
+
+    pub fn get_mut(&mut self, id: TreeId) -> Option<&mut T> {
+        self.nodes.get_mut(id.0).map(|n| &mut n.val)
+    }
+
+    pub fn parent(&self, id: TreeId) -> Option<TreeId> {
+        self.nodes[id.0].parent
+    }
+
+    pub fn left(&self, id: TreeId) -> Option<TreeId> {
+        self.nodes[id.0].left
+    }
+
+    pub fn right(&self, id: TreeId) -> Option<TreeId> {
+        self.nodes[id.0].right
+    }
+
+    /// the root of the tree containing `id`
+    pub fn root_of(&self, mut id: TreeId) -> TreeId {
+        while let Some(p) = self.nodes[id.0].parent {
+            id = p;
+        }
+        id
+    }
+
+    /// number of nodes on the longest path from `root` to a leaf
+    pub fn height(&self, root: TreeId) -> usize {
+        self.levels(root).count()
+    }
+
+    pub fn pre_order(&self, root: TreeId) -> PreOrder<'_, T> {
+        PreOrder {
+            arena: self,
+            stack: vec![root],
+        }
+    }
+
+    pub fn in_order(&self, root: TreeId) -> InOrder<'_, T> {
+        InOrder {
+            arena: self,
+            stack: Vec::new(),
+            cur: Some(root),
+        }
+    }
+
+    pub fn post_order(&self, root: TreeId) -> PostOrder<'_, T> {
+        PostOrder {
+            arena: self,
+            stack: vec![(root, false)],
+        }
+    }
+
+    pub fn level_order(&self, root: TreeId) -> LevelOrder<'_, T> {
+        LevelOrder {
+            arena: self,
+            queue: VecDeque::from([(0, root)]),
+        }
+    }
+
+    pub fn levels(&self, root: TreeId) -> Levels<'_, T> {
+        Levels {
+            arena: self,
+            level: vec![root],
+        }
+    }
+
+    /// copy a `Rc` tree into the arena, return the id of the copied root
+    pub fn insert_rc(&mut self, root: Option<&RcNode<T>>) -> Option<TreeId>
+    where
+        T: Clone,
+    {
+        let root = root?;
+        let id = self.add(root.borrow().val.clone());
+        // 栈中是(已复制的父节点, 是否左子节点, 待复制的Rc节点)
+        let mut stack = Vec::new();
+        let push_children = |stack: &mut Vec<_>, parent: TreeId, node: &RcNode<T>| {
+            let n = node.borrow();
+            stack.extend(n.right.clone().map(|c| (parent, false, c)));
+            stack.extend(n.left.clone().map(|c| (parent, true, c)));
+        };
+        push_children(&mut stack, id, root);
+        while let Some((parent, left, node)) = stack.pop() {
+            let child = self.add(node.borrow().val.clone());
+            self.attach(parent, child, left);
+            push_children(&mut stack, child, &node);
+        }
+        Some(id)
+    }
+
+    /// copy the tree rooted at `root` to a `Rc` tree
+    pub fn to_rc(&self, root: TreeId) -> RcNode<T>
+    where
+        T: Clone,
+    {
+        // 后序遍历保证子节点先于父节点创建
+        let mut built: Vec<Option<RcNode<T>>> = Vec::new();
+        built.resize_with(self.nodes.len(), || None);
+        for id in self.post_order(root) {
+            let n = &self.nodes[id.0];
+            let mut take = |c: Option<TreeId>| c.and_then(|c| built[c.0].take());
+            let node = BinaryTreeNode::from(take(n.left), take(n.right), n.val.clone());
+            built[id.0] = Some(Rc::new(RefCell::new(node)));
+        }
+        built[root.0].take().expect("root is built last")
+    }
+}
+
+impl<T> Default for TreeArena<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> ops::Index<TreeId> for TreeArena<T> {
+    type Output = T;
+
+    fn index(&self, id: TreeId) -> &T {
+        &self.nodes[id.0].val
+    }
+}
+
+impl<T> ops::IndexMut<TreeId> for TreeArena<T> {
+    fn index_mut(&mut self, id: TreeId) -> &mut T {
+        &mut self.nodes[id.0].val
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for TreeArena<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(
+                self.nodes
+                    .iter()
+                    .enumerate()
+                    .map(|(i, n)| (i, (&n.val, n.left.map(TreeId::index), n.right.map(TreeId::index)))),
+            )
+            .finish()
+    }
+}
+
+/// root -> left -> right
+pub struct PreOrder<'a, T> {
+    arena: &'a TreeArena<T>,
+    stack: Vec<TreeId>,
+}
+
+impl<'a, T> PreOrder<'a, T> {
+    pub fn values(self) -> impl Iterator<Item = &'a T> {
+        let arena = self.arena;
+        self.map(move |id| &arena[id])
+    }
+}
+
+impl<T> Iterator for PreOrder<'_, T> {
+    type Item = TreeId;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let id = self.stack.pop()?;
+        let n = &self.arena.nodes[id.0];
+        self.stack.extend(n.right);
+        self.stack.extend(n.left);
+        Some(id)
+    }
+}
+
+impl<T> FusedIterator for PreOrder<'_, T> {}
+
+/// left -> root -> right
+pub struct InOrder<'a, T> {
+    arena: &'a TreeArena<T>,
+    stack: Vec<TreeId>,
+    cur: Option<TreeId>,
+}
+
+impl<'a, T> InOrder<'a, T> {
+    pub fn values(self) -> impl Iterator<Item = &'a T> {
+        let arena = self.arena;
+        self.map(move |id| &arena[id])
+    }
+}
+
+impl<T> Iterator for InOrder<'_, T> {
+    type Item = TreeId;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(id) = self.cur.take() {
+            self.cur = self.arena.nodes[id.0].left;
+            self.stack.push(id);
+        }
+        let id = self.stack.pop()?;
+        self.cur = self.arena.nodes[id.0].right;
+        Some(id)
+    }
+}
+
+impl<T> FusedIterator for InOrder<'_, T> {}
+
+/// left -> right -> root
+pub struct PostOrder<'a, T> {
+    arena: &'a TreeArena<T>,
+    // bool代表子节点是否已经入栈
+    stack: Vec<(TreeId, bool)>,
+}
+
+impl<'a, T> PostOrder<'a, T> {
+    pub fn values(self) -> impl Iterator<Item = &'a T> {
+        let arena = self.arena;
+        self.map(move |id| &arena[id])
+    }
+}
+
+impl<T> Iterator for PostOrder<'_, T> {
+    type Item = TreeId;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (id, expanded) = self.stack.pop()?;
+            if expanded {
+                return Some(id);
+            }
+            let n = &self.arena.nodes[id.0];
+            self.stack.push((id, true));
+            self.stack.extend(n.right.map(|c| (c, false)));
+            self.stack.extend(n.left.map(|c| (c, false)));
+        }
+    }
+}
+
+impl<T> FusedIterator for PostOrder<'_, T> {}
+
+/// breadth first, yield `(depth, node)`, the depth of root is 0
+pub struct LevelOrder<'a, T> {
+    arena: &'a TreeArena<T>,
+    queue: VecDeque<(usize, TreeId)>,
+}
+
+impl<'a, T> LevelOrder<'a, T> {
+    pub fn values(self) -> impl Iterator<Item = (usize, &'a T)> {
+        let arena = self.arena;
+        self.map(move |(depth, id)| (depth, &arena[id]))
+    }
+}
+
+impl<T> Iterator for LevelOrder<'_, T> {
+    type Item = (usize, TreeId);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (depth, id) = self.queue.pop_front()?;
+        let n = &self.arena.nodes[id.0];
+        for child in [n.left, n.right].into_iter().flatten() {
+            self.queue.push_back((depth + 1, child));
+        }
+        Some((depth, id))
+    }
+}
+
+impl<T> FusedIterator for LevelOrder<'_, T> {}
+
+/// breadth first, yield all nodes of one depth at a time
+pub struct Levels<'a, T> {
+    arena: &'a TreeArena<T>,
+    level: Vec<TreeId>,
+}
+
+impl<'a, T> Levels<'a, T> {
+    pub fn values(self) -> impl Iterator<Item = Vec<&'a T>> {
+        let arena = self.arena;
+        self.map(move |level| level.into_iter().map(|id| &arena[id]).collect())
+    }
+}
+
+impl<T> Iterator for Levels<'_, T> {
+    type Item = Vec<TreeId>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.level.is_empty() {
+            return None;
+        }
+        let mut next = Vec::new();
+        for id in &self.level {
+            let n = &self.arena.nodes[id.0];
+            next.extend(n.left);
+            next.extend(n.right);
+        }
+        Some(std::mem::replace(&mut self.level, next))
+    }
+}
+
+impl<T> FusedIterator for Levels<'_, T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_struct::traversal;
+
+    const TREE: &str = "[1,2,3,4,5,6,null,null,null,null,7]";
+
+    fn values<'a>(it: impl Iterator<Item = &'a i32>) -> Vec<i32> {
+        it.copied().collect()
+    }
+
+    #[test]
+    fn matches_rc_traversal() {
+        let rc = BinaryTreeNode::<i32>::from_level_order_str(TREE).unwrap();
+        let mut t = TreeArena::new();
+        t.add(0);
+        let root = t.insert_rc(rc.as_ref()).unwrap();
+        assert_eq!(8, t.len());
+        let rc = rc.as_ref();
+        assert_eq!(
+            traversal::PreOrder::new(rc).values().collect::<Vec<_>>(),
+            values(t.pre_order(root).values())
+        );
+        assert_eq!(
+            traversal::InOrder::new(rc).values().collect::<Vec<_>>(),
+            values(t.in_order(root).values())
+        );
+        assert_eq!(
+            traversal::PostOrder::new(rc).values().collect::<Vec<_>>(),
+            values(t.post_order(root).values())
+        );
+        assert_eq!(
+            traversal::LevelOrder::new(rc).values().collect::<Vec<_>>(),
+            t.level_order(root).values().map(|(d, v)| (d, *v)).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            traversal::Levels::new(rc).values().collect::<Vec<_>>(),
+            t.levels(root)
+                .values()
+                .map(|l| values(l.into_iter()))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(4, t.height(root));
+        assert_eq!(TREE, BinaryTreeNode::to_level_order_string(Some(&t.to_rc(root))));
+        assert_eq!(None, t.insert_rc(None));
+    }
+
+    #[test]
+    fn parent_links() {
+        let mut t = TreeArena::new();
+        let root = t.add('a');
+        let b = t.add_left(root, 'b');
+        let c = t.add_right(b, 'c');
+        assert_eq!(Some(b), t.parent(c));
+        assert_eq!(None, t.parent(root));
+        assert_eq!(root, t.root_of(c));
+
+        // 把b子树换成d，b成为独立的树
+        let d = t.add('d');
+        assert_eq!(Some(b), t.set_left(root, Some(d)));
+        assert_eq!(None, t.parent(b));
+        assert_eq!(b, t.root_of(c));
+        assert_eq!(vec!['a', 'd'], t.pre_order(root).values().copied().collect::<Vec<_>>());
+        assert_eq!(None, t.set_right(root, Some(b)));
+        assert_eq!(root, t.root_of(c));
+        assert_eq!(Some(b), t.set_right(root, None));
+
+        t[c] = 'z';
+        *t.get_mut(d).unwrap() = 'y';
+        assert_eq!(Some(&'z'), t.get(c));
+        assert_eq!(
+            "{0: ('a', Some(3), None), 1: ('b', None, Some(2)), 2: ('z', None, None), 3: ('y', None, None)}",
+            format!("{:?}", t)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "child is an ancestor of parent")]
+    fn cycle() {
+        let mut t = TreeArena::new();
+        let root = t.add(1);
+        let child = t.add_left(root, 2);
+        t.set_right(child, Some(root));
+    }
+
+    #[test]
+    #[should_panic(expected = "left child already exists")]
+    fn occupied() {
+        let mut t = TreeArena::new();
+        let root = t.add(1);
+        t.add_left(root, 2);
+        t.add_left(root, 3);
+    }
+
+    #[test]
+    fn deep_tree() {
+        // 转换不递归，退化成链表的深树也不会栈溢出
+        let mut t = TreeArena::new();
+        let root = t.add(0);
+        let mut cur = root;
+        for i in 1..100_000 {
+            cur = t.add_right(cur, i);
+        }
+        let rc = t.to_rc(root);
+        let mut copy = TreeArena::with_capacity(t.len());
+        let r = copy.insert_rc(Some(&rc)).unwrap();
+        assert!(copy.in_order(r).values().eq(t.in_order(root).values()));
+        // Rc树的Drop是递归的，逐个断开以免栈溢出
+        let mut next = rc.borrow_mut().right.take();
+        while let Some(n) = next {
+            next = n.borrow_mut().right.take();
+        }
+    }
+}
@@ -0,0 +1,336 @@
+use std::{collections::BTreeMap, fmt};
+
+///
+/// a key which can be split into trie symbols
+///
+/// `str`可以按char或者按byte拆分，切片和数组按元素拆分
+///
+pub trait Symbols<T> {
+    fn symbols(&self) -> impl Iterator<Item = T> + '_;
+}
+
+impl Symbols<char> for str {
+    fn symbols(&self) -> impl Iterator<Item = char> + '_ {
+        self.chars()
+    }
+}
+
+impl Symbols<u8> for str {
+    fn symbols(&self) -> impl Iterator<Item = u8> + '_ {
+        self.bytes()
+    }
+}
+
+impl<T: Copy> Symbols<T> for [T] {
+    fn symbols(&self) -> impl Iterator<Item = T> + '_ {
+        self.iter().copied()
+    }
+}
+
+impl<T: Copy, const N: usize> Symbols<T> for [T; N] {
+    fn symbols(&self) -> impl Iterator<Item = T> + '_ {
+        self.iter().copied()
+    }
+}
+
+struct Node<T> {
+    children: BTreeMap<T, Node<T>>,
+    terminal: bool,
+    /// number of keys in this subtree, including this node
+    count: usize,
+}
+
+impl<T> Node<T> {
+    fn new() -> Self {
+        Node {
+            children: BTreeMap::new(),
+            terminal: false,
+            count: 0,
+        }
+    }
+}
+
+///
+/// prefix tree over `char`s (the default) or bytes
+///
+/// 子节点存在BTreeMap中，所以按前缀遍历时天然是字典序。每个节点记录子树中key的数量，按前缀计数是O(前缀长度)
+///
+/// ```
+/// use basic_utils::data_struct::Trie;
+///
+/// let mut t = Trie::new();
+/// for w in ["tea", "ten", "to", "inn", "tea"] {
+///     t.insert(w);
+/// }
+/// assert_eq!(4, t.len());
+/// assert_eq!(3, t.count_prefix("t"));
+/// assert_eq!(vec!["tea", "ten"], t.complete("te"));
+/// assert!(t.starts_with("in") && !t.contains("in"));
+///
+/// let mut bytes: Trie<u8> = Trie::new();
+/// bytes.insert(b"\x00\xff");
+/// assert!(bytes.contains(&[0u8, 255][..]));
+/// ```
+///
+pub struct Trie<T = char> {
+    root: Node<T>,
+}
+
+impl<T: Ord + Copy> Trie<T> {
+    pub fn new() -> Self {
+        Trie { root: Node::new() }
+    }
+
+    pub fn len(&self) -> usize {
+        self.root.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.count == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.root = Node::new();
+    }
+
+    fn find<K: Symbols<T> + ?Sized>(&self, prefix: &K) -> Option<&Node<T>> {
+        let mut node = &self.root;
+        for s in prefix.symbols() {
+            node = node.children.get(&s)?;
+        }
+        Some(node)
+    }
+
+    /// return false if the key was already present
+    pub fn insert<K: Symbols<T> + ?Sized>(&mut self, key: &K) -> bool {
+        if self.contains(key) {
+            return false;
+        }
+        let mut node = &mut self.root;
+        node.count += 1;
+        for s in key.symbols() {
+            node = node.children.entry(s).or_insert_with(Node::new);
+            node.count += 1;
+        }
+        node.terminal = true;
+        true
+    }
+
+    /// return false if the key was absent, nodes left without keys are removed
+    pub fn remove<K: Symbols<T> + ?Sized>(&mut self, key: &K) -> bool {
+        if !self.contains(key) {
+            return false;
+        }
+        let mut node = &mut self.root;
+        node.count -= 1;
+        for s in key.symbols() {
+            if node.children[&s].count == 1 {
+                node.children.remove(&s);
+                return true;
+            }
+            node = node.children.get_mut(&s).expect("path of a present key exists");
+            node.count -= 1;
+        }
+        node.terminal = false;
+        true
+    }
+
+    pub fn contains<K: Symbols<T> + ?Sized>(&self, key: &K) -> bool {
+        self.find(key).is_some_and(|n| n.terminal)
+    }
+
+    /// whether any key starts with `prefix`, every key starts with the empty prefix
+    pub fn starts_with<K: Symbols<T> + ?Sized>(&self, prefix: &K) -> bool {
+        self.find(prefix).is_some_and(|n| n.count > 0)
+    }
+
+    /// number of keys starting with `prefix`
+    pub fn count_prefix<K: Symbols<T> + ?Sized>(&self, prefix: &K) -> usize {
+        self.find(prefix).map_or(0, |n| n.count)
+    }
+
+    ///
+    /// length (in symbols) of the longest key which is a prefix of `key`
+    ///
+    /// ```
+    /// use basic_utils::data_struct::Trie;
+    ///
+    /// let mut routes: Trie<u8> = Trie::new();
+    /// routes.insert("/api");
+    /// routes.insert("/api/users");
+    /// let path = "/api/users/42";
+    /// assert_eq!(Some("/api/users"), routes.longest_prefix(path).map(|n| &path[..n]));
+    /// assert_eq!(None, routes.longest_prefix("/static"));
+    /// ```
+    ///
+    pub fn longest_prefix<K: Symbols<T> + ?Sized>(&self, key: &K) -> Option<usize> {
+        let mut node = &self.root;
+        let mut r = node.terminal.then_some(0);
+        for (i, s) in key.symbols().enumerate() {
+            match node.children.get(&s) {
+                Some(n) => node = n,
+                None => break,
+            }
+            if node.terminal {
+                r = Some(i + 1);
+            }
+        }
+        r
+    }
+
+    /// keys starting with `prefix` in lexicographic order
+    pub fn keys_with_prefix<K: Symbols<T> + ?Sized>(&self, prefix: &K) -> Keys<'_, T> {
+        let stack = match self.find(prefix) {
+            Some(node) => vec![(prefix.symbols().collect(), node)],
+            None => Vec::new(),
+        };
+        Keys { stack }
+    }
+
+    /// all keys in lexicographic order
+    pub fn iter(&self) -> Keys<'_, T> {
+        Keys {
+            stack: vec![(Vec::new(), &self.root)],
+        }
+    }
+}
+
+impl Trie<char> {
+    /// autocomplete: all keys starting with `prefix` in lexicographic order
+    pub fn complete(&self, prefix: &str) -> Vec<String> {
+        self.keys_with_prefix(prefix).map(String::from_iter).collect()
+    }
+
+    /// the longest key which is a prefix of `key`
+    pub fn longest_prefix_of<'k>(&self, key: &'k str) -> Option<&'k str> {
+        let n = self.longest_prefix(key)?;
+        let end = key.char_indices().nth(n).map_or(key.len(), |(i, _)| i);
+        Some(&key[..end])
+    }
+}
+
+impl<T: Ord + Copy> Default for Trie<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord + Copy + fmt::Debug> fmt::Debug for Trie<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, K: Symbols<char> + ?Sized + 'a> FromIterator<&'a K> for Trie<char> {
+    fn from_iter<I: IntoIterator<Item = &'a K>>(iter: I) -> Self {
+        let mut t = Trie::new();
+        t.extend(iter);
+        t
+    }
+}
+
+impl<'a, T: Ord + Copy, K: Symbols<T> + ?Sized + 'a> Extend<&'a K> for Trie<T> {
+    fn extend<I: IntoIterator<Item = &'a K>>(&mut self, iter: I) {
+        for key in iter {
+            self.insert(key);
+        }
+    }
+}
+
+/// iterator returned by [Trie::iter] and [Trie::keys_with_prefix]
+///
+/// 先序遍历，子节点逆序压栈，短的key先于以它为前缀的长key输出
+pub struct Keys<'a, T> {
+    stack: Vec<(Vec<T>, &'a Node<T>)>,
+}
+
+impl<T: Copy> Iterator for Keys<'_, T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((key, node)) = self.stack.pop() {
+            for (s, child) in node.children.iter().rev() {
+                let mut k = key.clone();
+                k.push(*s);
+                self.stack.push((k, child));
+            }
+            if node.terminal {
+                return Some(key);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::*;
+
+    #[test]
+    fn matches_btree_set() {
+        let mut t = Trie::new();
+        let mut model = BTreeSet::new();
+        let mut x: u32 = 1;
+        for _ in 0..3000 {
+            x = x.wrapping_mul(1103515245).wrapping_add(12345);
+            let len = (x >> 8) % 4;
+            let key: String = (0..len)
+                .map(|i| (b'a' + ((x >> (12 + 3 * i)) % 3) as u8) as char)
+                .collect();
+            if x.is_multiple_of(3) {
+                assert_eq!(model.remove(&key), t.remove(key.as_str()));
+            } else {
+                assert_eq!(model.insert(key.clone()), t.insert(key.as_str()));
+            }
+            assert_eq!(model.len(), t.len());
+        }
+        let keys: Vec<String> = t.iter().map(String::from_iter).collect();
+        assert_eq!(model.iter().cloned().collect::<Vec<_>>(), keys);
+        for prefix in ["", "a", "ab", "cc", "bca"] {
+            let expected: Vec<String> = model.iter().filter(|k| k.starts_with(prefix)).cloned().collect();
+            assert_eq!(expected.len(), t.count_prefix(prefix));
+            assert_eq!(!expected.is_empty(), t.starts_with(prefix));
+            assert_eq!(expected, t.complete(prefix));
+        }
+    }
+
+    #[test]
+    fn remove_prunes() {
+        let mut t: Trie = ["a", "abc"].into_iter().collect();
+        assert!(t.remove("abc"));
+        assert!(!t.starts_with("ab"));
+        assert!(t.root.children[&'a'].children.is_empty());
+        assert!(!t.remove("ab"));
+        assert!(t.remove("a"));
+        assert!(t.root.children.is_empty());
+        assert!(t.is_empty());
+        assert!(!t.starts_with(""));
+
+        t.insert("");
+        assert!(t.contains(""));
+        assert_eq!(Some(""), t.longest_prefix_of("xyz"));
+        t.clear();
+        assert_eq!(0, t.iter().count());
+    }
+
+    #[test]
+    fn unicode_and_bytes() {
+        let t: Trie = ["你好", "你好世界", "hello"].into_iter().collect();
+        assert_eq!(Some("你好世界"), t.longest_prefix_of("你好世界!"));
+        assert_eq!(Some("你好"), t.longest_prefix_of("你好吗"));
+        assert_eq!(None, t.longest_prefix_of("你"));
+        assert_eq!(vec!["你好", "你好世界"], t.complete("你"));
+        assert_eq!(
+            "{['h', 'e', 'l', 'l', 'o'], ['你', '好'], ['你', '好', '世', '界']}",
+            format!("{:?}", t)
+        );
+
+        let mut b: Trie<u8> = Trie::new();
+        b.extend(["你好", "hello"]);
+        assert_eq!(1, b.count_prefix(&[0xe4u8][..]));
+        assert_eq!(Some(6), b.longest_prefix("你好世界"));
+        assert_eq!(vec![b"hello".to_vec()], b.keys_with_prefix(b"he").collect::<Vec<_>>());
+    }
+}
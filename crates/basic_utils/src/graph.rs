@@ -0,0 +1,269 @@
+//!
+//! adjacency-list graph and the common algorithms on it
+//!
+//! ```
+//! use basic_utils::graph::{topological_sort, Graph};
+//!
+//! let mut g = Graph::new_directed();
+//! let fetch = g.add_node("fetch");
+//! let build = g.add_node("build");
+//! let test = g.add_node("test");
+//! g.add_edge(fetch, build, ());
+//! g.add_edge(build, test, ());
+//! g.add_edge(fetch, test, ());
+//!
+//! let order: Vec<_> = topological_sort(&g).unwrap().into_iter().map(|n| g[n]).collect();
+//! assert_eq!(vec!["fetch", "build", "test"], order);
+//!
+//! g.add_edge(test, fetch, ());
+//! let err = topological_sort(&g).unwrap_err();
+//! assert_eq!(3, err.cycle().len());
+//! ```
+//!
+use std::{fmt, ops::Index, slice};
+
+pub use components::{connected_components, strongly_connected_components};
+pub use shortest_path::{astar, bellman_ford, dijkstra, NegativeCycleError, ShortestPaths};
+pub use spanning_tree::{minimum_spanning_tree, IncomparableWeightError};
+pub use traversal::{topological_sort, Bfs, CycleError, Dfs};
+
+pub mod components;
+pub mod shortest_path;
+pub mod spanning_tree;
+pub mod traversal;
+
+/// handle of a node, only valid for the graph which created it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeId(usize);
+
+impl NodeId {
+    /// nodes are numbered from 0 in insertion order
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+impl fmt::Display for NodeId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+struct Edge<E> {
+    from: NodeId,
+    to: NodeId,
+    weight: E,
+}
+
+///
+/// directed or undirected graph stored as adjacency lists, `N` is the node data and `E` the edge data
+///
+/// 边只存一份，邻接表里存边的下标。无向图的边同时出现在两端的outgoing里，incoming只在有向图中使用
+///
+pub struct Graph<N, E = ()> {
+    nodes: Vec<N>,
+    edges: Vec<Edge<E>>,
+    outgoing: Vec<Vec<usize>>,
+    incoming: Vec<Vec<usize>>,
+    directed: bool,
+}
+
+impl<N, E> Graph<N, E> {
+    pub fn new_directed() -> Self {
+        Self::new(true)
+    }
+
+    pub fn new_undirected() -> Self {
+        Self::new(false)
+    }
+
+    fn new(directed: bool) -> Self {
+        Graph {
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            outgoing: Vec::new(),
+            incoming: Vec::new(),
+            directed,
+        }
+    }
+
+    pub fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    pub fn add_node(&mut self, data: N) -> NodeId {
+        self.nodes.push(data);
+        self.outgoing.push(Vec::new());
+        self.incoming.push(Vec::new());
+        NodeId(self.nodes.len() - 1)
+    }
+
+    ///
+    /// add an edge, parallel edges and self loops are allowed
+    ///
+    /// # Panics
+    ///
+    /// panics if either node does not belong to this graph
+    ///
+    pub fn add_edge(&mut self, from: NodeId, to: NodeId, weight: E) {
+        assert!(
+            from.0 < self.nodes.len() && to.0 < self.nodes.len(),
+            "node out of range"
+        );
+        let e = self.edges.len();
+        self.edges.push(Edge { from, to, weight });
+        self.outgoing[from.0].push(e);
+        if self.directed {
+            self.incoming[to.0].push(e);
+        } else if from != to {
+            self.outgoing[to.0].push(e);
+        }
+    }
+
+    pub fn node(&self, id: NodeId) -> Option<&N> {
+        self.nodes.get(id.0)
+    }
+
+    pub fn node_mut(&mut self, id: NodeId) -> Option<&mut N> {
+        self.nodes.get_mut(id.0)
+    }
+
+    /// all nodes in insertion order
+    pub fn nodes(&self) -> impl ExactSizeIterator<Item = (NodeId, &N)> {
+        self.nodes.iter().enumerate().map(|(i, n)| (NodeId(i), n))
+    }
+
+    pub fn node_ids(&self) -> impl ExactSizeIterator<Item = NodeId> {
+        (0..self.nodes.len()).map(NodeId)
+    }
+
+    /// all edges as `(from, to, weight)` in insertion order
+    pub fn edges(&self) -> impl ExactSizeIterator<Item = (NodeId, NodeId, &E)> {
+        self.edges.iter().map(|e| (e.from, e.to, &e.weight))
+    }
+
+    /// the first edge from `from` to `to`
+    pub fn edge(&self, from: NodeId, to: NodeId) -> Option<&E> {
+        self.neighbors(from).find(|&(n, _)| n == to).map(|(_, w)| w)
+    }
+
+    /// nodes reachable by one edge, with the edge weight
+    pub fn neighbors(&self, id: NodeId) -> Neighbors<'_, E> {
+        Neighbors {
+            edges: &self.edges,
+            ids: self.outgoing[id.0].iter(),
+            node: id,
+        }
+    }
+
+    /// nodes which have an edge to `id`, the same as [Graph::neighbors] for an undirected graph
+    pub fn predecessors(&self, id: NodeId) -> Neighbors<'_, E> {
+        let ids = if self.directed {
+            self.incoming[id.0].iter()
+        } else {
+            self.outgoing[id.0].iter()
+        };
+        Neighbors {
+            edges: &self.edges,
+            ids,
+            node: id,
+        }
+    }
+
+    pub fn bfs(&self, start: NodeId) -> Bfs<'_, N, E> {
+        Bfs::new(self, start)
+    }
+
+    pub fn dfs(&self, start: NodeId) -> Dfs<'_, N, E> {
+        Dfs::new(self, start)
+    }
+}
+
+impl<N, E> Index<NodeId> for Graph<N, E> {
+    type Output = N;
+
+    fn index(&self, id: NodeId) -> &N {
+        &self.nodes[id.0]
+    }
+}
+
+impl<N: fmt::Debug, E: fmt::Debug> fmt::Debug for Graph<N, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Graph")
+            .field("directed", &self.directed)
+            .field("nodes", &self.nodes)
+            .field("edges", &self.edges().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+/// iterator returned by [Graph::neighbors] and [Graph::predecessors], yield `(node, weight)`
+pub struct Neighbors<'a, E> {
+    edges: &'a [Edge<E>],
+    ids: slice::Iter<'a, usize>,
+    node: NodeId,
+}
+
+impl<'a, E> Iterator for Neighbors<'a, E> {
+    type Item = (NodeId, &'a E);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let e = &self.edges[*self.ids.next()?];
+        let other = if e.from == self.node { e.to } else { e.from };
+        Some((other, &e.weight))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.ids.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn directed_and_undirected() {
+        let mut g = Graph::new_directed();
+        let a = g.add_node('a');
+        let b = g.add_node('b');
+        g.add_edge(a, b, 1);
+        g.add_edge(a, a, 2);
+        assert_eq!(vec![(b, &1), (a, &2)], g.neighbors(a).collect::<Vec<_>>());
+        assert_eq!(0, g.neighbors(b).count());
+        assert_eq!(vec![(a, &1)], g.predecessors(b).collect::<Vec<_>>());
+        assert_eq!(Some(&1), g.edge(a, b));
+        assert_eq!(None, g.edge(b, a));
+
+        let mut g = Graph::new_undirected();
+        let a = g.add_node('a');
+        let b = g.add_node('b');
+        g.add_edge(a, b, 1);
+        g.add_edge(b, b, 2);
+        assert_eq!(Some(&1), g.edge(b, a));
+        assert_eq!(vec![(a, &1), (b, &2)], g.neighbors(b).collect::<Vec<_>>());
+        assert_eq!(2, g.edge_count());
+        *g.node_mut(a).unwrap() = 'z';
+        assert_eq!('z', g[a]);
+        assert_eq!("1", b.to_string());
+    }
+
+    #[test]
+    #[should_panic(expected = "node out of range")]
+    fn foreign_node() {
+        let mut other: Graph<()> = Graph::new_directed();
+        other.add_node(());
+        let foreign = other.add_node(());
+        let mut g: Graph<()> = Graph::new_directed();
+        let a = g.add_node(());
+        g.add_edge(a, foreign, ());
+    }
+}
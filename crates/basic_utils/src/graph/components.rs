@@ -0,0 +1,179 @@
+use std::collections::VecDeque;
+
+use super::{Graph, NodeId};
+
+///
+/// connected components, edge directions are ignored (weakly connected for a directed graph)
+///
+/// 每个分量内部按编号升序，分量之间按最小编号升序
+///
+/// ```
+/// use basic_utils::graph::{connected_components, Graph};
+///
+/// let mut g = Graph::new_directed();
+/// let n: Vec<_> = (0..5).map(|i| g.add_node(i)).collect();
+/// g.add_edge(n[1], n[0], ());
+/// g.add_edge(n[3], n[4], ());
+/// assert_eq!(vec![vec![n[0], n[1]], vec![n[2]], vec![n[3], n[4]]], connected_components(&g));
+/// ```
+///
+pub fn connected_components<N, E>(graph: &Graph<N, E>) -> Vec<Vec<NodeId>> {
+    let mut visited = vec![false; graph.node_count()];
+    let mut r = Vec::new();
+    for root in graph.node_ids() {
+        if visited[root.0] {
+            continue;
+        }
+        visited[root.0] = true;
+        let mut component = Vec::new();
+        let mut queue = VecDeque::from([root]);
+        while let Some(node) = queue.pop_front() {
+            component.push(node);
+            for (n, _) in graph.neighbors(node).chain(graph.predecessors(node)) {
+                if !visited[n.0] {
+                    visited[n.0] = true;
+                    queue.push_back(n);
+                }
+            }
+        }
+        component.sort();
+        r.push(component);
+    }
+    r
+}
+
+///
+/// strongly connected components (Tarjan's algorithm), the same as [connected_components] for an undirected graph
+///
+/// 分量按逆拓扑序输出：如果有边从分量A指向分量B，B在A之前。每个分量内部按编号升序。
+/// 用显式的调用栈代替递归，避免大图栈溢出
+///
+/// ```
+/// use basic_utils::graph::{strongly_connected_components, Graph};
+///
+/// let mut g = Graph::new_directed();
+/// let n: Vec<_> = (0..4).map(|i| g.add_node(i)).collect();
+/// g.add_edge(n[0], n[1], ());
+/// g.add_edge(n[1], n[0], ());
+/// g.add_edge(n[1], n[2], ());
+/// g.add_edge(n[2], n[3], ());
+/// assert_eq!(vec![vec![n[3]], vec![n[2]], vec![n[0], n[1]]], strongly_connected_components(&g));
+/// ```
+///
+pub fn strongly_connected_components<N, E>(graph: &Graph<N, E>) -> Vec<Vec<NodeId>> {
+    if !graph.is_directed() {
+        return connected_components(graph);
+    }
+    const UNVISITED: usize = usize::MAX;
+    let mut index = vec![UNVISITED; graph.node_count()];
+    let mut low = vec![0; graph.node_count()];
+    let mut on_stack = vec![false; graph.node_count()];
+    let mut stack = Vec::new();
+    let mut next_index = 0;
+    let mut r = Vec::new();
+
+    for root in graph.node_ids() {
+        if index[root.0] != UNVISITED {
+            continue;
+        }
+        let mut call = Vec::new();
+        let mut next = Some(root);
+        loop {
+            if let Some(v) = next.take() {
+                index[v.0] = next_index;
+                low[v.0] = next_index;
+                next_index += 1;
+                stack.push(v);
+                on_stack[v.0] = true;
+                call.push((v, graph.neighbors(v)));
+            }
+            let Some((node, neighbors)) = call.last_mut() else {
+                break;
+            };
+            let node = *node;
+            match neighbors.next() {
+                Some((m, _)) if index[m.0] == UNVISITED => next = Some(m),
+                Some((m, _)) => {
+                    if on_stack[m.0] {
+                        low[node.0] = low[node.0].min(index[m.0]);
+                    }
+                }
+                None => {
+                    call.pop();
+                    if let Some((parent, _)) = call.last() {
+                        low[parent.0] = low[parent.0].min(low[node.0]);
+                    }
+                    if low[node.0] == index[node.0] {
+                        let mut component = Vec::new();
+                        loop {
+                            let m = stack.pop().expect("node is on the stack");
+                            on_stack[m.0] = false;
+                            component.push(m);
+                            if m == node {
+                                break;
+                            }
+                        }
+                        component.sort();
+                        r.push(component);
+                    }
+                }
+            }
+        }
+    }
+    r
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scc() {
+        let mut g = Graph::new_directed();
+        let n: Vec<_> = (0..8).map(|i| g.add_node(i)).collect();
+        for (a, b) in [
+            (0, 1),
+            (1, 2),
+            (2, 0),
+            (2, 3),
+            (3, 4),
+            (4, 5),
+            (5, 3),
+            (6, 5),
+            (6, 7),
+            (7, 6),
+            (7, 7),
+        ] {
+            g.add_edge(n[a], n[b], ());
+        }
+        let sccs = strongly_connected_components(&g);
+        assert_eq!(
+            vec![vec![n[3], n[4], n[5]], vec![n[0], n[1], n[2]], vec![n[6], n[7]]],
+            sccs
+        );
+        assert_eq!(1, connected_components(&g).len());
+    }
+
+    #[test]
+    fn undirected() {
+        let mut g = Graph::new_undirected();
+        let n: Vec<_> = (0..4).map(|i| g.add_node(i)).collect();
+        g.add_edge(n[2], n[0], ());
+        assert_eq!(
+            vec![vec![n[0], n[2]], vec![n[1]], vec![n[3]]],
+            strongly_connected_components(&g)
+        );
+        assert!(connected_components(&Graph::<()>::new_undirected()).is_empty());
+    }
+
+    #[test]
+    fn long_chain() {
+        let mut g = Graph::new_directed();
+        let n: Vec<_> = (0..100_000).map(|i| g.add_node(i)).collect();
+        for w in n.windows(2) {
+            g.add_edge(w[0], w[1], ());
+        }
+        g.add_edge(n[n.len() - 1], n[0], ());
+        assert_eq!(vec![n], strongly_connected_components(&g));
+    }
+}
@@ -0,0 +1,328 @@
+use std::{error::Error, fmt, ops::Add};
+
+use super::{Graph, NodeId};
+use crate::IndexedPriorityQueue;
+
+///
+/// single-source shortest paths, returned by [dijkstra] and [bellman_ford]
+///
+/// prev记录最短路径树中每个节点的前驱，从终点沿prev走回起点就得到路径
+///
+pub struct ShortestPaths<W> {
+    source: NodeId,
+    dist: Vec<Option<W>>,
+    prev: Vec<Option<NodeId>>,
+}
+
+impl<W: Copy> ShortestPaths<W> {
+    pub fn source(&self) -> NodeId {
+        self.source
+    }
+
+    /// None if `to` is unreachable
+    pub fn distance(&self, to: NodeId) -> Option<W> {
+        self.dist[to.0]
+    }
+
+    /// nodes from the source to `to`, both included, None if `to` is unreachable
+    pub fn path_to(&self, to: NodeId) -> Option<Vec<NodeId>> {
+        self.dist[to.0]?;
+        Some(build_path(&self.prev, self.source, to))
+    }
+}
+
+impl<W: fmt::Debug> fmt::Debug for ShortestPaths<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ShortestPaths")
+            .field("source", &self.source)
+            .field("dist", &self.dist)
+            .finish()
+    }
+}
+
+fn build_path(prev: &[Option<NodeId>], source: NodeId, to: NodeId) -> Vec<NodeId> {
+    let mut path = vec![to];
+    let mut node = to;
+    while node != source {
+        node = prev[node.0].expect("reached node has a predecessor");
+        path.push(node);
+    }
+    path.reverse();
+    path
+}
+
+///
+/// Dijkstra's algorithm with a decrease-key priority queue, O((V + E) log V)
+///
+/// `weight` maps an edge to its length, lengths must not be negative. `W::default()` is the zero length
+///
+/// ```
+/// use basic_utils::graph::{dijkstra, Graph};
+///
+/// let mut g = Graph::new_directed();
+/// let n: Vec<_> = (0..4).map(|i| g.add_node(i)).collect();
+/// g.add_edge(n[0], n[1], 4u32);
+/// g.add_edge(n[0], n[2], 1);
+/// g.add_edge(n[2], n[1], 2);
+/// g.add_edge(n[1], n[3], 5);
+/// let paths = dijkstra(&g, n[0], |w| *w);
+/// assert_eq!(Some(8), paths.distance(n[3]));
+/// assert_eq!(Some(vec![n[0], n[2], n[1], n[3]]), paths.path_to(n[3]));
+/// ```
+///
+pub fn dijkstra<N, E, W>(graph: &Graph<N, E>, source: NodeId, mut weight: impl FnMut(&E) -> W) -> ShortestPaths<W>
+where
+    W: Copy + Ord + Add<Output = W> + Default,
+{
+    let mut dist = vec![None; graph.node_count()];
+    let mut prev = vec![None; graph.node_count()];
+    let mut done = vec![false; graph.node_count()];
+    dist[source.0] = Some(W::default());
+    let mut queue = IndexedPriorityQueue::new_min();
+    queue.push(source, W::default());
+    while let Some((node, d)) = queue.pop() {
+        done[node.0] = true;
+        for (m, e) in graph.neighbors(node) {
+            if done[m.0] {
+                continue;
+            }
+            let nd = d + weight(e);
+            if dist[m.0].is_none_or(|old| nd < old) {
+                dist[m.0] = Some(nd);
+                prev[m.0] = Some(node);
+                if !queue.decrease_key(&m, nd) {
+                    queue.push(m, nd);
+                }
+            }
+        }
+    }
+    ShortestPaths { source, dist, prev }
+}
+
+/// error returned by [bellman_ford]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NegativeCycleError {
+    cycle: Vec<NodeId>,
+}
+
+impl NegativeCycleError {
+    /// nodes of a negative cycle reachable from the source, in edge order starting from the smallest id
+    pub fn cycle(&self) -> &[NodeId] {
+        &self.cycle
+    }
+}
+
+impl fmt::Display for NegativeCycleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "graph has a negative cycle: ")?;
+        for n in &self.cycle {
+            write!(f, "{} -> ", n)?;
+        }
+        write!(f, "{}", self.cycle[0])
+    }
+}
+
+impl Error for NegativeCycleError {}
+
+///
+/// Bellman-Ford algorithm, negative lengths are allowed, O(VE)
+///
+/// 最多松弛V-1轮，第V轮仍然能松弛说明存在从起点可达的负环。
+/// 此时从被松弛的节点沿prev回退V步一定落在环上，再沿prev走一圈得到这个环。
+/// 无向图的负权边本身就是一个负环
+///
+/// ```
+/// use basic_utils::graph::{bellman_ford, Graph};
+///
+/// let mut g = Graph::new_directed();
+/// let n: Vec<_> = (0..3).map(|i| g.add_node(i)).collect();
+/// g.add_edge(n[0], n[1], 4);
+/// g.add_edge(n[0], n[2], 5);
+/// g.add_edge(n[2], n[1], -3);
+/// assert_eq!(Some(2), bellman_ford(&g, n[0], |w| *w).unwrap().distance(n[1]));
+///
+/// g.add_edge(n[1], n[2], 1);
+/// assert_eq!(&[n[1], n[2]], bellman_ford(&g, n[0], |w| *w).unwrap_err().cycle());
+/// ```
+///
+pub fn bellman_ford<N, E, W>(
+    graph: &Graph<N, E>,
+    source: NodeId,
+    mut weight: impl FnMut(&E) -> W,
+) -> Result<ShortestPaths<W>, NegativeCycleError>
+where
+    W: Copy + PartialOrd + Add<Output = W> + Default,
+{
+    let n = graph.node_count();
+    let mut dist: Vec<Option<W>> = vec![None; n];
+    let mut prev = vec![None; n];
+    dist[source.0] = Some(W::default());
+    for round in 0..n {
+        let mut relaxed = None;
+        for node in graph.node_ids() {
+            let Some(d) = dist[node.0] else {
+                continue;
+            };
+            for (m, e) in graph.neighbors(node) {
+                let nd = d + weight(e);
+                if dist[m.0].is_none_or(|old| nd < old) {
+                    dist[m.0] = Some(nd);
+                    prev[m.0] = Some(node);
+                    relaxed = Some(m);
+                }
+            }
+        }
+        let Some(mut node) = relaxed else {
+            break;
+        };
+        if round == n - 1 {
+            for _ in 0..n {
+                node = prev[node.0].expect("relaxed node has a predecessor");
+            }
+            let mut cycle = vec![node];
+            let mut m = prev[node.0].expect("node on a cycle has a predecessor");
+            while m != node {
+                cycle.push(m);
+                m = prev[m.0].expect("node on a cycle has a predecessor");
+            }
+            cycle.reverse();
+            let first = (0..cycle.len()).min_by_key(|&i| cycle[i]).expect("cycle is not empty");
+            cycle.rotate_left(first);
+            return Err(NegativeCycleError { cycle });
+        }
+    }
+    Ok(ShortestPaths { source, dist, prev })
+}
+
+///
+/// A* search from `source` to `target`, return the length and the path
+///
+/// `heuristic`估计节点到target的距离，不能高估(admissible)，否则结果可能不是最短路径。
+/// 不要求一致性(consistent)，节点的g值变小时会被重新放回队列
+///
+/// ```
+/// use basic_utils::graph::{astar, Graph};
+///
+/// // 3x3 grid, node data is (x, y)
+/// let mut g = Graph::new_undirected();
+/// let n: Vec<_> = (0..9).map(|i| g.add_node((i % 3, i / 3))).collect();
+/// for i in 0..9 {
+///     if i % 3 < 2 {
+///         g.add_edge(n[i], n[i + 1], 1);
+///     }
+///     if i < 6 && i != 4 {
+///         g.add_edge(n[i], n[i + 3], 1);
+///     }
+/// }
+/// let goal = g[n[8]];
+/// let manhattan = |id| {
+///     let (x, y): (i32, i32) = g[id];
+///     (goal.0 - x).abs() + (goal.1 - y).abs()
+/// };
+/// let (len, path) = astar(&g, n[0], n[8], |w| *w, manhattan).unwrap();
+/// assert_eq!(4, len);
+/// assert_eq!(5, path.len());
+/// ```
+///
+pub fn astar<N, E, W>(
+    graph: &Graph<N, E>,
+    source: NodeId,
+    target: NodeId,
+    mut weight: impl FnMut(&E) -> W,
+    mut heuristic: impl FnMut(NodeId) -> W,
+) -> Option<(W, Vec<NodeId>)>
+where
+    W: Copy + Ord + Add<Output = W> + Default,
+{
+    let mut g_score: Vec<Option<W>> = vec![None; graph.node_count()];
+    let mut prev = vec![None; graph.node_count()];
+    g_score[source.0] = Some(W::default());
+    let mut open = IndexedPriorityQueue::new_min();
+    open.push(source, heuristic(source));
+    while let Some((node, _)) = open.pop() {
+        let g = g_score[node.0].expect("queued node has a g score");
+        if node == target {
+            return Some((g, build_path(&prev, source, target)));
+        }
+        for (m, e) in graph.neighbors(node) {
+            let ng = g + weight(e);
+            if g_score[m.0].is_none_or(|old| ng < old) {
+                g_score[m.0] = Some(ng);
+                prev[m.0] = Some(node);
+                // 已经在队列中时push会更新优先级
+                open.push(m, ng + heuristic(m));
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// directed graph from `(from, to, weight)` triples
+    fn weighted(n: usize, edges: &[(usize, usize, i64)]) -> (Graph<usize, i64>, Vec<NodeId>) {
+        let mut g = Graph::new_directed();
+        let ids: Vec<_> = (0..n).map(|i| g.add_node(i)).collect();
+        for &(a, b, w) in edges {
+            g.add_edge(ids[a], ids[b], w);
+        }
+        (g, ids)
+    }
+
+    fn lightest_edge(g: &Graph<usize, i64>, a: NodeId, b: NodeId) -> i64 {
+        g.neighbors(a).filter(|&(m, _)| m == b).map(|(_, w)| *w).min().unwrap()
+    }
+
+    #[test]
+    fn algorithms_agree() {
+        let mut x: u64 = 42;
+        let mut edges = Vec::new();
+        for _ in 0..300 {
+            x = x.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            edges.push(((x >> 33) as usize % 40, (x >> 45) as usize % 40, (x >> 20) as i64 % 50));
+        }
+        let (g, n) = weighted(40, &edges);
+        let d = dijkstra(&g, n[0], |w| *w);
+        let b = bellman_ford(&g, n[0], |w| *w).unwrap();
+        assert_eq!(n[0], d.source());
+        for &t in &n {
+            assert_eq!(d.distance(t), b.distance(t));
+            let a = astar(&g, n[0], t, |w| *w, |_| 0);
+            assert_eq!(d.distance(t), a.as_ref().map(|(len, _)| *len));
+            if let Some(path) = d.path_to(t) {
+                assert_eq!((n[0], t), (path[0], path[path.len() - 1]));
+                let len: i64 = path.windows(2).map(|p| lightest_edge(&g, p[0], p[1])).sum();
+                assert_eq!(d.distance(t), Some(len));
+            }
+        }
+    }
+
+    #[test]
+    fn unreachable() {
+        let (g, n) = weighted(3, &[(0, 1, 1)]);
+        let d = dijkstra(&g, n[0], |w| *w);
+        assert_eq!(None, d.distance(n[2]));
+        assert_eq!(None, d.path_to(n[2]));
+        assert_eq!(Some(vec![n[0]]), d.path_to(n[0]));
+        assert_eq!(None, astar(&g, n[0], n[2], |w| *w, |_| 0));
+    }
+
+    #[test]
+    fn negative_cycle() {
+        let (g, n) = weighted(5, &[(0, 1, 1), (1, 2, 1), (2, 3, -2), (3, 1, 0), (3, 4, 1)]);
+        let err = bellman_ford(&g, n[0], |w| *w).unwrap_err();
+        assert_eq!(&[n[1], n[2], n[3]], err.cycle());
+        assert_eq!("graph has a negative cycle: 1 -> 2 -> 3 -> 1", err.to_string());
+
+        // the cycle is not reachable from 4
+        assert_eq!(Some(0), bellman_ford(&g, n[4], |w| *w).unwrap().distance(n[4]));
+
+        let mut g = Graph::new_undirected();
+        let a = g.add_node(());
+        let b = g.add_node(());
+        g.add_edge(a, b, -1.5);
+        assert_eq!(2, bellman_ford(&g, a, |w| *w).unwrap_err().cycle().len());
+    }
+}
@@ -0,0 +1,128 @@
+use std::{cmp::Ordering, error::Error, fmt};
+
+use super::{Graph, NodeId};
+use crate::data_struct::DenseDisjointSet;
+
+/// error returned by [minimum_spanning_tree] when a weight cannot be ordered, e.g. a NaN
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IncomparableWeightError {
+    edge: (NodeId, NodeId),
+}
+
+impl IncomparableWeightError {
+    /// `(from, to)` of an edge whose weight cannot be compared
+    pub fn edge(&self) -> (NodeId, NodeId) {
+        self.edge
+    }
+}
+
+impl fmt::Display for IncomparableWeightError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "weight of edge {} -> {} cannot be compared",
+            self.edge.0, self.edge.1
+        )
+    }
+}
+
+impl Error for IncomparableWeightError {}
+
+///
+/// minimum spanning forest (Kruskal's algorithm), return `(from, to, weight)` of the chosen edges
+///
+/// 按权重从小到大考虑每条边，两端不在同一个集合时选中并合并。边的方向被忽略，
+/// 图不连通时得到每个连通分量的最小生成树
+///
+/// # Errors
+///
+/// 权重无法比较时(比如浮点数的NaN)返回[IncomparableWeightError]，而不是panic或者得到错误的树。
+/// 对于只实现了偏序的自定义权重，除了和自身无法比较的值以外，其他值之间需要能互相比较
+///
+/// ```
+/// use basic_utils::graph::{minimum_spanning_tree, Graph};
+///
+/// let mut g = Graph::new_undirected();
+/// let n: Vec<_> = (0..4).map(|i| g.add_node(i)).collect();
+/// g.add_edge(n[0], n[1], 1);
+/// g.add_edge(n[1], n[2], 4);
+/// g.add_edge(n[0], n[2], 3);
+/// g.add_edge(n[2], n[3], 2);
+/// let tree = minimum_spanning_tree(&g, |w| *w).unwrap();
+/// assert_eq!(6, tree.iter().map(|(_, _, w)| **w).sum::<i32>());
+/// assert_eq!(vec![(n[0], n[1]), (n[2], n[3]), (n[0], n[2])], tree.iter().map(|(a, b, _)| (*a, *b)).collect::<Vec<_>>());
+/// ```
+///
+pub fn minimum_spanning_tree<N, E, W: PartialOrd>(
+    graph: &Graph<N, E>,
+    mut weight: impl FnMut(&E) -> W,
+) -> Result<Vec<(NodeId, NodeId, &E)>, IncomparableWeightError> {
+    let mut edges: Vec<(W, NodeId, NodeId, &E)> = graph.edges().map(|(a, b, e)| (weight(e), a, b, e)).collect();
+    // 和自身都无法比较的权重(NaN)会让排序没有意义，先拒绝
+    if let Some((_, a, b, _)) = edges.iter().find(|(w, ..)| w.partial_cmp(w).is_none()) {
+        return Err(IncomparableWeightError { edge: (*a, *b) });
+    }
+    let mut incomparable = None;
+    // sort_by是稳定排序，权重相同的边按加入顺序考虑
+    edges.sort_by(|x, y| {
+        x.0.partial_cmp(&y.0).unwrap_or_else(|| {
+            incomparable.get_or_insert((x.1, x.2));
+            Ordering::Equal
+        })
+    });
+    if let Some(edge) = incomparable {
+        return Err(IncomparableWeightError { edge });
+    }
+    let mut sets = DenseDisjointSet::new(graph.node_count());
+    let mut tree = Vec::with_capacity(graph.node_count().saturating_sub(1));
+    for (_, a, b, e) in edges {
+        if sets.union(a.index(), b.index()) {
+            tree.push((a, b, e));
+        }
+    }
+    Ok(tree)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn forest() {
+        let mut g = Graph::new_directed();
+        let n: Vec<_> = (0..6).map(|i| g.add_node(i)).collect();
+        for (a, b, w) in [
+            (0, 1, 2.5),
+            (1, 0, 1.0),
+            (1, 2, 3.0),
+            (2, 0, 0.5),
+            (3, 4, 1.0),
+            (4, 4, 0.0),
+        ] {
+            g.add_edge(n[a], n[b], w);
+        }
+        let tree = minimum_spanning_tree(&g, |w| *w).unwrap();
+        assert_eq!(
+            vec![(n[2], n[0]), (n[1], n[0]), (n[3], n[4])],
+            tree.iter().map(|(a, b, _)| (*a, *b)).collect::<Vec<_>>()
+        );
+        assert_eq!(2.5, tree.iter().map(|(_, _, w)| **w).sum::<f64>());
+        assert!(minimum_spanning_tree(&Graph::<(), i32>::new_undirected(), |w| *w)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn nan_weight() {
+        let mut g = Graph::new_undirected();
+        let n: Vec<_> = (0..3).map(|i| g.add_node(i)).collect();
+        g.add_edge(n[0], n[1], 1.0);
+        g.add_edge(n[1], n[2], f64::NAN);
+        let err = minimum_spanning_tree(&g, |w| *w).unwrap_err();
+        assert_eq!((n[1], n[2]), err.edge());
+        assert_eq!(
+            format!("weight of edge {} -> {} cannot be compared", n[1], n[2]),
+            err.to_string()
+        );
+    }
+}
@@ -0,0 +1,216 @@
+use std::{collections::VecDeque, error::Error, fmt, iter::FusedIterator};
+
+use super::{Graph, NodeId};
+
+///
+/// breadth-first iterator over the nodes reachable from `start`
+///
+/// ```
+/// use basic_utils::graph::Graph;
+///
+/// let mut g = Graph::new_undirected();
+/// let n: Vec<_> = (0..4).map(|i| g.add_node(i)).collect();
+/// g.add_edge(n[0], n[1], ());
+/// g.add_edge(n[1], n[3], ());
+/// g.add_edge(n[0], n[2], ());
+/// assert_eq!(vec![0, 1, 2, 3], g.bfs(n[0]).map(|id| g[id]).collect::<Vec<_>>());
+/// assert_eq!(vec![0, 1, 3, 2], g.dfs(n[0]).map(|id| g[id]).collect::<Vec<_>>());
+/// ```
+///
+pub struct Bfs<'a, N, E> {
+    graph: &'a Graph<N, E>,
+    queue: VecDeque<NodeId>,
+    visited: Vec<bool>,
+}
+
+impl<'a, N, E> Bfs<'a, N, E> {
+    pub fn new(graph: &'a Graph<N, E>, start: NodeId) -> Self {
+        let mut visited = vec![false; graph.node_count()];
+        visited[start.0] = true;
+        Bfs {
+            graph,
+            queue: VecDeque::from([start]),
+            visited,
+        }
+    }
+}
+
+impl<N, E> Iterator for Bfs<'_, N, E> {
+    type Item = NodeId;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.queue.pop_front()?;
+        for (n, _) in self.graph.neighbors(node) {
+            if !self.visited[n.0] {
+                self.visited[n.0] = true;
+                self.queue.push_back(n);
+            }
+        }
+        Some(node)
+    }
+}
+
+impl<N, E> FusedIterator for Bfs<'_, N, E> {}
+
+///
+/// depth-first (pre-order) iterator over the nodes reachable from `start`
+///
+/// 邻居按加边顺序访问，所以压栈时逆序
+///
+pub struct Dfs<'a, N, E> {
+    graph: &'a Graph<N, E>,
+    stack: Vec<NodeId>,
+    visited: Vec<bool>,
+}
+
+impl<'a, N, E> Dfs<'a, N, E> {
+    pub fn new(graph: &'a Graph<N, E>, start: NodeId) -> Self {
+        Dfs {
+            graph,
+            stack: vec![start],
+            visited: vec![false; graph.node_count()],
+        }
+    }
+}
+
+impl<N, E> Iterator for Dfs<'_, N, E> {
+    type Item = NodeId;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let node = self.stack.pop()?;
+            if self.visited[node.0] {
+                continue;
+            }
+            self.visited[node.0] = true;
+            let next: Vec<NodeId> = self.graph.neighbors(node).map(|(n, _)| n).collect();
+            self.stack.extend(next.into_iter().rev().filter(|n| !self.visited[n.0]));
+            return Some(node);
+        }
+    }
+}
+
+impl<N, E> FusedIterator for Dfs<'_, N, E> {}
+
+/// error returned by [topological_sort]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CycleError {
+    cycle: Vec<NodeId>,
+}
+
+impl CycleError {
+    /// nodes of one cycle in edge order starting from the smallest id, the last node has an edge back to the first
+    pub fn cycle(&self) -> &[NodeId] {
+        &self.cycle
+    }
+}
+
+impl fmt::Display for CycleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "graph has a cycle: ")?;
+        for n in &self.cycle {
+            write!(f, "{} -> ", n)?;
+        }
+        write!(f, "{}", self.cycle[0])
+    }
+}
+
+impl Error for CycleError {}
+
+///
+/// order the nodes so that every edge goes forward (Kahn's algorithm)
+///
+/// 入度为0的节点按编号顺序输出，所以结果是确定的。
+/// 失败时剩下的节点都有来自剩余节点的入边，沿着入边反向走一定会回到走过的节点，由此找出一个环。
+/// 无向图的每条边都被看作两个方向，所以只有没有边的无向图可以排序
+///
+pub fn topological_sort<N, E>(graph: &Graph<N, E>) -> Result<Vec<NodeId>, CycleError> {
+    let mut in_degree: Vec<usize> = graph.node_ids().map(|n| graph.predecessors(n).count()).collect();
+    let mut ready: VecDeque<NodeId> = graph.node_ids().filter(|n| in_degree[n.0] == 0).collect();
+    let mut order = Vec::with_capacity(graph.node_count());
+    while let Some(node) = ready.pop_front() {
+        order.push(node);
+        for (n, _) in graph.neighbors(node) {
+            in_degree[n.0] -= 1;
+            if in_degree[n.0] == 0 {
+                ready.push_back(n);
+            }
+        }
+    }
+    if order.len() == graph.node_count() {
+        return Ok(order);
+    }
+
+    let start = graph.node_ids().find(|n| in_degree[n.0] > 0).expect("a node is left");
+    // step[i]是反向走时第一次到达节点i的序号
+    let mut step = vec![usize::MAX; graph.node_count()];
+    let mut path = vec![start];
+    let mut node = start;
+    loop {
+        step[node.0] = path.len() - 1;
+        node = graph
+            .predecessors(node)
+            .map(|(n, _)| n)
+            .find(|n| in_degree[n.0] > 0)
+            .expect("a left node has a left predecessor");
+        if step[node.0] != usize::MAX {
+            let mut cycle = path.split_off(step[node.0]);
+            cycle.reverse();
+            let first = (0..cycle.len()).min_by_key(|&i| cycle[i]).expect("cycle is not empty");
+            cycle.rotate_left(first);
+            return Err(CycleError { cycle });
+        }
+        path.push(node);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn directed(n: usize, edges: &[(usize, usize)]) -> (Graph<usize>, Vec<NodeId>) {
+        let mut g = Graph::new_directed();
+        let ids: Vec<_> = (0..n).map(|i| g.add_node(i)).collect();
+        for &(a, b) in edges {
+            g.add_edge(ids[a], ids[b], ());
+        }
+        (g, ids)
+    }
+
+    #[test]
+    fn traversal() {
+        let (g, n) = directed(6, &[(0, 1), (0, 2), (1, 3), (2, 3), (3, 0), (4, 5)]);
+        assert_eq!(vec![n[0], n[1], n[2], n[3]], g.bfs(n[0]).collect::<Vec<_>>());
+        assert_eq!(vec![n[0], n[1], n[3], n[2]], g.dfs(n[0]).collect::<Vec<_>>());
+        assert_eq!(vec![n[5]], g.dfs(n[5]).collect::<Vec<_>>());
+        assert_eq!(vec![n[4], n[5]], Bfs::new(&g, n[4]).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn sort() {
+        let (g, n) = directed(5, &[(3, 1), (1, 0), (3, 0), (4, 2)]);
+        let order = topological_sort(&g).unwrap();
+        assert_eq!(vec![n[3], n[4], n[1], n[2], n[0]], order);
+        for (a, b, _) in g.edges() {
+            assert!(order.iter().position(|&x| x == a) < order.iter().position(|&x| x == b));
+        }
+        assert!(topological_sort(&Graph::<()>::new_directed()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn cycle() {
+        let (g, n) = directed(5, &[(0, 1), (1, 2), (2, 3), (3, 1), (3, 4)]);
+        let err = topological_sort(&g).unwrap_err();
+        assert_eq!(&[n[1], n[2], n[3]], err.cycle());
+        assert_eq!("graph has a cycle: 1 -> 2 -> 3 -> 1", err.to_string());
+
+        let (g, n) = directed(2, &[(0, 1), (1, 1)]);
+        assert_eq!(&[n[1]], topological_sort(&g).unwrap_err().cycle());
+
+        let mut g = Graph::new_undirected();
+        let a = g.add_node(());
+        let b = g.add_node(());
+        g.add_edge(a, b, ());
+        assert_eq!(2, topological_sort(&g).unwrap_err().cycle().len());
+    }
+}
@@ -5,7 +5,25 @@
 //!
 
 // 使用re-export避免crate使用者需要感知内部复杂的层次结构
+pub use arithmetic::{Arithmetic, ArithmeticError};
+pub use bigint::{BigInt, BigUint};
 pub use converter::convert_to_i32;
+pub use matrix::{Matrix, MatrixError};
+pub use priority_queue::IndexedPriorityQueue;
+pub use queue::{Deque, Queue};
+pub use stack::{CapacityError, MinStack, Stack};
+
+pub mod arithmetic;
+pub mod bigint;
+pub mod converter;
+pub mod data_struct;
+pub mod graph;
+pub mod matrix;
+pub mod priority_queue;
+pub mod queue;
+pub mod selection;
+pub mod stack;
+pub mod tokenizer;
 
 ///
 /// add two number
@@ -27,6 +45,8 @@ pub use converter::convert_to_i32;
 ///
 /// Output是associated type，可以在泛型声明处制定
 ///
+/// 溢出时的行为和`+`一致，需要检查溢出请使用[Arithmetic::try_add]
+///
 pub fn add<T: std::ops::Add<Output = T>>(a: T, b: T) -> T {
     a + b
 }
@@ -39,8 +59,13 @@ pub fn add<T: std::ops::Add<Output = T>>(a: T, b: T) -> T {
 /// # // panics on devision by zero
 /// # let r = basic_utils::devide(10,0);
 /// ```
+///
+/// 除0或者`i32::MIN / -1`时panic，不希望panic请使用[Arithmetic::try_div]
 pub fn devide(a: i32, b: i32) -> i32 {
-    a / b
+    match a.try_div(b) {
+        Ok(r) => r,
+        Err(e) => panic!("devide {} by {} failed: {}", a, b, e),
+    }
 }
 
 /// []支持文档跳转
@@ -50,137 +75,26 @@ pub fn add_one(t: i32) -> Option<i32> {
     Some(r)
 }
 
+/// # Panics
+///
+/// 空切片时panic，不希望panic或者需要跳过NaN请使用[selection::largest]
 pub fn largest<T: PartialOrd>(list: &[T]) -> &T {
-    let mut largest = &list[0];
-
-    for item in list.iter() {
-        if item > largest {
-            largest = item;
-        }
-    }
-
-    largest
+    selection::largest(list).expect("largest of an empty slice")
 }
 
 ///
 /// return first world of &str
 ///
+/// 按[tokenizer]的默认规则分词，没有单词时返回空字符串
+///
+/// ```
+/// assert_eq!("hello", basic_utils::first_world("hello world"));
+/// assert_eq!("hello", basic_utils::first_world("\t\"hello,\nworld"));
+/// assert_eq!("", basic_utils::first_world("  "));
+/// ```
+///
 pub fn first_world(s: &str) -> &str {
-    let bs = s.as_bytes();
-    for (i, &item) in bs.iter().enumerate() {
-        if item == b' ' {
-            return &s[0..i];
-        }
-    }
-    return &s[..];
-}
-
-pub mod converter {
-    ///
-    /// convert &str to i32, if err exists return default
-    ///
-    /// ```
-    /// let r1 = basic_utils::convert_to_i32(0, "a");
-    /// assert_eq!(0, r1);
-    ///
-    /// let r2 = basic_utils::convert_to_i32(0, "123");
-    /// assert_eq!(123, r2);
-    ///
-    /// ```
-    ///
-    pub fn convert_to_i32(default: i32, str: &str) -> i32 {
-        let r = str.trim().parse::<i32>();
-        if r.is_err() {
-            default
-        } else {
-            r.unwrap()
-        }
-    }
-}
-
-pub mod data_struct {
-    use std::{cell::RefCell, rc::Rc};
-
-    pub struct ListNode<T> {
-        val: T,
-        next: Option<Rc<RefCell<ListNode<T>>>>,
-    }
-
-    #[derive(Debug, PartialEq, Eq)]
-    pub struct BinaryTreeNode {
-        pub left: Option<Rc<RefCell<BinaryTreeNode>>>,
-        pub right: Option<Rc<RefCell<BinaryTreeNode>>>,
-        pub val: i32,
-    }
-
-    impl BinaryTreeNode {
-        #[inline]
-        pub fn new(val: i32) -> Self {
-            BinaryTreeNode {
-                left: None,
-                right: None,
-                val,
-            }
-        }
-
-        #[inline]
-        pub fn from(
-            left: Option<Rc<RefCell<BinaryTreeNode>>>,
-            right: Option<Rc<RefCell<BinaryTreeNode>>>,
-            val: i32,
-        ) -> Self {
-            BinaryTreeNode { left, right, val }
-        }
-
-        #[inline]
-        pub fn set_left(&mut self, l: &Rc<RefCell<BinaryTreeNode>>) {
-            self.left = Some(Rc::clone(l));
-        }
-
-        #[inline]
-        pub fn set_left_none(&mut self) {
-            self.left = None;
-        }
-
-        #[inline]
-        pub fn set_right(&mut self, l: &Rc<RefCell<BinaryTreeNode>>) {
-            self.right = Some(Rc::clone(l));
-        }
-
-        #[inline]
-        pub fn set_right_none(&mut self) {
-            self.right = None;
-        }
-    }
-}
-
-pub struct Stack<T> {
-    elements: Vec<T>,
-}
-
-impl<T> Stack<T> {
-    pub fn new() -> Self {
-        Stack { elements: Vec::new() }
-    }
-
-    pub fn push(&mut self, item: T) {
-        self.elements.push(item);
-    }
-
-    pub fn pop(&mut self) -> Option<T> {
-        self.elements.pop()
-    }
-
-    pub fn peek(&self) -> Option<&T> {
-        if self.elements.len() == 0 {
-            return None;
-        }
-        self.elements.get(self.elements.len() - 1)
-    }
-
-    pub fn is_empty(&self) -> bool {
-        self.elements.is_empty()
-    }
+    tokenizer::nth_word(s, 0).unwrap_or("")
 }
 
 // 测试模块，测试用例尽量写到这里边
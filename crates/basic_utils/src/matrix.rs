@@ -0,0 +1,709 @@
+//!
+//! dense row-major matrix
+//!
+//! 和[crate::add]一样通过`std::ops`的trait约束元素类型：加减只需要`Add`/`Sub`，
+//! 乘法和幂需要[Scalar]提供0和1，行列式和逆矩阵需要[Float]，只有f32, f64实现了它。
+//! 两个矩阵之间的运算形状不匹配时返回[MatrixError]，所以`&a + &b`的结果是`Result`
+//!
+//! ```
+//! use basic_utils::matrix::Matrix;
+//!
+//! let a = Matrix::from_rows(vec![vec![1, 2], vec![3, 4]]).unwrap();
+//! let b = Matrix::identity(2);
+//! assert_eq!(Ok(a.clone()), &a * &b);
+//! assert_eq!(vec![2, 2], (&a + &b).unwrap().row(0).to_vec());
+//! assert!((&a + &Matrix::zeros(2, 3)).is_err());
+//! assert_eq!(Matrix::from_rows(vec![vec![1, 3], vec![2, 4]]), Ok(a.transpose()));
+//! ```
+//!
+use std::{
+    cmp::Ordering,
+    error::Error,
+    fmt,
+    ops::{Add, Div, Index, IndexMut, Mul, Neg, Sub},
+};
+
+/// number types which can be multiplied as matrix elements
+pub trait Scalar: Copy + PartialEq + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> {
+    const ZERO: Self;
+    const ONE: Self;
+}
+
+macro_rules! impl_scalar {
+    ($zero:literal, $one:literal, $($t:ty)*) => {$(
+        impl Scalar for $t {
+            const ZERO: Self = $zero;
+            const ONE: Self = $one;
+        }
+    )*};
+}
+
+impl_scalar!(0, 1, i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize);
+impl_scalar!(0.0, 1.0, f32 f64);
+
+/// floating point scalars, needed by [Matrix::determinant] and [Matrix::inverse]
+pub trait Float: Scalar + PartialOrd + Div<Output = Self> + Neg<Output = Self> {
+    const EPSILON: Self;
+
+    fn abs(self) -> Self;
+
+    fn from_usize(n: usize) -> Self;
+
+    /// false for NaN and the infinities
+    fn is_finite(self) -> bool;
+
+    /// IEEE 754 total order, never fails even for NaN
+    fn total_cmp(&self, other: &Self) -> Ordering;
+}
+
+macro_rules! impl_float {
+    ($($t:ident)*) => {$(
+        impl Float for $t {
+            const EPSILON: Self = $t::EPSILON;
+
+            fn abs(self) -> Self {
+                $t::abs(self)
+            }
+
+            fn from_usize(n: usize) -> Self {
+                n as $t
+            }
+
+            fn is_finite(self) -> bool {
+                $t::is_finite(self)
+            }
+
+            fn total_cmp(&self, other: &Self) -> Ordering {
+                $t::total_cmp(self, other)
+            }
+        }
+    )*};
+}
+
+impl_float!(f32 f64);
+
+/// error returned by matrix construction and arithmetic
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatrixError {
+    /// the shapes of two operands are incompatible, shapes are `(rows, cols)`
+    ShapeMismatch {
+        op: &'static str,
+        left: (usize, usize),
+        right: (usize, usize),
+    },
+    /// a row of the nested Vecs has a different length from the first row
+    RaggedRows { row: usize, expected: usize, found: usize },
+    /// the length of the data is not `rows * cols`
+    DataLength { expected: usize, found: usize },
+    /// the operation is only defined for square matrices
+    NotSquare { rows: usize, cols: usize },
+    /// the matrix has no inverse
+    Singular,
+    /// an element is NaN or infinite, elimination on it has no meaningful result
+    NonFinite,
+}
+
+impl fmt::Display for MatrixError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MatrixError::ShapeMismatch { op, left, right } => write!(
+                f,
+                "cannot {} a {}x{} matrix and a {}x{} matrix",
+                op, left.0, left.1, right.0, right.1
+            ),
+            MatrixError::RaggedRows { row, expected, found } => {
+                write!(f, "row {} has {} columns, expected {}", row, found, expected)
+            }
+            MatrixError::DataLength { expected, found } => {
+                write!(f, "expected {} elements, found {}", expected, found)
+            }
+            MatrixError::NotSquare { rows, cols } => write!(f, "{}x{} matrix is not square", rows, cols),
+            MatrixError::Singular => write!(f, "matrix is singular"),
+            MatrixError::NonFinite => write!(f, "matrix has a NaN or infinite element"),
+        }
+    }
+}
+
+impl Error for MatrixError {}
+
+/// `rows x cols` matrix stored row by row in one `Vec`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Matrix<T> {
+    rows: usize,
+    cols: usize,
+    data: Vec<T>,
+}
+
+impl<T> Matrix<T> {
+    /// build from row-major `data`
+    pub fn from_vec(rows: usize, cols: usize, data: Vec<T>) -> Result<Self, MatrixError> {
+        if data.len() != rows * cols {
+            return Err(MatrixError::DataLength {
+                expected: rows * cols,
+                found: data.len(),
+            });
+        }
+        Ok(Matrix { rows, cols, data })
+    }
+
+    /// build from nested Vecs, every row must have the same length
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self, MatrixError> {
+        let cols = rows.first().map_or(0, Vec::len);
+        let n = rows.len();
+        let mut data = Vec::with_capacity(n * cols);
+        for (i, row) in rows.into_iter().enumerate() {
+            if row.len() != cols {
+                return Err(MatrixError::RaggedRows {
+                    row: i,
+                    expected: cols,
+                    found: row.len(),
+                });
+            }
+            data.extend(row);
+        }
+        Ok(Matrix { rows: n, cols, data })
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// `(rows, cols)`
+    pub fn shape(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+
+    pub fn is_square(&self) -> bool {
+        self.rows == self.cols
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> Option<&T> {
+        if row < self.rows && col < self.cols {
+            self.data.get(row * self.cols + col)
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, row: usize, col: usize) -> Option<&mut T> {
+        if row < self.rows && col < self.cols {
+            self.data.get_mut(row * self.cols + col)
+        } else {
+            None
+        }
+    }
+
+    ///
+    /// # Panics
+    ///
+    /// panics if `row` is out of range
+    ///
+    pub fn row(&self, row: usize) -> &[T] {
+        assert!(row < self.rows, "row {} out of range for {} rows", row, self.rows);
+        &self.data[row * self.cols..(row + 1) * self.cols]
+    }
+
+    pub fn iter_rows(&self) -> impl ExactSizeIterator<Item = &[T]> {
+        (0..self.rows).map(|r| self.row(r))
+    }
+
+    /// apply `f` to every element
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Matrix<U> {
+        Matrix {
+            rows: self.rows,
+            cols: self.cols,
+            data: self.data.iter().map(f).collect(),
+        }
+    }
+
+    pub fn into_vec(self) -> Vec<T> {
+        self.data
+    }
+
+    fn check_square(&self) -> Result<(), MatrixError> {
+        if self.is_square() {
+            Ok(())
+        } else {
+            Err(MatrixError::NotSquare {
+                rows: self.rows,
+                cols: self.cols,
+            })
+        }
+    }
+
+    fn check_same_shape<U>(&self, other: &Matrix<U>, op: &'static str) -> Result<(), MatrixError> {
+        if self.shape() == other.shape() {
+            Ok(())
+        } else {
+            Err(MatrixError::ShapeMismatch {
+                op,
+                left: self.shape(),
+                right: other.shape(),
+            })
+        }
+    }
+
+    fn zip_with(&self, other: &Self, op: &'static str, f: impl Fn(T, T) -> T) -> Result<Self, MatrixError>
+    where
+        T: Copy,
+    {
+        self.check_same_shape(other, op)?;
+        Ok(Matrix {
+            rows: self.rows,
+            cols: self.cols,
+            data: self.data.iter().zip(&other.data).map(|(&a, &b)| f(a, b)).collect(),
+        })
+    }
+}
+
+impl<T: Clone> Matrix<T> {
+    pub fn transpose(&self) -> Self {
+        let mut data = Vec::with_capacity(self.data.len());
+        for c in 0..self.cols {
+            data.extend((0..self.rows).map(|r| self.data[r * self.cols + c].clone()));
+        }
+        Matrix {
+            rows: self.cols,
+            cols: self.rows,
+            data,
+        }
+    }
+}
+
+impl<T: Scalar> Matrix<T> {
+    pub fn zeros(rows: usize, cols: usize) -> Self {
+        Matrix {
+            rows,
+            cols,
+            data: vec![T::ZERO; rows * cols],
+        }
+    }
+
+    pub fn identity(n: usize) -> Self {
+        let mut m = Self::zeros(n, n);
+        for i in 0..n {
+            m.data[i * n + i] = T::ONE;
+        }
+        m
+    }
+
+    ///
+    /// `self` raised to the power `exp` by repeated squaring, O(n³ log exp)
+    ///
+    /// 常用于线性递推：把递推关系写成矩阵，第n项就是矩阵的n次幂乘以初始向量
+    ///
+    /// ```
+    /// use basic_utils::matrix::Matrix;
+    ///
+    /// // F(n+1) = F(n) + F(n-1)
+    /// let fib = Matrix::from_rows(vec![vec![1u64, 1], vec![1, 0]]).unwrap();
+    /// assert_eq!(Ok(12586269025), fib.pow(50).map(|m| m[(0, 1)]));
+    /// assert_eq!(Ok(Matrix::identity(2)), fib.pow(0));
+    /// ```
+    ///
+    pub fn pow(&self, mut exp: u32) -> Result<Self, MatrixError> {
+        self.check_square()?;
+        let mut result = Self::identity(self.rows);
+        let mut base = self.clone();
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = multiply(&result, &base);
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base = multiply(&base, &base);
+            }
+        }
+        Ok(result)
+    }
+}
+
+/// the shapes have been checked by the caller
+fn multiply<T: Scalar>(a: &Matrix<T>, b: &Matrix<T>) -> Matrix<T> {
+    let mut r = Matrix::zeros(a.rows, b.cols);
+    // i-k-j的顺序使最内层循环按行连续访问b和r
+    for i in 0..a.rows {
+        for k in 0..a.cols {
+            let x = a.data[i * a.cols + k];
+            let b_row = &b.data[k * b.cols..(k + 1) * b.cols];
+            let r_row = &mut r.data[i * b.cols..(i + 1) * b.cols];
+            for (r, &y) in r_row.iter_mut().zip(b_row) {
+                *r = *r + x * y;
+            }
+        }
+    }
+    r
+}
+
+impl<T: Float> Matrix<T> {
+    ///
+    /// determinant by Gaussian elimination with partial pivoting
+    ///
+    /// 有NaN或无穷大元素时返回[MatrixError::NonFinite]
+    ///
+    /// ```
+    /// use basic_utils::matrix::Matrix;
+    ///
+    /// let m = Matrix::from_rows(vec![vec![2.0, 1.0], vec![4.0, 5.0]]).unwrap();
+    /// assert_eq!(Ok(6.0), m.determinant());
+    /// assert!(Matrix::<f32>::zeros(2, 3).determinant().is_err());
+    /// ```
+    ///
+    pub fn determinant(&self) -> Result<T, MatrixError> {
+        self.check_square()?;
+        self.check_finite()?;
+        let n = self.rows;
+        let mut a = self.data.clone();
+        let mut det = T::ONE;
+        for col in 0..n {
+            let pivot = pivot_row(&a, n, col);
+            if a[pivot * n + col] == T::ZERO {
+                return Ok(T::ZERO);
+            }
+            if pivot != col {
+                swap_rows(&mut a, n, pivot, col);
+                det = -det;
+            }
+            let p = a[col * n + col];
+            det = det * p;
+            for r in col + 1..n {
+                let factor = a[r * n + col] / p;
+                for c in col..n {
+                    a[r * n + c] = a[r * n + c] - factor * a[col * n + c];
+                }
+            }
+        }
+        Ok(det)
+    }
+
+    ///
+    /// inverse by Gauss-Jordan elimination with partial pivoting
+    ///
+    /// 主元的绝对值不超过`n * EPSILON * 最大元素`时视为奇异矩阵
+    /// 和[Matrix::determinant]一样，有NaN或无穷大元素时返回[MatrixError::NonFinite]
+    ///
+    /// ```
+    /// use basic_utils::matrix::{Matrix, MatrixError};
+    ///
+    /// let m = Matrix::from_rows(vec![vec![4.0, 7.0], vec![2.0, 6.0]]).unwrap();
+    /// let inv = m.inverse().unwrap();
+    /// assert_eq!(Matrix::from_rows(vec![vec![0.6, -0.7], vec![-0.2, 0.4]]).unwrap(), inv.map(|x: &f64| (x * 10.0).round() / 10.0));
+    ///
+    /// let singular = Matrix::from_rows(vec![vec![1.0, 2.0], vec![2.0, 4.0]]).unwrap();
+    /// assert_eq!(Err(MatrixError::Singular), singular.inverse());
+    /// ```
+    ///
+    pub fn inverse(&self) -> Result<Self, MatrixError> {
+        self.check_square()?;
+        self.check_finite()?;
+        let n = self.rows;
+        let mut a = self.data.clone();
+        let mut inv = Self::identity(n).data;
+        let max = a.iter().fold(T::ZERO, |m, &x| if x.abs() > m { x.abs() } else { m });
+        let tolerance = T::from_usize(n) * T::EPSILON * max;
+        for col in 0..n {
+            let pivot = pivot_row(&a, n, col);
+            if a[pivot * n + col].abs() <= tolerance {
+                return Err(MatrixError::Singular);
+            }
+            swap_rows(&mut a, n, pivot, col);
+            swap_rows(&mut inv, n, pivot, col);
+            let p = a[col * n + col];
+            for c in 0..n {
+                a[col * n + c] = a[col * n + c] / p;
+                inv[col * n + c] = inv[col * n + c] / p;
+            }
+            for r in (0..n).filter(|&r| r != col) {
+                let factor = a[r * n + col];
+                if factor == T::ZERO {
+                    continue;
+                }
+                for c in 0..n {
+                    a[r * n + c] = a[r * n + c] - factor * a[col * n + c];
+                    inv[r * n + c] = inv[r * n + c] - factor * inv[col * n + c];
+                }
+            }
+        }
+        Ok(Matrix {
+            rows: n,
+            cols: n,
+            data: inv,
+        })
+    }
+
+    fn check_finite(&self) -> Result<(), MatrixError> {
+        match self.data.iter().all(|x| x.is_finite()) {
+            true => Ok(()),
+            false => Err(MatrixError::NonFinite),
+        }
+    }
+}
+
+/// the row at or below `col` with the largest absolute value in column `col`
+fn pivot_row<T: Float>(a: &[T], n: usize, col: usize) -> usize {
+    (col..n)
+        .max_by(|&x, &y| a[x * n + col].abs().total_cmp(&a[y * n + col].abs()))
+        .expect("col < n")
+}
+
+fn swap_rows<T>(a: &mut [T], n: usize, x: usize, y: usize) {
+    if x != y {
+        let (x, y) = (x.min(y), x.max(y));
+        let (top, bottom) = a.split_at_mut(y * n);
+        top[x * n..(x + 1) * n].swap_with_slice(&mut bottom[..n]);
+    }
+}
+
+impl<T> Index<(usize, usize)> for Matrix<T> {
+    type Output = T;
+
+    /// `m[(row, col)]`, panics if out of range
+    fn index(&self, (row, col): (usize, usize)) -> &T {
+        self.get(row, col).unwrap_or_else(|| {
+            panic!(
+                "index ({}, {}) out of range for {}x{} matrix",
+                row, col, self.rows, self.cols
+            )
+        })
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Matrix<T> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut T {
+        let (rows, cols) = self.shape();
+        self.get_mut(row, col)
+            .unwrap_or_else(|| panic!("index ({}, {}) out of range for {}x{} matrix", row, col, rows, cols))
+    }
+}
+
+impl<T: Copy + Add<Output = T>> Add for &Matrix<T> {
+    type Output = Result<Matrix<T>, MatrixError>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.zip_with(rhs, "add", |a, b| a + b)
+    }
+}
+
+impl<T: Copy + Add<Output = T>> Add for Matrix<T> {
+    type Output = Result<Matrix<T>, MatrixError>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        &self + &rhs
+    }
+}
+
+impl<T: Copy + Sub<Output = T>> Sub for &Matrix<T> {
+    type Output = Result<Matrix<T>, MatrixError>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.zip_with(rhs, "subtract", |a, b| a - b)
+    }
+}
+
+impl<T: Copy + Sub<Output = T>> Sub for Matrix<T> {
+    type Output = Result<Matrix<T>, MatrixError>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        &self - &rhs
+    }
+}
+
+impl<T: Scalar> Mul for &Matrix<T> {
+    type Output = Result<Matrix<T>, MatrixError>;
+
+    /// matrix product, the columns of `self` must match the rows of `rhs`
+    fn mul(self, rhs: Self) -> Self::Output {
+        if self.cols != rhs.rows {
+            return Err(MatrixError::ShapeMismatch {
+                op: "multiply",
+                left: self.shape(),
+                right: rhs.shape(),
+            });
+        }
+        Ok(multiply(self, rhs))
+    }
+}
+
+impl<T: Scalar> Mul for Matrix<T> {
+    type Output = Result<Matrix<T>, MatrixError>;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        &self * &rhs
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Mul<T> for &Matrix<T> {
+    type Output = Matrix<T>;
+
+    /// multiply every element by `rhs`
+    fn mul(self, rhs: T) -> Matrix<T> {
+        self.map(|&x| x * rhs)
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Mul<T> for Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn mul(self, rhs: T) -> Matrix<T> {
+        &self * rhs
+    }
+}
+
+impl<T: Copy + Div<Output = T>> Div<T> for &Matrix<T> {
+    type Output = Matrix<T>;
+
+    /// divide every element by `rhs`
+    fn div(self, rhs: T) -> Matrix<T> {
+        self.map(|&x| x / rhs)
+    }
+}
+
+impl<T: Copy + Div<Output = T>> Div<T> for Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn div(self, rhs: T) -> Matrix<T> {
+        &self / rhs
+    }
+}
+
+impl<T: Copy + Neg<Output = T>> Neg for &Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn neg(self) -> Matrix<T> {
+        self.map(|&x| -x)
+    }
+}
+
+impl<T: Copy + Neg<Output = T>> Neg for Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn neg(self) -> Matrix<T> {
+        -&self
+    }
+}
+
+impl<T> TryFrom<Vec<Vec<T>>> for Matrix<T> {
+    type Error = MatrixError;
+
+    fn try_from(rows: Vec<Vec<T>>) -> Result<Self, Self::Error> {
+        Self::from_rows(rows)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Matrix<T> {
+    /// one row per line, `[1, 2]`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, row) in self.iter_rows().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "[")?;
+            for (j, x) in row.iter().enumerate() {
+                if j > 0 {
+                    write!(f, ", ")?;
+                }
+                x.fmt(f)?;
+            }
+            write!(f, "]")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn m(rows: Vec<Vec<i64>>) -> Matrix<i64> {
+        Matrix::from_rows(rows).unwrap()
+    }
+
+    #[test]
+    fn shapes() {
+        let a = m(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        assert_eq!((2, 3), a.shape());
+        assert_eq!(6, a[(1, 2)]);
+        assert_eq!(None, a.get(2, 0));
+        assert_eq!(
+            Err(MatrixError::RaggedRows {
+                row: 1,
+                expected: 2,
+                found: 1
+            }),
+            Matrix::from_rows(vec![vec![1, 2], vec![3]])
+        );
+        assert_eq!(
+            Err(MatrixError::DataLength { expected: 4, found: 3 }),
+            Matrix::from_vec(2, 2, vec![1, 2, 3])
+        );
+        let err = (&a * &a).unwrap_err();
+        assert_eq!("cannot multiply a 2x3 matrix and a 2x3 matrix", err.to_string());
+        assert_eq!(Err(MatrixError::NotSquare { rows: 2, cols: 3 }), a.pow(2));
+        assert_eq!((0, 0), Matrix::<i32>::from_rows(vec![]).unwrap().shape());
+        assert_eq!("[1, 2, 3]\n[4, 5, 6]", a.to_string());
+    }
+
+    #[test]
+    fn arithmetic() {
+        let a = m(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        let b = a.transpose();
+        assert_eq!(Ok(m(vec![vec![14, 32], vec![32, 77]])), &a * &b);
+        assert_eq!(Ok(a.clone() * 2), a.clone() + a.clone());
+        assert_eq!(Ok(Matrix::zeros(2, 3)), &a - &a);
+        assert_eq!(-&a, &a * -1);
+        assert_eq!(a, (&a * 3) / 3);
+        let mut c = Matrix::identity(2);
+        c[(0, 1)] = 1;
+        assert_eq!(Ok(m(vec![vec![1, 10], vec![0, 1]])), c.pow(10));
+        assert_eq!(Ok(Matrix::identity(0)), Matrix::<i64>::zeros(0, 0).pow(3));
+    }
+
+    #[test]
+    fn inverse_round_trip() {
+        let mut x: u32 = 9;
+        for n in 1..7 {
+            let data: Vec<f64> = (0..n * n)
+                .map(|_| {
+                    x = x.wrapping_mul(1103515245).wrapping_add(12345);
+                    (x >> 16) as f64 % 19.0 - 9.0
+                })
+                .collect();
+            let a = Matrix::from_vec(n, n, data).unwrap();
+            let det = a.determinant().unwrap();
+            match a.inverse() {
+                Ok(inv) => {
+                    let product = (&a * &inv).unwrap();
+                    let error = (&product - &Matrix::identity(n)).unwrap().into_vec();
+                    assert!(error.iter().all(|e| e.abs() < 1e-9), "{}", product);
+                    // det(A⁻¹) = 1 / det(A)
+                    assert!((inv.determinant().unwrap() * det - 1.0).abs() < 1e-9);
+                }
+                Err(e) => {
+                    assert_eq!(MatrixError::Singular, e);
+                    assert!(det.abs() < 1e-9);
+                }
+            }
+        }
+        let swap = Matrix::from_rows(vec![vec![0.0, 1.0], vec![1.0, 0.0]]).unwrap();
+        assert_eq!(Ok(-1.0), swap.determinant());
+        assert_eq!(Ok(swap.clone()), swap.inverse());
+    }
+
+    #[test]
+    fn non_finite() {
+        for bad in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            let a = Matrix::from_rows(vec![vec![1.0, bad], vec![2.0, 3.0]]).unwrap();
+            assert_eq!(Err(MatrixError::NonFinite), a.determinant());
+            assert_eq!(Err(MatrixError::NonFinite), a.inverse());
+        }
+        let a = Matrix::from_rows(vec![vec![f32::NAN]]).unwrap();
+        assert_eq!(
+            "matrix has a NaN or infinite element",
+            a.inverse().unwrap_err().to_string()
+        );
+    }
+}
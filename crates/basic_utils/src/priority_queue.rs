@@ -0,0 +1,348 @@
+use std::{borrow::Borrow, collections::HashMap, fmt, hash::Hash};
+
+/// which item a priority queue pops first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Order {
+    /// smallest priority first
+    #[default]
+    Min,
+    /// largest priority first
+    Max,
+}
+
+///
+/// binary heap priority queue indexed by key, priorities can be changed in O(log n)
+///
+/// heap存放`(key, priority)`，positions记录每个key在heap中的下标，交换元素时同步更新，
+/// 所以按key修改优先级或删除只需要找到下标后上浮/下沉
+///
+/// ```
+/// use basic_utils::IndexedPriorityQueue;
+///
+/// let mut q = IndexedPriorityQueue::new_min();
+/// q.push("write", 3);
+/// q.push("test", 5);
+/// q.push("ship", 9);
+/// assert!(q.decrease_key("ship", 1));
+/// assert!(!q.decrease_key("test", 8));
+/// assert_eq!(Some((&"ship", &1)), q.peek());
+/// assert_eq!(Some(5), q.remove("test"));
+/// assert_eq!(Some(("ship", 1)), q.pop());
+/// assert_eq!(Some(("write", 3)), q.pop());
+/// assert!(q.is_empty());
+/// ```
+///
+pub struct IndexedPriorityQueue<K, P> {
+    heap: Vec<(K, P)>,
+    positions: HashMap<K, usize>,
+    order: Order,
+}
+
+impl<K: Hash + Eq + Clone, P: Ord> IndexedPriorityQueue<K, P> {
+    pub fn with_order(order: Order) -> Self {
+        IndexedPriorityQueue {
+            heap: Vec::new(),
+            positions: HashMap::new(),
+            order,
+        }
+    }
+
+    /// pop the smallest priority first
+    pub fn new_min() -> Self {
+        Self::with_order(Order::Min)
+    }
+
+    /// pop the largest priority first
+    pub fn new_max() -> Self {
+        Self::with_order(Order::Max)
+    }
+
+    pub fn order(&self) -> Order {
+        self.order
+    }
+
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.heap.clear();
+        self.positions.clear();
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.positions.contains_key(key)
+    }
+
+    pub fn get_priority<Q>(&self, key: &Q) -> Option<&P>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.positions.get(key).map(|&i| &self.heap[i].1)
+    }
+
+    /// insert a key, or change its priority if it is already queued, the old priority is returned
+    pub fn push(&mut self, key: K, priority: P) -> Option<P> {
+        if let Some(&i) = self.positions.get(&key) {
+            return Some(self.set_priority(i, priority));
+        }
+        self.positions.insert(key.clone(), self.heap.len());
+        self.heap.push((key, priority));
+        self.sift_up(self.heap.len() - 1);
+        None
+    }
+
+    /// the item which would be popped next
+    pub fn peek(&self) -> Option<(&K, &P)> {
+        self.heap.first().map(|(k, p)| (k, p))
+    }
+
+    pub fn pop(&mut self) -> Option<(K, P)> {
+        if self.heap.is_empty() {
+            return None;
+        }
+        Some(self.remove_at(0))
+    }
+
+    /// set the priority of a queued key, return the old one or None if the key is not queued
+    pub fn change_priority<Q>(&mut self, key: &Q, priority: P) -> Option<P>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let i = *self.positions.get(key)?;
+        Some(self.set_priority(i, priority))
+    }
+
+    ///
+    /// move a queued key closer to the front: decrease its priority in [Order::Min] mode,
+    /// increase it in [Order::Max] mode
+    ///
+    /// 新的优先级不比原来靠前时不做修改，返回false；key不存在时也返回false
+    ///
+    pub fn decrease_key<Q>(&mut self, key: &Q, priority: P) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let Some(&i) = self.positions.get(key) else {
+            return false;
+        };
+        if !self.before(&priority, &self.heap[i].1) {
+            return false;
+        }
+        self.heap[i].1 = priority;
+        self.sift_up(i);
+        true
+    }
+
+    /// remove a key, return its priority
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<P>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let i = *self.positions.get(key)?;
+        Some(self.remove_at(i).1)
+    }
+
+    /// iterate in heap order, not in priority order
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = (&K, &P)> {
+        self.heap.iter().map(|(k, p)| (k, p))
+    }
+
+    /// pop all items in priority order
+    pub fn into_sorted_vec(mut self) -> Vec<(K, P)> {
+        let mut r = Vec::with_capacity(self.len());
+        while let Some(item) = self.pop() {
+            r.push(item);
+        }
+        r
+    }
+
+    /// whether priority `a` should be popped before `b`
+    fn before(&self, a: &P, b: &P) -> bool {
+        match self.order {
+            Order::Min => a < b,
+            Order::Max => a > b,
+        }
+    }
+
+    fn set_priority(&mut self, i: usize, priority: P) -> P {
+        let old = std::mem::replace(&mut self.heap[i].1, priority);
+        let i = self.sift_up(i);
+        self.sift_down(i);
+        old
+    }
+
+    fn remove_at(&mut self, i: usize) -> (K, P) {
+        let last = self.heap.len() - 1;
+        self.swap(i, last);
+        let (key, priority) = self.heap.pop().expect("heap is not empty");
+        self.positions.remove(&key);
+        if i < self.heap.len() {
+            let i = self.sift_up(i);
+            self.sift_down(i);
+        }
+        (key, priority)
+    }
+
+    fn swap(&mut self, i: usize, j: usize) {
+        self.heap.swap(i, j);
+        *self
+            .positions
+            .get_mut(&self.heap[i].0)
+            .expect("queued key has a position") = i;
+        *self
+            .positions
+            .get_mut(&self.heap[j].0)
+            .expect("queued key has a position") = j;
+    }
+
+    /// return the final index
+    fn sift_up(&mut self, mut i: usize) -> usize {
+        while i > 0 {
+            let parent = (i - 1) / 2;
+            if !self.before(&self.heap[i].1, &self.heap[parent].1) {
+                break;
+            }
+            self.swap(i, parent);
+            i = parent;
+        }
+        i
+    }
+
+    fn sift_down(&mut self, mut i: usize) {
+        loop {
+            let mut first = i;
+            for child in [2 * i + 1, 2 * i + 2] {
+                if child < self.heap.len() && self.before(&self.heap[child].1, &self.heap[first].1) {
+                    first = child;
+                }
+            }
+            if first == i {
+                return;
+            }
+            self.swap(i, first);
+            i = first;
+        }
+    }
+}
+
+impl<K: Hash + Eq + Clone, P: Ord> Default for IndexedPriorityQueue<K, P> {
+    fn default() -> Self {
+        Self::new_min()
+    }
+}
+
+impl<K: fmt::Debug, P: fmt::Debug> fmt::Debug for IndexedPriorityQueue<K, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.heap.iter().map(|(k, p)| (k, p))).finish()
+    }
+}
+
+/// a min queue, a repeated key keeps the last priority
+impl<K: Hash + Eq + Clone, P: Ord> FromIterator<(K, P)> for IndexedPriorityQueue<K, P> {
+    fn from_iter<I: IntoIterator<Item = (K, P)>>(iter: I) -> Self {
+        let mut q = Self::new_min();
+        q.extend(iter);
+        q
+    }
+}
+
+impl<K: Hash + Eq + Clone, P: Ord> Extend<(K, P)> for IndexedPriorityQueue<K, P> {
+    fn extend<I: IntoIterator<Item = (K, P)>>(&mut self, iter: I) {
+        for (k, p) in iter {
+            self.push(k, p);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    impl<K: Hash + Eq + Clone, P: Ord> IndexedPriorityQueue<K, P> {
+        fn assert_valid(&self) {
+            for i in 1..self.heap.len() {
+                assert!(!self.before(&self.heap[i].1, &self.heap[(i - 1) / 2].1));
+            }
+            assert_eq!(self.heap.len(), self.positions.len());
+            for (i, (k, _)) in self.heap.iter().enumerate() {
+                assert_eq!(Some(&i), self.positions.get(k));
+            }
+        }
+    }
+
+    #[test]
+    fn matches_model() {
+        let mut model: HashMap<u32, i32> = HashMap::new();
+        let mut q = IndexedPriorityQueue::new_min();
+        let mut x: u32 = 7;
+        for _ in 0..2000 {
+            x = x.wrapping_mul(1103515245).wrapping_add(12345);
+            let key = (x >> 8) % 40;
+            let priority = ((x >> 16) % 100) as i32;
+            match x % 5 {
+                0 | 1 => assert_eq!(model.insert(key, priority), q.push(key, priority)),
+                2 => {
+                    let improves = model.get(&key).is_some_and(|&p| priority < p);
+                    assert_eq!(improves, q.decrease_key(&key, priority));
+                    if improves {
+                        model.insert(key, priority);
+                    }
+                }
+                3 => assert_eq!(model.remove(&key), q.remove(&key)),
+                _ => {
+                    let expected = model.values().min().copied();
+                    let popped = q.pop();
+                    assert_eq!(expected, popped.map(|(_, p)| p));
+                    if let Some((k, _)) = popped {
+                        model.remove(&k);
+                    }
+                }
+            }
+            q.assert_valid();
+            assert_eq!(model.len(), q.len());
+        }
+    }
+
+    #[test]
+    fn max_order() {
+        let mut q: IndexedPriorityQueue<String, u8> = IndexedPriorityQueue::new_max();
+        q.extend([("a".to_string(), 1), ("b".to_string(), 5), ("c".to_string(), 3)]);
+        assert_eq!(Order::Max, q.order());
+        assert!(q.decrease_key("a", 9));
+        assert!(!q.decrease_key("c", 2));
+        assert_eq!(Some(5), q.change_priority("b", 0));
+        assert_eq!(None, q.change_priority("d", 0));
+        assert_eq!(Some(&0), q.get_priority("b"));
+        assert!(q.contains_key("c"));
+        let sorted: Vec<_> = q.into_sorted_vec().into_iter().map(|(k, p)| (k.clone(), p)).collect();
+        assert_eq!(
+            vec![("a".to_string(), 9), ("c".to_string(), 3), ("b".to_string(), 0)],
+            sorted
+        );
+    }
+
+    #[test]
+    fn from_iter() {
+        let mut q: IndexedPriorityQueue<char, i32> = "hello".chars().zip([5, 3, 8, 1, 2]).collect();
+        // 'l' is repeated, the last priority 1 wins
+        assert_eq!(4, q.len());
+        assert_eq!(Some(('l', 1)), q.pop());
+        assert_eq!(Some(('o', 2)), q.pop());
+        q.clear();
+        assert_eq!(None, q.peek());
+        assert_eq!(None, q.pop());
+    }
+}
@@ -0,0 +1,360 @@
+use std::fmt;
+
+use crate::stack::{fmt_list, CapacityError, Stack};
+
+///
+/// FIFO queue built from two stacks
+///
+/// push压入inbox，pop时若outbox为空，则把inbox整体倒入outbox，每个元素最多被移动一次，均摊O(1)
+///
+/// ```
+/// use basic_utils::Queue;
+///
+/// let mut q: Queue<i32> = (1..=3).collect();
+/// q.push(4);
+/// assert_eq!(Some(1), q.pop());
+/// assert_eq!(Some(&2), q.peek());
+/// assert_eq!("[2, 3, 4]", q.to_string());
+/// ```
+///
+pub struct Queue<T> {
+    inbox: Stack<T>,
+    outbox: Stack<T>,
+}
+
+impl<T> Queue<T> {
+    pub fn new() -> Self {
+        Queue {
+            inbox: Stack::new(),
+            outbox: Stack::new(),
+        }
+    }
+
+    pub fn push(&mut self, item: T) {
+        self.inbox.push(item);
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        if self.outbox.is_empty() {
+            while let Some(item) = self.inbox.pop() {
+                self.outbox.push(item);
+            }
+        }
+        self.outbox.pop()
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.outbox.peek().or_else(|| self.inbox.bottom())
+    }
+
+    pub fn len(&self) -> usize {
+        self.inbox.len() + self.outbox.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inbox.is_empty() && self.outbox.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.inbox.clear();
+        self.outbox.clear();
+    }
+
+    /// iterate from front to back
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &T> {
+        self.outbox.iter().chain(self.inbox.iter().rev())
+    }
+}
+
+impl<T> Default for Queue<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Queue<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/// front first, `[1, 2, 3]`
+impl<T: fmt::Display> fmt::Display for Queue<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_list(f, self.iter())
+    }
+}
+
+impl<T> FromIterator<T> for Queue<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut q = Queue::new();
+        q.extend(iter);
+        q
+    }
+}
+
+impl<T> Extend<T> for Queue<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.push(item);
+        }
+    }
+}
+
+/// owning iterator of [Queue]
+pub struct IntoIter<T>(Queue<T>);
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.pop()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.len(), Some(self.0.len()))
+    }
+}
+
+impl<T> IntoIterator for Queue<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self)
+    }
+}
+
+///
+/// double-ended queue with a fixed capacity, backed by a ring buffer
+///
+/// head指向第一个元素，第i个元素位于(head + i) % capacity，push/pop两端都是O(1)
+///
+/// ```
+/// use basic_utils::Deque;
+///
+/// let mut d = Deque::with_capacity(3);
+/// d.push_back(2).unwrap();
+/// d.push_front(1).unwrap();
+/// d.push_back(3).unwrap();
+/// assert!(d.push_back(4).is_err());
+/// assert_eq!("[1, 2, 3]", d.to_string());
+/// assert_eq!(Some(3), d.pop_back());
+/// assert_eq!(Some(&2), d.get(1));
+/// ```
+///
+pub struct Deque<T> {
+    buf: Box<[Option<T>]>,
+    head: usize,
+    len: usize,
+}
+
+impl<T> Deque<T> {
+    pub fn with_capacity(capacity: usize) -> Self {
+        Deque {
+            buf: (0..capacity).map(|_| None).collect(),
+            head: 0,
+            len: 0,
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.buf.len()
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len == self.capacity()
+    }
+
+    /// physical index of the i-th element
+    fn slot(&self, i: usize) -> usize {
+        (self.head + i) % self.capacity()
+    }
+
+    pub fn push_back(&mut self, item: T) -> Result<(), CapacityError<T>> {
+        if self.is_full() {
+            return Err(CapacityError::new(item));
+        }
+        let slot = self.slot(self.len);
+        self.buf[slot] = Some(item);
+        self.len += 1;
+        Ok(())
+    }
+
+    pub fn push_front(&mut self, item: T) -> Result<(), CapacityError<T>> {
+        if self.is_full() {
+            return Err(CapacityError::new(item));
+        }
+        self.head = self.slot(self.capacity() - 1);
+        self.buf[self.head] = Some(item);
+        self.len += 1;
+        Ok(())
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        let item = self.buf[self.head].take();
+        self.head = self.slot(1);
+        self.len -= 1;
+        item
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        let slot = self.slot(self.len - 1);
+        self.len -= 1;
+        self.buf[slot].take()
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        self.get(self.len.checked_sub(1)?)
+    }
+
+    pub fn get(&self, i: usize) -> Option<&T> {
+        if i >= self.len {
+            return None;
+        }
+        self.buf[self.slot(i)].as_ref()
+    }
+
+    pub fn get_mut(&mut self, i: usize) -> Option<&mut T> {
+        if i >= self.len {
+            return None;
+        }
+        let slot = self.slot(i);
+        self.buf[slot].as_mut()
+    }
+
+    pub fn clear(&mut self) {
+        while self.pop_front().is_some() {}
+        self.head = 0;
+    }
+
+    /// iterate from front to back
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator {
+        (0..self.len).map(move |i| self.buf[self.slot(i)].as_ref().expect("slot in range is empty"))
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Deque<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/// front first, `[1, 2, 3]`
+impl<T: fmt::Display> fmt::Display for Deque<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_list(f, self.iter())
+    }
+}
+
+/// the capacity is the number of items
+impl<T> FromIterator<T> for Deque<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let buf: Box<[Option<T>]> = iter.into_iter().map(Some).collect();
+        Deque {
+            len: buf.len(),
+            buf,
+            head: 0,
+        }
+    }
+}
+
+///
+/// # Panics
+///
+/// if the deque becomes full, use [Deque::push_back] to keep the rejected items
+///
+impl<T> Extend<T> for Deque<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            if self.push_back(item).is_err() {
+                panic!("push into a full deque, capacity {}", self.capacity());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn queue() {
+        let mut q = Queue::new();
+        assert_eq!(None, q.pop());
+        q.extend([1, 2]);
+        assert_eq!(Some(1), q.pop());
+        q.push(3);
+        // outbox: [2], inbox: [3]
+        assert_eq!(vec![&2, &3], q.iter().collect::<Vec<_>>());
+        assert_eq!(Some(&2), q.peek());
+        assert_eq!(2, q.len());
+        assert_eq!(vec![2, 3], q.into_iter().collect::<Vec<_>>());
+
+        let mut q: Queue<i32> = Queue::default();
+        q.push(1);
+        assert_eq!(Some(&1), q.peek());
+        q.clear();
+        assert!(q.is_empty());
+    }
+
+    #[test]
+    fn deque_wraps_around() {
+        let mut d = Deque::with_capacity(4);
+        for round in 0..10 {
+            d.push_back(round).unwrap();
+            d.push_front(-round).unwrap();
+            assert_eq!(Some(&-round), d.front());
+            assert_eq!(Some(&round), d.back());
+            assert_eq!(Some(-round), d.pop_front());
+            assert_eq!(Some(round), d.pop_back());
+        }
+        assert!(d.is_empty());
+
+        d.extend(1..5);
+        assert!(d.is_full());
+        assert_eq!(vec![&1, &2, &3, &4], d.iter().collect::<Vec<_>>());
+        *d.get_mut(3).unwrap() = 40;
+        assert_eq!(vec![&40, &3, &2, &1], d.iter().rev().collect::<Vec<_>>());
+        assert_eq!(None, d.get(4));
+        d.clear();
+        assert_eq!(None, d.back());
+    }
+
+    #[test]
+    fn deque_zero_capacity() {
+        let mut d = Deque::with_capacity(0);
+        assert!(d.push_back(1).is_err());
+        assert!(d.push_front(1).is_err());
+        assert_eq!(None, d.pop_back());
+
+        let d: Deque<_> = "abc".chars().collect();
+        assert_eq!(3, d.capacity());
+        assert_eq!("[a, b, c]", d.to_string());
+    }
+
+    #[test]
+    #[should_panic(expected = "push into a full deque, capacity 2")]
+    fn deque_extend_beyond_capacity() {
+        let mut d = Deque::with_capacity(2);
+        d.extend(1..4);
+    }
+}
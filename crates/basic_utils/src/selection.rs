@@ -0,0 +1,382 @@
+//!
+//! selection and statistics over slices
+//!
+//! 所有函数在空切片上返回None而不是panic。
+//! 和自身无法比较的元素(`x.partial_cmp(&x) == None`，比如浮点数的NaN)会被跳过，
+//! 所以`[1.0, NaN, 3.0]`的最大值是`3.0`，全是NaN时返回None。
+//!
+//! ```
+//! use basic_utils::selection;
+//!
+//! let v = [3.5, f64::NAN, -1.0, 8.0, 2.0];
+//! assert_eq!(Some(&8.0), selection::largest(&v));
+//! assert_eq!(Some((&-1.0, &8.0)), selection::min_max(&v));
+//! assert_eq!(vec![&8.0, &3.5], selection::top_k(&v, 2));
+//! assert_eq!(Some(&2.0), selection::kth_smallest(&v, 1));
+//! assert_eq!(Some(2.75), selection::median(&v));
+//! ```
+//!
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::BinaryHeap,
+};
+
+fn is_comparable<T: PartialOrd + ?Sized>(x: &T) -> bool {
+    x.partial_cmp(x).is_some()
+}
+
+/// 过滤掉NaN之后剩下的元素是全序的，这里的Equal只是兜底
+fn cmp<T: PartialOrd + ?Sized>(a: &T, b: &T) -> Ordering {
+    a.partial_cmp(b).unwrap_or(Ordering::Equal)
+}
+
+fn comparable<T: PartialOrd>(list: &[T]) -> impl Iterator<Item = &T> {
+    list.iter().filter(|x| is_comparable(*x))
+}
+
+/// the first item whose key is `want` compared to all the previous ones
+fn extreme<'a, T, K: PartialOrd>(pairs: impl Iterator<Item = (&'a T, K)>, want: Ordering) -> Option<&'a T> {
+    let mut best: Option<(&T, K)> = None;
+    for (item, k) in pairs {
+        if !is_comparable(&k) {
+            continue;
+        }
+        if best.as_ref().is_none_or(|(_, b)| k.partial_cmp(b) == Some(want)) {
+            best = Some((item, k));
+        }
+    }
+    best.map(|(item, _)| item)
+}
+
+/// `(first item with the smallest key, first item with the largest key)`
+fn extremes<'a, T, K: PartialOrd>(pairs: impl Iterator<Item = (&'a T, K)>) -> Option<(&'a T, &'a T)> {
+    let mut pairs = pairs.filter(|(_, k)| is_comparable(k));
+    let first = pairs.next()?;
+    // K不一定能Clone，hi为None表示最大的就是lo
+    let (lo, hi) = pairs.fold((first, None), |(lo, hi), p| {
+        if p.1 < lo.1 {
+            (p, Some(hi.unwrap_or(lo)))
+        } else if p.1 > hi.as_ref().unwrap_or(&lo).1 {
+            (lo, Some(p))
+        } else {
+            (lo, hi)
+        }
+    });
+    Some((lo.0, hi.map_or(lo.0, |(item, _)| item)))
+}
+
+///
+/// the largest item, the first one wins a tie
+///
+/// ```
+/// assert_eq!(Some(&'y'), basic_utils::selection::largest(&['q', 'y', 'a']));
+/// assert_eq!(None, basic_utils::selection::largest::<i32>(&[]));
+/// ```
+///
+pub fn largest<T: PartialOrd>(list: &[T]) -> Option<&T> {
+    extreme(list.iter().map(|x| (x, x)), Ordering::Greater)
+}
+
+/// the smallest item, the first one wins a tie
+pub fn smallest<T: PartialOrd>(list: &[T]) -> Option<&T> {
+    extreme(list.iter().map(|x| (x, x)), Ordering::Less)
+}
+
+/// `(smallest, largest)` in one pass
+pub fn min_max<T: PartialOrd>(list: &[T]) -> Option<(&T, &T)> {
+    extremes(list.iter().map(|x| (x, x)))
+}
+
+///
+/// the item with the largest key, the first one wins a tie
+///
+/// ```
+/// let words = ["apple", "fig", "banana", "kiwi"];
+/// assert_eq!(Some(&"banana"), basic_utils::selection::largest_by_key(&words, |w| w.len()));
+/// ```
+///
+pub fn largest_by_key<T, K: PartialOrd>(list: &[T], mut key: impl FnMut(&T) -> K) -> Option<&T> {
+    extreme(list.iter().map(|x| (x, key(x))), Ordering::Greater)
+}
+
+/// the item with the smallest key, the first one wins a tie
+pub fn smallest_by_key<T, K: PartialOrd>(list: &[T], mut key: impl FnMut(&T) -> K) -> Option<&T> {
+    extreme(list.iter().map(|x| (x, key(x))), Ordering::Less)
+}
+
+/// `(item with the smallest key, item with the largest key)`, the key is computed once per item
+pub fn min_max_by_key<T, K: PartialOrd>(list: &[T], mut key: impl FnMut(&T) -> K) -> Option<(&T, &T)> {
+    extremes(list.iter().map(|x| (x, key(x))))
+}
+
+/// orders a reference by [cmp] so that it can be put into a [BinaryHeap]
+struct Ordered<'a, T>(&'a T);
+
+impl<T: PartialOrd> PartialEq for Ordered<'_, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T: PartialOrd> Eq for Ordered<'_, T> {}
+
+impl<T: PartialOrd> PartialOrd for Ordered<'_, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: PartialOrd> Ord for Ordered<'_, T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        cmp(self.0, other.0)
+    }
+}
+
+///
+/// the k largest items in descending order, O(n log k)
+///
+/// 维护一个大小为k的最小堆，堆顶是目前第k大的元素，新元素比堆顶大时替换堆顶
+///
+/// ```
+/// assert_eq!(vec![&9, &7, &7], basic_utils::selection::top_k(&[7, 1, 9, 7, 3], 3));
+/// assert_eq!(vec![&2, &1], basic_utils::selection::top_k(&[1, 2], 5));
+/// ```
+///
+pub fn top_k<T: PartialOrd>(list: &[T], k: usize) -> Vec<&T> {
+    if k == 0 {
+        return Vec::new();
+    }
+    let mut heap = BinaryHeap::with_capacity(k + 1);
+    for item in comparable(list) {
+        heap.push(Reverse(Ordered(item)));
+        if heap.len() > k {
+            heap.pop();
+        }
+    }
+    heap.into_sorted_vec()
+        .into_iter()
+        .map(|Reverse(Ordered(x))| x)
+        .collect()
+}
+
+///
+/// the k-th (start from 0) smallest item, quickselect in average O(n)
+///
+/// ```
+/// let v = [9, 2, 7, 4, 5];
+/// assert_eq!(Some(&2), basic_utils::selection::kth_smallest(&v, 0));
+/// assert_eq!(Some(&5), basic_utils::selection::kth_smallest(&v, 2));
+/// assert_eq!(None, basic_utils::selection::kth_smallest(&v, 5));
+/// ```
+///
+pub fn kth_smallest<T: PartialOrd>(list: &[T], k: usize) -> Option<&T> {
+    let mut items: Vec<&T> = comparable(list).collect();
+    if k >= items.len() {
+        return None;
+    }
+    Some(select(&mut items, k))
+}
+
+/// median of the first, middle and last item of `items[lo..hi]`
+fn pivot_index<T: PartialOrd>(items: &[&T], lo: usize, hi: usize) -> usize {
+    let (a, b, c) = (lo, lo + (hi - lo) / 2, hi - 1);
+    let lt = |i: usize, j: usize| cmp(items[i], items[j]) == Ordering::Less;
+    if lt(a, b) {
+        if lt(b, c) {
+            b
+        } else if lt(a, c) {
+            c
+        } else {
+            a
+        }
+    } else if lt(a, c) {
+        a
+    } else if lt(b, c) {
+        c
+    } else {
+        b
+    }
+}
+
+/// 三路划分的quickselect，返回后`items[k]`就位，`items[k + 1..]`都不小于它
+fn select<'a, T: PartialOrd>(items: &mut [&'a T], k: usize) -> &'a T {
+    let (mut lo, mut hi) = (0, items.len());
+    loop {
+        if hi - lo == 1 {
+            return items[lo];
+        }
+        let pivot = items[pivot_index(items, lo, hi)];
+        // [lo, lt) < pivot, [lt, gt) == pivot, [gt, hi) > pivot
+        let (mut lt, mut i, mut gt) = (lo, lo, hi);
+        while i < gt {
+            match cmp(items[i], pivot) {
+                Ordering::Less => {
+                    items.swap(lt, i);
+                    lt += 1;
+                    i += 1;
+                }
+                Ordering::Greater => {
+                    gt -= 1;
+                    items.swap(i, gt);
+                }
+                Ordering::Equal => i += 1,
+            }
+        }
+        if k < lt {
+            hi = lt;
+        } else if k < gt {
+            return items[k];
+        } else {
+            lo = gt;
+        }
+    }
+}
+
+/// numeric types which can be used in [median] and [percentile]
+pub trait Numeric: Copy + PartialOrd {
+    fn to_f64(self) -> f64;
+}
+
+macro_rules! impl_numeric {
+    ($($t:ty)*) => {
+        $(
+            impl Numeric for $t {
+                #[inline]
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+            }
+        )*
+    };
+}
+
+impl_numeric!(i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize f32 f64);
+
+///
+/// median, the mean of the two middle items for an even length
+///
+/// ```
+/// assert_eq!(Some(3.0), basic_utils::selection::median(&[5, 1, 3]));
+/// assert_eq!(Some(2.5), basic_utils::selection::median(&[4u8, 1, 3, 2]));
+/// assert_eq!(None, basic_utils::selection::median(&[f32::NAN]));
+/// ```
+///
+pub fn median<T: Numeric>(list: &[T]) -> Option<f64> {
+    percentile(list, 50.0)
+}
+
+///
+/// the p-th percentile (`0.0..=100.0`), linearly interpolated between the closest ranks
+///
+/// 排名为`p / 100 * (n - 1)`，和numpy的默认方式一致。p超出范围或者为NaN时返回None
+///
+/// ```
+/// let v = [15, 20, 35, 40, 50];
+/// assert_eq!(Some(15.0), basic_utils::selection::percentile(&v, 0.0));
+/// assert_eq!(Some(29.0), basic_utils::selection::percentile(&v, 40.0));
+/// assert_eq!(Some(50.0), basic_utils::selection::percentile(&v, 100.0));
+/// assert_eq!(None, basic_utils::selection::percentile(&v, 101.0));
+/// ```
+///
+pub fn percentile<T: Numeric>(list: &[T], p: f64) -> Option<f64> {
+    if !(0.0..=100.0).contains(&p) {
+        return None;
+    }
+    let mut items: Vec<&T> = comparable(list).collect();
+    if items.is_empty() {
+        return None;
+    }
+    let rank = p / 100.0 * (items.len() - 1) as f64;
+    let lo = rank.floor() as usize;
+    let low = select(&mut items, lo).to_f64();
+    let frac = rank - lo as f64;
+    if frac == 0.0 {
+        return Some(low);
+    }
+    // select之后比items[lo]大的都在右边，下一个顺序统计量就是右边的最小值
+    let high = smallest(&items[lo + 1..])?.to_f64();
+    Some(low + (high - low) * frac)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// deterministic pseudo random numbers with many duplicates
+    fn samples(n: usize) -> Vec<i32> {
+        let mut x: u32 = 12345;
+        (0..n)
+            .map(|_| {
+                x = x.wrapping_mul(1103515245).wrapping_add(12345);
+                ((x >> 16) % 50) as i32 - 25
+            })
+            .collect()
+    }
+
+    #[test]
+    fn select_matches_sort() {
+        for n in [1, 2, 3, 10, 101, 500] {
+            let v = samples(n);
+            let mut sorted = v.clone();
+            sorted.sort();
+            for (k, expected) in sorted.iter().enumerate() {
+                assert_eq!(Some(expected), kth_smallest(&v, k));
+            }
+            assert_eq!(None, kth_smallest(&v, n));
+            for k in [0, 1, n / 2, n] {
+                let expected: Vec<&i32> = sorted.iter().rev().take(k).collect();
+                assert_eq!(expected, top_k(&v, k));
+            }
+            assert_eq!(Some((&sorted[0], &sorted[n - 1])), min_max(&v));
+        }
+        let sorted: [i32; 0] = [];
+        assert_eq!(None, min_max(&sorted));
+        assert!(top_k(&sorted, 3).is_empty());
+    }
+
+    #[test]
+    fn first_wins_tie() {
+        let v = [(1, 'a'), (3, 'b'), (0, 'c'), (3, 'd'), (0, 'e')];
+        assert_eq!(Some(&(3, 'b')), largest_by_key(&v, |p| p.0));
+        assert_eq!(Some(&(0, 'c')), smallest_by_key(&v, |p| p.0));
+        assert_eq!(Some((&(0, 'c'), &(3, 'b'))), min_max_by_key(&v, |p| p.0));
+        // 所有key相等时，最小和最大都是第一个
+        assert_eq!(Some((&(1, 'a'), &(1, 'a'))), min_max_by_key(&v, |_| 1));
+    }
+
+    #[test]
+    fn nan_is_skipped() {
+        let v = [f64::NAN, 2.0, f64::NAN, -0.5, f64::INFINITY, 1.0];
+        assert_eq!(Some(&f64::INFINITY), largest(&v));
+        assert_eq!(Some(&-0.5), smallest(&v));
+        assert_eq!(Some(&1.0), kth_smallest(&v, 1));
+        assert_eq!(None, kth_smallest(&v, 4));
+        assert_eq!(vec![&f64::INFINITY, &2.0], top_k(&v, 2));
+        assert_eq!(Some(1.5), median(&v));
+
+        let nans = [f32::NAN; 3];
+        assert_eq!(None, largest(&nans));
+        assert_eq!(None, min_max(&nans));
+        assert!(top_k(&nans, 1).is_empty());
+        assert_eq!(None, percentile(&nans, 10.0));
+
+        let points = [(1.0, "a"), (f64::NAN, "b"), (3.0, "c")];
+        assert_eq!(Some((&(1.0, "a"), &(3.0, "c"))), min_max_by_key(&points, |p| p.0));
+    }
+
+    #[test]
+    fn statistics() {
+        assert_eq!(None, median::<i64>(&[]));
+        assert_eq!(Some(7.0), median(&[7u64]));
+        assert_eq!(Some(-1.5), median(&[-1i64, -2]));
+        assert_eq!(None, percentile(&[1, 2], f64::NAN));
+
+        let v = samples(101);
+        let mut sorted = v.clone();
+        sorted.sort();
+        assert_eq!(Some(sorted[50] as f64), median(&v));
+        assert_eq!(Some(sorted[25] as f64), percentile(&v, 25.0));
+        let p = percentile(&v, 12.5).unwrap();
+        assert_eq!(sorted[12] as f64 + (sorted[13] - sorted[12]) as f64 * 0.5, p);
+    }
+}
@@ -0,0 +1,402 @@
+use std::{error::Error, fmt, iter::Rev, vec};
+
+/// error returned when pushing into a full bounded collection, the rejected item is given back
+#[derive(Clone, PartialEq, Eq)]
+pub struct CapacityError<T> {
+    item: T,
+}
+
+impl<T> CapacityError<T> {
+    pub(crate) fn new(item: T) -> Self {
+        CapacityError { item }
+    }
+
+    /// the item which was not pushed
+    pub fn into_inner(self) -> T {
+        self.item
+    }
+}
+
+impl<T> fmt::Debug for CapacityError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CapacityError {{ .. }}")
+    }
+}
+
+impl<T> fmt::Display for CapacityError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "capacity exceeded")
+    }
+}
+
+impl<T> Error for CapacityError<T> {}
+
+///
+/// LIFO stack backed by a Vec, optionally bounded
+///
+/// 迭代顺序和pop顺序一致，从栈顶到栈底
+///
+/// ```
+/// use basic_utils::Stack;
+///
+/// let mut s: Stack<i32> = (1..=3).collect();
+/// assert_eq!(Some(&3), s.peek());
+/// assert_eq!("[3, 2, 1]", s.to_string());
+///
+/// let mut bounded = Stack::bounded(1);
+/// assert!(bounded.try_push("a").is_ok());
+/// assert_eq!("b", bounded.try_push("b").unwrap_err().into_inner());
+/// ```
+///
+pub struct Stack<T> {
+    elements: Vec<T>,
+    bound: Option<usize>,
+}
+
+impl<T> Stack<T> {
+    pub fn new() -> Self {
+        Stack {
+            elements: Vec::new(),
+            bound: None,
+        }
+    }
+
+    /// a stack holding at most `capacity` items
+    pub fn bounded(capacity: usize) -> Self {
+        Stack {
+            elements: Vec::with_capacity(capacity),
+            bound: Some(capacity),
+        }
+    }
+
+    ///
+    /// push an item
+    ///
+    /// # Panics
+    ///
+    /// if a bounded stack is full, use [Stack::try_push] to get the item back instead
+    ///
+    pub fn push(&mut self, item: T) {
+        if self.try_push(item).is_err() {
+            panic!("push into a full stack, capacity {}", self.elements.len());
+        }
+    }
+
+    /// push an item, return it back in the error if the stack is full
+    pub fn try_push(&mut self, item: T) -> Result<(), CapacityError<T>> {
+        if self.is_full() {
+            return Err(CapacityError::new(item));
+        }
+        self.elements.push(item);
+        Ok(())
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        self.elements.pop()
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.elements.last()
+    }
+
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        self.elements.last_mut()
+    }
+
+    /// the first pushed item which is still in the stack
+    pub(crate) fn bottom(&self) -> Option<&T> {
+        self.elements.first()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.bound.is_some_and(|b| self.elements.len() >= b)
+    }
+
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    pub fn capacity_bound(&self) -> Option<usize> {
+        self.bound
+    }
+
+    pub fn clear(&mut self) {
+        self.elements.clear();
+    }
+
+    /// iterate from top to bottom
+    pub fn iter(&self) -> Rev<std::slice::Iter<'_, T>> {
+        self.elements.iter().rev()
+    }
+}
+
+impl<T> Default for Stack<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Stack<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/// top first, `[3, 2, 1]`
+impl<T: fmt::Display> fmt::Display for Stack<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_list(f, self.iter())
+    }
+}
+
+pub(crate) fn fmt_list<T: fmt::Display>(f: &mut fmt::Formatter<'_>, iter: impl Iterator<Item = T>) -> fmt::Result {
+    write!(f, "[")?;
+    for (i, item) in iter.enumerate() {
+        if i > 0 {
+            write!(f, ", ")?;
+        }
+        write!(f, "{}", item)?;
+    }
+    write!(f, "]")
+}
+
+/// the last item of the iterator becomes the top, the stack is unbounded
+impl<T> FromIterator<T> for Stack<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Stack {
+            elements: Vec::from_iter(iter),
+            bound: None,
+        }
+    }
+}
+
+///
+/// # Panics
+///
+/// like [Stack::push], if a bounded stack becomes full, use [Stack::try_push] to keep the rejected items
+///
+impl<T> Extend<T> for Stack<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.push(item);
+        }
+    }
+}
+
+impl<T> IntoIterator for Stack<T> {
+    type Item = T;
+    type IntoIter = Rev<vec::IntoIter<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.elements.into_iter().rev()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Stack<T> {
+    type Item = &'a T;
+    type IntoIter = Rev<std::slice::Iter<'a, T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+///
+/// stack with O(1) `min()`
+///
+/// 额外记录每次出现新最小值时的下标，pop到该下标时最小值回退到上一个
+///
+/// ```
+/// use basic_utils::MinStack;
+///
+/// let mut s = MinStack::new();
+/// for i in [3, 1, 2] {
+///     s.push(i);
+/// }
+/// assert_eq!(Some(&1), s.min());
+/// s.pop();
+/// s.pop();
+/// assert_eq!(Some(&3), s.min());
+/// ```
+///
+pub struct MinStack<T> {
+    stack: Stack<T>,
+    mins: Vec<usize>,
+}
+
+impl<T: Ord> MinStack<T> {
+    pub fn new() -> Self {
+        MinStack {
+            stack: Stack::new(),
+            mins: Vec::new(),
+        }
+    }
+
+    pub fn bounded(capacity: usize) -> Self {
+        MinStack {
+            stack: Stack::bounded(capacity),
+            mins: Vec::new(),
+        }
+    }
+
+    ///
+    /// push an item
+    ///
+    /// # Panics
+    ///
+    /// if a bounded stack is full, use [MinStack::try_push] to get the item back instead
+    ///
+    pub fn push(&mut self, item: T) {
+        if self.try_push(item).is_err() {
+            panic!("push into a full stack, capacity {}", self.stack.len());
+        }
+    }
+
+    /// push an item, return it back in the error if the stack is full
+    pub fn try_push(&mut self, item: T) -> Result<(), CapacityError<T>> {
+        let is_min = self.min().is_none_or(|m| item < *m);
+        self.stack.try_push(item)?;
+        if is_min {
+            self.mins.push(self.stack.len() - 1);
+        }
+        Ok(())
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        let item = self.stack.pop()?;
+        if self.mins.last() == Some(&self.stack.len()) {
+            self.mins.pop();
+        }
+        Some(item)
+    }
+
+    pub fn min(&self) -> Option<&T> {
+        self.mins.last().map(|&i| &self.stack.elements[i])
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.stack.peek()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stack.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.stack.len()
+    }
+
+    pub fn clear(&mut self) {
+        self.stack.clear();
+        self.mins.clear();
+    }
+
+    pub fn iter(&self) -> Rev<std::slice::Iter<'_, T>> {
+        self.stack.iter()
+    }
+}
+
+impl<T: Ord> Default for MinStack<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for MinStack<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.stack, f)
+    }
+}
+
+impl<T: Ord> FromIterator<T> for MinStack<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut s = MinStack::new();
+        s.extend(iter);
+        s
+    }
+}
+
+///
+/// # Panics
+///
+/// like [MinStack::push], if a bounded stack becomes full
+///
+impl<T: Ord> Extend<T> for MinStack<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.push(item);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stack() {
+        let mut s = Stack::new();
+        assert_eq!(None, s.peek());
+        assert_eq!(None, s.capacity_bound());
+        s.extend([1, 2, 3]);
+        *s.peek_mut().unwrap() = 30;
+        assert_eq!(3, s.len());
+        assert_eq!(vec![&30, &2, &1], s.iter().collect::<Vec<_>>());
+        assert_eq!(Some(&1), s.bottom());
+        assert_eq!(Some(30), s.pop());
+        assert_eq!(vec![2, 1], s.into_iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn bounded() {
+        let mut s = Stack::bounded(2);
+        s.extend(["a", "b"]);
+        assert!(s.is_full());
+        assert_eq!(Some(&"b"), s.peek());
+        assert_eq!("capacity exceeded", s.try_push("d").unwrap_err().to_string());
+        s.clear();
+        s.push("e");
+        assert_eq!(Some(&"e"), s.peek());
+        assert_eq!(Some(2), s.capacity_bound());
+    }
+
+    #[test]
+    fn min_stack() {
+        let mut s: MinStack<i32> = [5, 3, 3, 4, 1].into_iter().collect();
+        assert_eq!(Some(&1), s.min());
+        assert_eq!(Some(1), s.pop());
+        assert_eq!(Some(&3), s.min());
+        s.pop();
+        s.pop();
+        assert_eq!(Some(&3), s.min());
+        s.pop();
+        assert_eq!(Some(&5), s.min());
+        assert_eq!("[5]", s.to_string());
+        s.pop();
+        assert_eq!(None, s.min());
+
+        let mut bounded = MinStack::bounded(1);
+        bounded.push(2);
+        assert!(bounded.try_push(1).is_err());
+        assert_eq!(Some(&2), bounded.min());
+    }
+
+    #[test]
+    #[should_panic(expected = "push into a full stack, capacity 1")]
+    fn push_into_full() {
+        let mut s = Stack::bounded(1);
+        s.push(1);
+        s.push(2);
+    }
+
+    #[test]
+    #[should_panic(expected = "push into a full stack, capacity 2")]
+    fn extend_beyond_bound() {
+        let mut s = MinStack::bounded(2);
+        s.extend([3, 2, 1]);
+    }
+}
@@ -0,0 +1,312 @@
+//!
+//! split text into words and sentences
+//!
+//! 默认规则：字母、数字(包括非ASCII字符)以及`_`组成单词，`'` `’` `-`只有在两个单词字符之间时才属于单词，
+//! 比如`don't`, `well-known`。其余字符(空白、标点等)都是分隔符。
+//!
+//! ```
+//! use basic_utils::tokenizer;
+//!
+//! let text = "Hello,\tworld!  Don't panic.";
+//! let words: Vec<_> = tokenizer::words(text).collect();
+//! assert_eq!(vec![(0, "Hello"), (7, "world"), (15, "Don't"), (21, "panic")], words);
+//! assert_eq!(4, tokenizer::word_count(text));
+//! assert_eq!(vec![(0, "Hello,\tworld!"), (15, "Don't panic.")], tokenizer::sentences(text).collect::<Vec<_>>());
+//! ```
+//!
+use std::collections::HashMap;
+
+/// how words are separated
+enum Delimiters {
+    /// unicode aware default rule, see the module document
+    Default,
+    /// split on any of the chars
+    Chars(Vec<char>),
+    /// split where the function returns true
+    Fn(fn(char) -> bool),
+}
+
+///
+/// word splitter with a configurable delimiter set
+///
+/// ```
+/// use basic_utils::tokenizer::Tokenizer;
+///
+/// let csv = Tokenizer::with_delimiters(&[',', ';']);
+/// assert_eq!(Some("b c"), csv.nth_word("a,b c;;d", 1));
+/// assert_eq!(3, csv.word_count("a,b c;;d"));
+///
+/// let digits = Tokenizer::with_delimiter_fn(|c| !c.is_ascii_digit());
+/// assert_eq!(vec![(4, "2024"), (10, "10")], digits.words("date2024 -10").collect::<Vec<_>>());
+/// ```
+///
+pub struct Tokenizer {
+    delimiters: Delimiters,
+}
+
+impl Tokenizer {
+    /// tokenizer with the default unicode aware rule
+    pub fn new() -> Self {
+        Tokenizer {
+            delimiters: Delimiters::Default,
+        }
+    }
+
+    /// only the given chars are delimiters
+    pub fn with_delimiters(chars: &[char]) -> Self {
+        Tokenizer {
+            delimiters: Delimiters::Chars(chars.to_vec()),
+        }
+    }
+
+    /// chars for which `f` returns true are delimiters
+    pub fn with_delimiter_fn(f: fn(char) -> bool) -> Self {
+        Tokenizer {
+            delimiters: Delimiters::Fn(f),
+        }
+    }
+
+    fn is_delimiter(&self, s: &str, i: usize, c: char) -> bool {
+        match &self.delimiters {
+            Delimiters::Default => {
+                if is_word_char(c) {
+                    return false;
+                }
+                if !matches!(c, '\'' | '’' | '-') {
+                    return true;
+                }
+                let prev = s[..i].chars().next_back().is_some_and(is_word_char);
+                let next = s[i + c.len_utf8()..].chars().next().is_some_and(is_word_char);
+                !(prev && next)
+            }
+            Delimiters::Chars(chars) => chars.contains(&c),
+            Delimiters::Fn(f) => f(c),
+        }
+    }
+
+    /// iterate words with their byte offsets
+    pub fn words<'a, 't>(&'t self, s: &'a str) -> Words<'a, 't> {
+        Words {
+            s,
+            pos: 0,
+            tokenizer: self,
+        }
+    }
+
+    /// the n-th word, start from 0
+    pub fn nth_word<'a>(&self, s: &'a str, n: usize) -> Option<&'a str> {
+        self.words(s).nth(n).map(|(_, w)| w)
+    }
+
+    pub fn word_count(&self, s: &str) -> usize {
+        self.words(s).count()
+    }
+
+    /// count words case-insensitively, keys are lowercased
+    pub fn word_frequency(&self, s: &str) -> HashMap<String, usize> {
+        let mut r = HashMap::new();
+        for (_, w) in self.words(s) {
+            *r.entry(w.to_lowercase()).or_insert(0) += 1;
+        }
+        r
+    }
+}
+
+impl Default for Tokenizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// iterator returned by [Tokenizer::words], yield `(byte offset, word)`
+pub struct Words<'a, 't> {
+    s: &'a str,
+    pos: usize,
+    tokenizer: &'t Tokenizer,
+}
+
+impl<'a> Iterator for Words<'a, '_> {
+    type Item = (usize, &'a str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (s, t) = (self.s, self.tokenizer);
+        let start = self.pos
+            + s[self.pos..]
+                .char_indices()
+                .find(|&(i, c)| !t.is_delimiter(s, self.pos + i, c))?
+                .0;
+        let end = s[start..]
+            .char_indices()
+            .find(|&(i, c)| t.is_delimiter(s, start + i, c))
+            .map_or(s.len(), |(i, _)| start + i);
+        self.pos = end;
+        Some((start, &s[start..end]))
+    }
+}
+
+/// iterate words with the default rule
+pub fn words(s: &str) -> Words<'_, 'static> {
+    static DEFAULT: Tokenizer = Tokenizer {
+        delimiters: Delimiters::Default,
+    };
+    DEFAULT.words(s)
+}
+
+/// the n-th word with the default rule, start from 0
+///
+/// ```
+/// assert_eq!(Some("world"), basic_utils::tokenizer::nth_word("hello\u{3000}world", 1));
+/// ```
+pub fn nth_word(s: &str, n: usize) -> Option<&str> {
+    words(s).nth(n).map(|(_, w)| w)
+}
+
+pub fn word_count(s: &str) -> usize {
+    words(s).count()
+}
+
+///
+/// count words case-insensitively with the default rule
+///
+/// ```
+/// let freq = basic_utils::tokenizer::word_frequency("The cat and the hat.");
+/// assert_eq!(Some(&2), freq.get("the"));
+/// assert_eq!(Some(&1), freq.get("hat"));
+/// ```
+///
+pub fn word_frequency(s: &str) -> HashMap<String, usize> {
+    Tokenizer::new().word_frequency(s)
+}
+
+/// iterator returned by [sentences], yield `(byte offset, sentence)`
+pub struct Sentences<'a> {
+    s: &'a str,
+    pos: usize,
+}
+
+impl<'a> Iterator for Sentences<'a> {
+    type Item = (usize, &'a str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let rest = &self.s[self.pos..];
+        let start = self.pos + rest.len() - rest.trim_start().len();
+        if start == self.s.len() {
+            self.pos = start;
+            return None;
+        }
+        let end = sentence_end(self.s, start);
+        self.pos = end;
+        Some((start, self.s[start..end].trim_end()))
+    }
+}
+
+/// 句子结束于终止符(可以连续多个，后面可以跟引号或右括号)
+/// `.` `!` `?`之后必须是空白或者文本结尾，这样`3.14`和`example.com`不会被切开；全角终止符不需要空白
+fn sentence_end(s: &str, start: usize) -> usize {
+    let mut chars = s[start..].char_indices().map(|(i, c)| (start + i, c)).peekable();
+    while let Some((i, c)) = chars.next() {
+        if !matches!(c, '.' | '!' | '?' | '。' | '！' | '？' | '…') {
+            continue;
+        }
+        let mut end = i + c.len_utf8();
+        while let Some(&(j, n)) = chars.peek() {
+            if !matches!(
+                n,
+                '.' | '!' | '?' | '。' | '！' | '？' | '…' | '"' | '\'' | '”' | '’' | ')' | '）' | '」'
+            ) {
+                break;
+            }
+            end = j + n.len_utf8();
+            chars.next();
+        }
+        let full_width = matches!(c, '。' | '！' | '？');
+        match chars.peek() {
+            None => return end,
+            Some(&(_, n)) if n.is_whitespace() || full_width => return end,
+            _ => {}
+        }
+    }
+    s.len()
+}
+
+///
+/// split text into sentences, leading and trailing whitespace of each sentence is trimmed
+///
+/// ```
+/// let s: Vec<_> = basic_utils::tokenizer::sentences("Pi is 3.14! Really?  Yes.").map(|(_, s)| s).collect();
+/// assert_eq!(vec!["Pi is 3.14!", "Really?", "Yes."], s);
+///
+/// let s: Vec<_> = basic_utils::tokenizer::sentences("你好。再见！").map(|(_, s)| s).collect();
+/// assert_eq!(vec!["你好。", "再见！"], s);
+/// ```
+///
+pub fn sentences(s: &str) -> Sentences<'_> {
+    Sentences { s, pos: 0 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_rule() {
+        let text = "  well-known -- rock'n'roll, 'quoted' snake_case\nЗдравствуй\u{a0}мир 2.5 ";
+        let words: Vec<&str> = words(text).map(|(_, w)| w).collect();
+        assert_eq!(
+            vec![
+                "well-known",
+                "rock'n'roll",
+                "quoted",
+                "snake_case",
+                "Здравствуй",
+                "мир",
+                "2",
+                "5"
+            ],
+            words
+        );
+        for (i, w) in super::words(text) {
+            assert_eq!(w, &text[i..i + w.len()]);
+        }
+        assert_eq!(None, nth_word(" ,.; ", 0));
+        assert_eq!(0, word_count(""));
+    }
+
+    #[test]
+    fn custom_delimiters() {
+        let t = Tokenizer::with_delimiters(&['|']);
+        assert_eq!(vec![(1, "a b"), (6, "c")], t.words("|a b||c|").collect::<Vec<_>>());
+        let t = Tokenizer::with_delimiter_fn(char::is_whitespace);
+        assert_eq!(Some("world!"), t.nth_word("hello,\tworld!", 1));
+        assert_eq!(2, Tokenizer::default().word_count("hello,\tworld!"));
+    }
+
+    #[test]
+    fn frequency() {
+        let freq = word_frequency("Rust rust RUST, Straße STRASSE straße");
+        assert_eq!(Some(&3), freq.get("rust"));
+        assert_eq!(Some(&2), freq.get("straße"));
+        assert_eq!(Some(&1), freq.get("strasse"));
+        assert_eq!(3, freq.len());
+    }
+
+    #[test]
+    fn sentence() {
+        let text = "He said \"stop!\" Then left... ok? visit example.com now";
+        let r: Vec<_> = sentences(text).collect();
+        assert_eq!(
+            vec![
+                (0, "He said \"stop!\""),
+                (16, "Then left..."),
+                (29, "ok?"),
+                (33, "visit example.com now")
+            ],
+            r
+        );
+        assert_eq!(0, sentences("  \n ").count());
+    }
+}
@@ -0,0 +1,24 @@
+use std::{error::Error, fmt};
+
+/// error returned by the `build()` of a `#[derive(Builder)]` builder
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BuilderError {
+    /// a required field was never set
+    MissingField {
+        /// name of the struct being built
+        target: &'static str,
+        field: &'static str,
+    },
+}
+
+impl fmt::Display for BuilderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuilderError::MissingField { target, field } => {
+                write!(f, "field `{}` of `{}` must be set before build()", field, target)
+            }
+        }
+    }
+}
+
+impl Error for BuilderError {}
@@ -0,0 +1,419 @@
+//!
+//! 紧凑的二进制编码，`#[derive(Encode, Decode)]`生成的代码调用这里的实现
+//!
+//! - `u8`、`i8`写一个字节，`bool`写0或1
+//! - 其他整数写varint(LEB128)，有符号整数先做zigzag，-1写成1个字节
+//! - `f32`、`f64`按小端写固定长度
+//! - `String`、`Vec<T>`先写varint长度，再写内容
+//! - `Option<T>`先写0(None)或1(Some)
+//! - enum先写variant的下标(varint)，再写字段
+//!
+//! [to_bytes]在开头写入版本号，字段上的`#[encode(since = N)]`只在版本号>=N时编码，
+//! 解码更老版本的数据时使用`Default::default()`，这样新代码可以读旧数据
+//!
+use std::{error::Error, fmt};
+
+/// error returned when decoding truncated or malformed input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecodeError {
+    /// the input ended in the middle of a value
+    Truncated {
+        /// bytes still needed by the value being decoded
+        needed: usize,
+    },
+    /// a varint longer than 10 bytes or larger than `u64::MAX`
+    VarintOverflow,
+    /// an integer that does not fit into the target type, `value` is the decoded number, not the zigzag varint
+    OutOfRange {
+        ty: &'static str,
+        value: i128,
+    },
+    /// a byte other than 0 or 1 for a `bool` or an `Option`
+    InvalidFlag {
+        ty: &'static str,
+        value: u8,
+    },
+    InvalidUtf8,
+    /// an enum tag without a matching variant
+    UnknownTag {
+        target: &'static str,
+        tag: u64,
+    },
+    /// bytes left over after the value
+    TrailingBytes(usize),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::Truncated { needed } => write!(f, "input truncated, {} more byte(s) needed", needed),
+            DecodeError::VarintOverflow => f.write_str("varint does not fit into 64 bits"),
+            DecodeError::OutOfRange { ty, value } => write!(f, "value {} is out of range for `{}`", value, ty),
+            DecodeError::InvalidFlag { ty, value } => write!(f, "invalid byte {} for `{}`, expected 0 or 1", value, ty),
+            DecodeError::InvalidUtf8 => f.write_str("string is not valid UTF-8"),
+            DecodeError::UnknownTag { target, tag } => write!(f, "unknown tag {} for `{}`", tag, target),
+            DecodeError::TrailingBytes(n) => write!(f, "{} trailing byte(s) after the value", n),
+        }
+    }
+}
+
+impl Error for DecodeError {}
+
+/// output buffer of [Encode]
+#[derive(Debug, Clone, Default)]
+pub struct Encoder {
+    buf: Vec<u8>,
+    version: u32,
+}
+
+impl Encoder {
+    pub fn new(version: u32) -> Encoder {
+        Encoder {
+            buf: Vec::new(),
+            version,
+        }
+    }
+
+    /// the version being written, fields with `#[encode(since = N)]` are only written when it is at least N
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    pub fn write_byte(&mut self, byte: u8) {
+        self.buf.push(byte);
+    }
+
+    pub fn write_bytes(&mut self, bytes: &[u8]) {
+        self.buf.extend_from_slice(bytes);
+    }
+
+    /// 7 bits per byte, low bits first, the high bit marks that more bytes follow
+    pub fn write_varint(&mut self, mut value: u64) {
+        while value >= 0x80 {
+            self.buf.push(value as u8 | 0x80);
+            value >>= 7;
+        }
+        self.buf.push(value as u8);
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.buf
+    }
+}
+
+/// input of [Decode]
+#[derive(Debug, Clone)]
+pub struct Decoder<'a> {
+    input: &'a [u8],
+    version: u32,
+}
+
+impl<'a> Decoder<'a> {
+    pub fn new(input: &'a [u8], version: u32) -> Decoder<'a> {
+        Decoder { input, version }
+    }
+
+    /// the version of the input, fields with `#[encode(since = N)]` are only read when it is at least N
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// number of bytes not decoded yet
+    pub fn remaining(&self) -> usize {
+        self.input.len()
+    }
+
+    pub fn read_byte(&mut self) -> Result<u8, DecodeError> {
+        Ok(self.read_bytes(1)?[0])
+    }
+
+    pub fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], DecodeError> {
+        if len > self.input.len() {
+            return Err(DecodeError::Truncated {
+                needed: len - self.input.len(),
+            });
+        }
+        let (bytes, rest) = self.input.split_at(len);
+        self.input = rest;
+        Ok(bytes)
+    }
+
+    pub fn read_varint(&mut self) -> Result<u64, DecodeError> {
+        let mut value = 0u64;
+        for i in 0..10 {
+            let byte = self.read_byte()?;
+            // 第10个字节只剩1位可用
+            if i == 9 && byte > 1 {
+                return Err(DecodeError::VarintOverflow);
+            }
+            value |= u64::from(byte & 0x7f) << (7 * i);
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(DecodeError::VarintOverflow)
+    }
+
+    /// a varint length of a collection
+    pub fn read_len(&mut self) -> Result<usize, DecodeError> {
+        let len = self.read_varint()?;
+        usize::try_from(len).map_err(|_| DecodeError::OutOfRange {
+            ty: "usize",
+            value: i128::from(len),
+        })
+    }
+}
+
+/// a value that can be written by an [Encoder], derive it with `#[derive(Encode)]`
+pub trait Encode {
+    fn encode(&self, encoder: &mut Encoder);
+}
+
+/// a value that can be read from a [Decoder], derive it with `#[derive(Decode)]`
+pub trait Decode: Sized {
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, DecodeError>;
+}
+
+/// `value` encoded as `version`, the version is written first as a varint
+///
+/// ```
+/// use hello_macro::{from_bytes, to_bytes};
+///
+/// let bytes = to_bytes(&(300u32, String::from("hi"), Some(-1i64)), 1);
+/// assert_eq!(vec![1, 0xac, 0x02, 2, b'h', b'i', 1, 1], bytes);
+/// assert_eq!(Ok((300u32, String::from("hi"), Some(-1i64))), from_bytes(&bytes));
+/// ```
+pub fn to_bytes<T: Encode + ?Sized>(value: &T, version: u32) -> Vec<u8> {
+    let mut encoder = Encoder::new(version);
+    encoder.write_varint(u64::from(version));
+    value.encode(&mut encoder);
+    encoder.into_bytes()
+}
+
+/// a value written by [to_bytes], every byte of `bytes` must be used
+pub fn from_bytes<T: Decode>(bytes: &[u8]) -> Result<T, DecodeError> {
+    let mut decoder = Decoder::new(bytes, 0);
+    decoder.version = u32::decode(&mut decoder)?;
+    let value = T::decode(&mut decoder)?;
+    match decoder.remaining() {
+        0 => Ok(value),
+        n => Err(DecodeError::TrailingBytes(n)),
+    }
+}
+
+macro_rules! impl_unsigned {
+    ($($t:ty)*) => {$(
+        impl Encode for $t {
+            fn encode(&self, encoder: &mut Encoder) {
+                encoder.write_varint(*self as u64);
+            }
+        }
+
+        impl Decode for $t {
+            fn decode(decoder: &mut Decoder<'_>) -> Result<Self, DecodeError> {
+                let value = decoder.read_varint()?;
+                <$t>::try_from(value).map_err(|_| DecodeError::OutOfRange {
+                    ty: stringify!($t),
+                    value: i128::from(value),
+                })
+            }
+        }
+    )*};
+}
+
+impl_unsigned! { u16 u32 u64 usize }
+
+macro_rules! impl_signed {
+    ($($t:ty)*) => {$(
+        impl Encode for $t {
+            fn encode(&self, encoder: &mut Encoder) {
+                // zigzag: 0, -1, 1, -2 ... => 0, 1, 2, 3 ...
+                let value = *self as i64;
+                encoder.write_varint(((value << 1) ^ (value >> 63)) as u64);
+            }
+        }
+
+        impl Decode for $t {
+            fn decode(decoder: &mut Decoder<'_>) -> Result<Self, DecodeError> {
+                let zigzag = decoder.read_varint()?;
+                let value = (zigzag >> 1) as i64 ^ -((zigzag & 1) as i64);
+                <$t>::try_from(value).map_err(|_| DecodeError::OutOfRange {
+                    ty: stringify!($t),
+                    value: i128::from(value),
+                })
+            }
+        }
+    )*};
+}
+
+impl_signed! { i16 i32 i64 isize }
+
+impl Encode for u8 {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.write_byte(*self);
+    }
+}
+
+impl Decode for u8 {
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, DecodeError> {
+        decoder.read_byte()
+    }
+}
+
+impl Encode for i8 {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.write_byte(*self as u8);
+    }
+}
+
+impl Decode for i8 {
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, DecodeError> {
+        Ok(decoder.read_byte()? as i8)
+    }
+}
+
+impl Encode for bool {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.write_byte(u8::from(*self));
+    }
+}
+
+impl Decode for bool {
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, DecodeError> {
+        match decoder.read_byte()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            value => Err(DecodeError::InvalidFlag { ty: "bool", value }),
+        }
+    }
+}
+
+macro_rules! impl_float {
+    ($($t:ty)*) => {$(
+        impl Encode for $t {
+            fn encode(&self, encoder: &mut Encoder) {
+                encoder.write_bytes(&self.to_le_bytes());
+            }
+        }
+
+        impl Decode for $t {
+            fn decode(decoder: &mut Decoder<'_>) -> Result<Self, DecodeError> {
+                let bytes = decoder.read_bytes(std::mem::size_of::<$t>())?;
+                Ok(<$t>::from_le_bytes(bytes.try_into().expect("read exactly size_of bytes")))
+            }
+        }
+    )*};
+}
+
+impl_float! { f32 f64 }
+
+impl Encode for str {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.write_varint(self.len() as u64);
+        encoder.write_bytes(self.as_bytes());
+    }
+}
+
+impl Encode for String {
+    fn encode(&self, encoder: &mut Encoder) {
+        self.as_str().encode(encoder);
+    }
+}
+
+impl Decode for String {
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, DecodeError> {
+        let len = decoder.read_len()?;
+        let bytes = decoder.read_bytes(len)?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| DecodeError::InvalidUtf8)
+    }
+}
+
+impl<T: Encode> Encode for [T] {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.write_varint(self.len() as u64);
+        for item in self {
+            item.encode(encoder);
+        }
+    }
+}
+
+impl<T: Encode> Encode for Vec<T> {
+    fn encode(&self, encoder: &mut Encoder) {
+        self.as_slice().encode(encoder);
+    }
+}
+
+impl<T: Decode> Decode for Vec<T> {
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, DecodeError> {
+        let len = decoder.read_len()?;
+        // 长度来自输入，不可信，预分配不超过剩余的字节数，避免一个错误的长度分配大量内存
+        let mut items = Vec::with_capacity(len.min(decoder.remaining()));
+        for _ in 0..len {
+            items.push(T::decode(decoder)?);
+        }
+        Ok(items)
+    }
+}
+
+impl<T: Encode> Encode for Option<T> {
+    fn encode(&self, encoder: &mut Encoder) {
+        match self {
+            None => encoder.write_byte(0),
+            Some(value) => {
+                encoder.write_byte(1);
+                value.encode(encoder);
+            }
+        }
+    }
+}
+
+impl<T: Decode> Decode for Option<T> {
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, DecodeError> {
+        match decoder.read_byte()? {
+            0 => Ok(None),
+            1 => Ok(Some(T::decode(decoder)?)),
+            value => Err(DecodeError::InvalidFlag { ty: "Option", value }),
+        }
+    }
+}
+
+impl<T: Encode + ?Sized> Encode for &T {
+    fn encode(&self, encoder: &mut Encoder) {
+        (**self).encode(encoder);
+    }
+}
+
+impl<T: Encode + ?Sized> Encode for Box<T> {
+    fn encode(&self, encoder: &mut Encoder) {
+        (**self).encode(encoder);
+    }
+}
+
+impl<T: Decode> Decode for Box<T> {
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, DecodeError> {
+        Ok(Box::new(T::decode(decoder)?))
+    }
+}
+
+macro_rules! impl_tuple {
+    ($($name:ident)*) => {
+        impl<$($name: Encode),*> Encode for ($($name,)*) {
+            #[allow(non_snake_case)]
+            fn encode(&self, encoder: &mut Encoder) {
+                let ($($name,)*) = self;
+                $($name.encode(encoder);)*
+            }
+        }
+
+        impl<$($name: Decode),*> Decode for ($($name,)*) {
+            fn decode(decoder: &mut Decoder<'_>) -> Result<Self, DecodeError> {
+                Ok(($($name::decode(decoder)?,)*))
+            }
+        }
+    };
+}
+
+impl_tuple! { A }
+impl_tuple! { A B }
+impl_tuple! { A B C }
+impl_tuple! { A B C D }
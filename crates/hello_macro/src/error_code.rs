@@ -0,0 +1,54 @@
+use std::fmt::Write;
+
+/// one row of the `CODES` table generated by `#[derive(ErrorCode)]`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ErrorCodeEntry {
+    pub code: u32,
+    /// `Enum::Variant`
+    pub name: &'static str,
+}
+
+/// panics if two entries of `tables` share a code, call it in a `const` so the check runs at compile time
+///
+/// ```
+/// use hello_macro::{assert_unique_codes, ErrorCodeEntry};
+///
+/// const USER: &[ErrorCodeEntry] = &[ErrorCodeEntry { code: 20000, name: "UserError::NotFound" }];
+/// const ORDER: &[ErrorCodeEntry] = &[ErrorCodeEntry { code: 30000, name: "OrderError::Closed" }];
+/// const _: () = assert_unique_codes(&[USER, ORDER]);
+/// ```
+pub const fn assert_unique_codes(tables: &[&[ErrorCodeEntry]]) {
+    // const fn里不能用迭代器，只能用while
+    let mut i = 0;
+    while i < tables.len() {
+        let mut j = 0;
+        while j < tables[i].len() {
+            let code = tables[i][j].code;
+            let mut k = i;
+            let mut l = j + 1;
+            while k < tables.len() {
+                while l < tables[k].len() {
+                    if tables[k][l].code == code {
+                        panic!("duplicate error code across error enums");
+                    }
+                    l += 1;
+                }
+                k += 1;
+                l = 0;
+            }
+            j += 1;
+        }
+        i += 1;
+    }
+}
+
+/// every entry of `tables` sorted by code, one `code name` per line
+pub fn code_table(tables: &[&[ErrorCodeEntry]]) -> String {
+    let mut entries: Vec<_> = tables.iter().flat_map(|t| t.iter()).collect();
+    entries.sort_by_key(|e| e.code);
+    let mut table = String::new();
+    for entry in entries {
+        writeln!(table, "{:<8}{}", entry.code, entry.name).expect("writing to a String never fails");
+    }
+    table
+}
@@ -0,0 +1,53 @@
+use std::{error::Error, fmt};
+
+/// error returned by the `TryFrom<repr>` of a `#[derive(IntEnum)]` enum
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntEnumError<R: 'static> {
+    /// name of the enum
+    pub target: &'static str,
+    pub value: R,
+    /// discriminants of every variant in declaration order
+    pub valid: &'static [R],
+}
+
+impl<R: fmt::Display> fmt::Display for IntEnumError<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid value {} for `{}`, expected one of ",
+            self.value, self.target
+        )?;
+        write_list(f, self.valid)
+    }
+}
+
+impl<R: fmt::Debug + fmt::Display> Error for IntEnumError<R> {}
+
+/// error returned by the `FromStr` of a `#[derive(IntEnum)]` enum
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseEnumError {
+    /// name of the enum
+    pub target: &'static str,
+    pub name: String,
+    /// names of every variant in declaration order
+    pub valid: &'static [&'static str],
+}
+
+impl fmt::Display for ParseEnumError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown `{}` variant {:?}, expected one of ", self.target, self.name)?;
+        write_list(f, self.valid)
+    }
+}
+
+impl Error for ParseEnumError {}
+
+fn write_list<T: fmt::Display>(f: &mut fmt::Formatter<'_>, items: &[T]) -> fmt::Result {
+    for (i, item) in items.iter().enumerate() {
+        if i > 0 {
+            f.write_str(", ")?;
+        }
+        write!(f, "{}", item)?;
+    }
+    Ok(())
+}
@@ -1,5 +1,27 @@
+pub use builder::BuilderError;
+pub use codec::{from_bytes, to_bytes, Decode, DecodeError, Decoder, Encode, Encoder};
+pub use error_code::{assert_unique_codes, code_table, ErrorCodeEntry};
+pub use int_enum::{IntEnumError, ParseEnumError};
+#[cfg(feature = "sqlx")]
+pub use sql::BindParams;
+pub use sql::{SqlQuery, StatementKind};
+
+mod builder;
+mod codec;
+mod error_code;
+mod int_enum;
+mod sql;
+
 ///
 /// 过程宏
 pub trait HelloMacro {
+    /// print the greeting of the type
     fn hello_macro();
+
+    /// the greeting of this value, an enum greets differently for each variant
+    ///
+    /// 默认使用类型名，derive生成的实现会覆盖它
+    fn greeting(&self) -> String {
+        format!("Hello, Macro! My name is {}!", std::any::type_name::<Self>())
+    }
 }
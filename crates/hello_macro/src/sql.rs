@@ -0,0 +1,130 @@
+use std::fmt;
+
+#[cfg(feature = "sqlx")]
+use sqlx::{database::HasArguments, query::Query, Database, Encode, Type};
+
+/// the statement parsed by `sql!`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StatementKind {
+    Select,
+    Insert,
+    Update,
+    Delete,
+}
+
+///
+/// a statement checked by `sql!` at compile time, with `N` `?` placeholders
+///
+/// 参数个数是类型的一部分，启用`sqlx` feature后`bind`只接受N个元素的tuple，少传或者多传参数都会编译失败
+///
+/// ```
+/// use hello_macro::{SqlQuery, StatementKind};
+/// use hello_macro_derive::sql;
+///
+/// const FIND_USER: SqlQuery<2> = sql!(select id, name from user where id = ? or name like ?);
+/// assert_eq!("SELECT id, name FROM user WHERE id = ? OR name LIKE ?", FIND_USER.sql());
+/// assert_eq!(StatementKind::Select, FIND_USER.kind());
+/// ```
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SqlQuery<const N: usize> {
+    kind: StatementKind,
+    sql: &'static str,
+}
+
+impl<const N: usize> SqlQuery<N> {
+    /// number of `?` placeholders
+    pub const PARAMS: usize = N;
+
+    /// only called by `sql!`, which has already checked `sql` and counted its placeholders
+    #[doc(hidden)]
+    pub const fn __new(kind: StatementKind, sql: &'static str) -> Self {
+        SqlQuery { kind, sql }
+    }
+
+    pub const fn kind(&self) -> StatementKind {
+        self.kind
+    }
+
+    /// the normalized statement text, keywords are uppercase
+    pub const fn sql(&self) -> &'static str {
+        self.sql
+    }
+}
+
+#[cfg(feature = "sqlx")]
+impl<const N: usize> SqlQuery<N> {
+    ///
+    /// a sqlx query of this statement, with `params` bound to the placeholders in order
+    ///
+    /// ```
+    /// use hello_macro::SqlQuery;
+    /// use hello_macro_derive::sql;
+    /// use sqlx::{Any, Execute};
+    ///
+    /// const FIND_USER: SqlQuery<2> = sql!(select id, name from user where id = ? or name like ?);
+    /// let query = FIND_USER.bind::<Any, _>((1i64, String::from("a%")));
+    /// assert_eq!(FIND_USER.sql(), query.sql());
+    /// ```
+    ///
+    pub fn bind<'q, DB, P>(&self, params: P) -> Query<'q, DB, <DB as HasArguments<'q>>::Arguments>
+    where
+        DB: Database,
+        P: BindParams<'q, DB, N>,
+    {
+        params.bind_to(sqlx::query(self.sql))
+    }
+}
+
+impl<const N: usize> fmt::Display for SqlQuery<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.sql)
+    }
+}
+
+/// a tuple of `N` values that can be bound to a sqlx query
+#[cfg(feature = "sqlx")]
+pub trait BindParams<'q, DB: Database, const N: usize> {
+    fn bind_to(
+        self,
+        query: Query<'q, DB, <DB as HasArguments<'q>>::Arguments>,
+    ) -> Query<'q, DB, <DB as HasArguments<'q>>::Arguments>;
+}
+
+// 为0到12个元素的tuple实现BindParams，N就是tuple的长度
+#[cfg(feature = "sqlx")]
+macro_rules! impl_bind_params {
+    ($($n:literal => ($($t:ident)*);)*) => {$(
+        impl<'q, DB: Database, $($t,)*> BindParams<'q, DB, $n> for ($($t,)*)
+        where
+            $($t: 'q + Send + Encode<'q, DB> + Type<DB>,)*
+        {
+            #[allow(non_snake_case, unused_mut)]
+            fn bind_to(
+                self,
+                mut query: Query<'q, DB, <DB as HasArguments<'q>>::Arguments>,
+            ) -> Query<'q, DB, <DB as HasArguments<'q>>::Arguments> {
+                let ($($t,)*) = self;
+                $(query = query.bind($t);)*
+                query
+            }
+        }
+    )*};
+}
+
+#[cfg(feature = "sqlx")]
+impl_bind_params! {
+    0 => ();
+    1 => (A);
+    2 => (A B);
+    3 => (A B C);
+    4 => (A B C D);
+    5 => (A B C D E);
+    6 => (A B C D E F);
+    7 => (A B C D E F G);
+    8 => (A B C D E F G H);
+    9 => (A B C D E F G H I);
+    10 => (A B C D E F G H I J);
+    11 => (A B C D E F G H I J K);
+    12 => (A B C D E F G H I J K L);
+}
@@ -0,0 +1,53 @@
+//!
+//! 过程宏的编译测试，pass目录下的文件必须编译通过并运行成功，fail目录下的文件必须编译失败，
+//! 错误信息和同名的.stderr一致。修改了错误信息后用`TRYBUILD=overwrite cargo test`更新.stderr
+//!
+
+#[test]
+fn hello_macro() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/ui/hello/pass/*.rs");
+    t.compile_fail("tests/ui/hello/fail/*.rs");
+}
+
+#[test]
+fn builder() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/ui/builder/pass/*.rs");
+    t.compile_fail("tests/ui/builder/fail/*.rs");
+}
+
+#[test]
+fn int_enum() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/ui/int_enum/pass/*.rs");
+    t.compile_fail("tests/ui/int_enum/fail/*.rs");
+}
+
+#[test]
+fn error_code() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/ui/error_code/pass/*.rs");
+    t.compile_fail("tests/ui/error_code/fail/*.rs");
+}
+
+#[test]
+fn codec() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/ui/codec/pass/*.rs");
+    t.compile_fail("tests/ui/codec/fail/*.rs");
+}
+
+#[test]
+fn sql() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/ui/sql/pass/*.rs");
+    t.compile_fail("tests/ui/sql/fail/*.rs");
+}
+
+#[test]
+fn route() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/ui/route/pass/*.rs");
+    t.compile_fail("tests/ui/route/fail/*.rs");
+}
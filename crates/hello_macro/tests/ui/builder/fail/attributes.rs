@@ -0,0 +1,17 @@
+use hello_macro_derive::Builder;
+
+#[derive(Builder)]
+struct Command {
+    #[builder(intoo)]
+    program: String,
+    #[builder(each = "build")]
+    args: Vec<String>,
+    #[builder(each = "an arg")]
+    more_args: Vec<String>,
+    #[builder(each = "dir")]
+    dir: Option<String>,
+    #[builder(default, default = 1)]
+    retries: u8,
+}
+
+fn main() {}
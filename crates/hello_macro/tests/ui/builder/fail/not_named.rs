@@ -0,0 +1,12 @@
+use hello_macro_derive::Builder;
+
+#[derive(Builder)]
+struct Grades(char, f32);
+
+#[derive(Builder)]
+enum Level {
+    Low,
+    High,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use hello_macro_derive::Builder;
+
+#[derive(Builder)]
+struct Student {
+    name: String,
+}
+
+fn main() {
+    // 没有#[builder(into)]时setter只接受字段的类型
+    let _ = Student::builder().name("libai").build();
+}
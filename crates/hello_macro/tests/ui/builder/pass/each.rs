@@ -0,0 +1,39 @@
+use std::collections::{BTreeMap, HashSet};
+
+use hello_macro_derive::Builder;
+
+#[derive(Builder)]
+struct Command {
+    program: String,
+    #[builder(each = "arg")]
+    args: Vec<String>,
+    #[builder(each = "env")]
+    env: BTreeMap<String, String>,
+    #[builder(each = "flag", default = HashSet::from(['v']))]
+    flags: HashSet<char>,
+}
+
+fn main() {
+    let command = Command::builder()
+        .program(String::from("cargo"))
+        .arg(String::from("build"))
+        .arg(String::from("--release"))
+        .env((String::from("RUST_LOG"), String::from("debug")))
+        .build()
+        .unwrap();
+    assert_eq!(vec!["build", "--release"], command.args);
+    assert_eq!(Some("debug"), command.env.get("RUST_LOG").map(String::as_str));
+    assert_eq!(HashSet::from(['v']), command.flags);
+
+    // 整体设置会替换之前逐个添加的元素
+    let command = Command::builder()
+        .program(String::from("ls"))
+        .arg(String::from("-l"))
+        .args(vec![String::from("-a")])
+        .flag('q')
+        .build()
+        .unwrap();
+    assert_eq!(vec!["-a"], command.args);
+    assert!(command.env.is_empty());
+    assert_eq!(HashSet::from(['q']), command.flags);
+}
@@ -0,0 +1,43 @@
+use hello_macro::BuilderError;
+use hello_macro_derive::Builder;
+
+#[derive(Debug, PartialEq, Builder)]
+pub struct Student {
+    #[builder(into)]
+    name: String,
+    level: u8,
+    #[builder(default)]
+    remote: bool,
+    #[builder(default = 60.0)]
+    score: f32,
+    #[builder(into)]
+    nickname: Option<String>,
+}
+
+fn main() {
+    let student = Student::builder().name("libai").level(1).nickname("taibai").build();
+    assert_eq!(
+        Ok(Student {
+            name: String::from("libai"),
+            level: 1,
+            remote: false,
+            score: 60.0,
+            nickname: Some(String::from("taibai")),
+        }),
+        student
+    );
+
+    let student = Student::builder().level(3).name("dufu").remote(true).score(99.5).build().unwrap();
+    assert!(student.remote);
+    assert_eq!(None, student.nickname);
+
+    let err = Student::builder().level(1).build().unwrap_err();
+    assert_eq!(
+        BuilderError::MissingField {
+            target: "Student",
+            field: "name"
+        },
+        err
+    );
+    assert_eq!("field `name` of `Student` must be set before build()", err.to_string());
+}
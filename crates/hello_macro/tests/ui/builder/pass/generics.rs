@@ -0,0 +1,30 @@
+use std::fmt::Debug;
+
+use hello_macro_derive::Builder;
+
+#[derive(Debug, Builder)]
+pub struct Page<'a, T, const N: usize>
+where
+    T: Debug + Clone,
+{
+    title: &'a str,
+    #[builder(each = "item")]
+    items: Vec<T>,
+    #[builder(default = [0; N])]
+    marks: [u8; N],
+    next: Option<Box<Page<'a, T, N>>>,
+}
+
+fn main() {
+    let last = Page::<u32, 2>::builder().title("2").item(3).build().unwrap();
+    let page = Page::builder()
+        .title("1")
+        .item(1)
+        .item(2)
+        .next(Box::new(last))
+        .build()
+        .unwrap();
+    assert_eq!(vec![1, 2], page.items);
+    assert_eq!([0, 0], page.marks);
+    assert_eq!(Some("2"), page.next.as_ref().map(|p| p.title));
+}
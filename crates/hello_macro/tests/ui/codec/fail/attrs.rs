@@ -0,0 +1,20 @@
+use hello_macro_derive::{Decode, Encode};
+
+#[derive(Encode, Decode)]
+#[encode(since = 2)]
+struct Login {
+    #[encode(skip, since = 2)]
+    user: String,
+    #[encode(sice = 2)]
+    device: Option<String>,
+    #[encode(since = "2")]
+    token: String,
+}
+
+#[derive(Encode)]
+enum Role {
+    #[encode(skip)]
+    Guest,
+}
+
+fn main() {}
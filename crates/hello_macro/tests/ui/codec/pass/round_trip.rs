@@ -0,0 +1,120 @@
+use hello_macro::{from_bytes, to_bytes, DecodeError};
+use hello_macro_derive::{Decode, Encode};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Login {
+    id: u64,
+    user: String,
+    roles: Vec<Role>,
+    // 版本2增加的字段，旧数据解码为None
+    #[encode(since = 2)]
+    device: Option<String>,
+    // 只在本地使用，不编码
+    #[encode(skip)]
+    cached: bool,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+enum Role {
+    Guest,
+    User(i32),
+    Admin { level: u8, note: Option<String> },
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Pair<T>(T, T);
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Unit;
+
+fn login() -> Login {
+    Login {
+        id: 300,
+        user: String::from("libai"),
+        roles: vec![Role::Guest, Role::User(-2), Role::Admin { level: 9, note: None }],
+        device: Some(String::from("pad")),
+        cached: true,
+    }
+}
+
+fn main() {
+    let bytes = to_bytes(&login(), 2);
+    assert_eq!(
+        vec![
+            2, // version
+            0xac, 0x02, // id = 300
+            5, b'l', b'i', b'b', b'a', b'i', // user
+            3, 0, 1, 3, 2, 9, 0, // roles: Guest, User(-2), Admin { level: 9, note: None }
+            1, 3, b'p', b'a', b'd', // device
+        ],
+        bytes
+    );
+    let decoded: Login = from_bytes(&bytes).unwrap();
+    assert_eq!(
+        Login {
+            cached: false,
+            ..login()
+        },
+        decoded
+    );
+
+    // 版本1不编码device，解码时为None
+    let v1 = to_bytes(&login(), 1);
+    assert_eq!(bytes.len() - 5, v1.len());
+    let decoded: Login = from_bytes(&v1).unwrap();
+    assert_eq!(None, decoded.device);
+
+    assert_eq!(Ok(Pair(-1i16, 7)), from_bytes(&to_bytes(&Pair(-1i16, 7), 1)));
+    assert_eq!(Ok(Unit), from_bytes(&to_bytes(&Unit, 1)));
+    assert_eq!(Ok(vec![Unit, Unit]), from_bytes(&[1, 2]));
+
+    // 截断的输入
+    assert_eq!(
+        Err(DecodeError::Truncated { needed: 3 }),
+        from_bytes::<Login>(&bytes[..6])
+    );
+    assert_eq!(
+        Err(DecodeError::Truncated { needed: 1 }),
+        from_bytes::<Login>(&bytes[..2])
+    );
+    // 格式错误的输入
+    assert_eq!(
+        Err(DecodeError::UnknownTag { target: "Role", tag: 7 }),
+        from_bytes::<Role>(&[1, 7])
+    );
+    assert_eq!(
+        Err(DecodeError::InvalidFlag { ty: "Option", value: 2 }),
+        from_bytes::<Option<u8>>(&[1, 2, 0])
+    );
+    assert_eq!(Err(DecodeError::InvalidUtf8), from_bytes::<String>(&[1, 2, 0xff, 0xfe]));
+    assert_eq!(
+        Err(DecodeError::OutOfRange {
+            ty: "u16",
+            value: 70000
+        }),
+        from_bytes::<u16>(&[1, 0xf0, 0xa2, 0x04])
+    );
+    // 有符号整数报告解码后的值，而不是zigzag编码
+    let err = from_bytes::<i16>(&to_bytes(&-40000i32, 1)).unwrap_err();
+    assert_eq!(
+        DecodeError::OutOfRange {
+            ty: "i16",
+            value: -40000
+        },
+        err
+    );
+    assert_eq!("value -40000 is out of range for `i16`", err.to_string());
+    assert_eq!(
+        Err(DecodeError::VarintOverflow),
+        from_bytes::<u64>(&[1, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x02])
+    );
+    assert_eq!(Err(DecodeError::TrailingBytes(1)), from_bytes::<Unit>(&[1, 0]));
+    assert_eq!(
+        Err(DecodeError::Truncated { needed: 1 }),
+        from_bytes::<Vec<u32>>(&[1, 0xff, 0xff, 0xff, 0x0f])
+    );
+    assert_eq!(
+        "unknown tag 7 for `Role`",
+        from_bytes::<Role>(&[1, 7]).unwrap_err().to_string()
+    );
+}
@@ -0,0 +1,18 @@
+use hello_macro_derive::ErrorCode;
+
+#[derive(Debug, ErrorCode)]
+#[code(1)]
+enum AppError {
+    #[code(10000)]
+    Io(#[from] std::io::Error),
+    #[code(10000)]
+    Again,
+    Missing,
+    #[code(10001, msg = "oops")]
+    Typo,
+    #[code(10002)]
+    #[code(10003)]
+    Twice,
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use hello_macro_derive::ErrorCode;
+
+#[derive(Debug, ErrorCode)]
+enum AppError {
+    #[code(10000)]
+    Io(#[from] std::io::Error),
+    #[code(10001)]
+    Other(#[from] std::io::Error),
+    #[code(10002)]
+    Pair(#[from] String, u32),
+    #[code(10003)]
+    Named(#[from(String)] String),
+}
+
+fn main() {}
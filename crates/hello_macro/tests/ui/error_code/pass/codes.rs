@@ -0,0 +1,62 @@
+use std::{error::Error, io, num::ParseIntError};
+
+use hello_macro::{assert_unique_codes, code_table, ErrorCodeEntry};
+use hello_macro_derive::ErrorCode;
+
+#[derive(Debug, ErrorCode)]
+enum AppError {
+    #[code(10000)]
+    Io(#[from] io::Error),
+    #[code(10001, message = "not a number")]
+    Parse {
+        #[from]
+        source: ParseIntError,
+    },
+    #[code(10003, message = "user {id} not found in {table}")]
+    UserNotFound { id: u32, table: &'static str },
+    #[code(10002)]
+    Unknown,
+}
+
+#[derive(Debug, ErrorCode)]
+enum OrderError {
+    #[code(20000)]
+    Closed(u64),
+}
+
+// 两个enum的错误码没有冲突，有冲突时这里编译失败
+const _: () = assert_unique_codes(&[AppError::CODES, OrderError::CODES]);
+
+fn parse(s: &str) -> Result<i32, AppError> {
+    Ok(s.parse()?)
+}
+
+fn main() {
+    let err = parse("a1").unwrap_err();
+    assert_eq!(10001, err.code());
+    assert_eq!("code: 10001, message: not a number", err.to_string());
+    assert_eq!("invalid digit found in string", err.source().unwrap().to_string());
+
+    let err = AppError::from(io::Error::new(io::ErrorKind::NotFound, "no such file"));
+    assert_eq!(10000, err.code());
+    assert_eq!("code: 10000, message: no such file", err.to_string());
+
+    let err = AppError::UserNotFound { id: 7, table: "user" };
+    assert_eq!("code: 10003, message: user 7 not found in user", err.to_string());
+    assert!(err.source().is_none());
+    assert_eq!("code: 10002, message: Unknown", AppError::Unknown.to_string());
+    assert_eq!("code: 20000, message: 42", OrderError::Closed(42).to_string());
+
+    assert_eq!(
+        ErrorCodeEntry {
+            code: 10003,
+            name: "AppError::UserNotFound"
+        },
+        AppError::CODES[2]
+    );
+    assert_eq!(
+        "10000   AppError::Io\n10001   AppError::Parse\n10002   AppError::Unknown\n\
+         10003   AppError::UserNotFound\n20000   OrderError::Closed\n",
+        code_table(&[OrderError::CODES, AppError::CODES])
+    );
+}
@@ -0,0 +1,19 @@
+use hello_macro_derive::ErrorCode;
+
+// 字段名和生成代码中的局部变量同名
+#[derive(Debug, ErrorCode)]
+enum FieldError {
+    #[code(1, message = "formatter field {f}")]
+    Formatter { f: String },
+    #[code(2, message = "{value} and {self_}")]
+    Value { value: u32, self_: u32 },
+    #[code(3)]
+    Single { f: &'static str },
+}
+
+fn main() {
+    let err = FieldError::Formatter { f: String::from("x") };
+    assert_eq!("code: 1, message: formatter field x", err.to_string());
+    assert_eq!("code: 2, message: 1 and 2", FieldError::Value { value: 1, self_: 2 }.to_string());
+    assert_eq!("code: 3, message: y", FieldError::Single { f: "y" }.to_string());
+}
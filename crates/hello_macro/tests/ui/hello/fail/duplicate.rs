@@ -0,0 +1,8 @@
+use hello_macro_derive::HelloMacro;
+
+#[derive(HelloMacro)]
+#[hello(name = "Pancakes")]
+#[hello(greeting = "Hi", name = "Waffles")]
+struct Pancakes;
+
+fn main() {}
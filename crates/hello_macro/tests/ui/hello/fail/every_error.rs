@@ -0,0 +1,14 @@
+use hello_macro_derive::HelloMacro;
+
+// 所有的错误都会报告，而不是只报第一个
+#[derive(HelloMacro)]
+enum Breakfast {
+    #[hello(nmae = "Toast")]
+    Toast(#[hello(name = "slice")] u8),
+    Egg {
+        #[hello(greeting = "Hi")]
+        count: u32,
+    },
+}
+
+fn main() {}
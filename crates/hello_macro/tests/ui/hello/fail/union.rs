@@ -0,0 +1,9 @@
+use hello_macro_derive::HelloMacro;
+
+#[derive(HelloMacro)]
+union Number {
+    int: u32,
+    float: f32,
+}
+
+fn main() {}
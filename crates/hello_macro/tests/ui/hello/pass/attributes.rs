@@ -0,0 +1,19 @@
+use hello_macro::HelloMacro;
+use hello_macro_derive::HelloMacro;
+
+#[derive(HelloMacro)]
+#[hello(name = "Fluffy Pancakes", greeting = "Good morning")]
+struct Pancakes;
+
+#[derive(HelloMacro)]
+#[hello(greeting = "Hi")]
+#[hello(name = "Waffle")]
+struct Waffles {
+    count: u32,
+}
+
+fn main() {
+    Pancakes::hello_macro();
+    assert_eq!("Good morning, Macro! My name is Fluffy Pancakes!", Pancakes.greeting());
+    assert_eq!("Hi, Macro! My name is Waffle!", Waffles { count: 2 }.greeting());
+}
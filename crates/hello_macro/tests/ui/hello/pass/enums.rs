@@ -0,0 +1,23 @@
+use hello_macro::HelloMacro;
+use hello_macro_derive::HelloMacro;
+
+#[derive(HelloMacro)]
+#[hello(greeting = "Howdy")]
+enum Breakfast<T> {
+    Pancakes,
+    #[hello(name = "French Toast", greeting = "Bonjour")]
+    Toast(u8),
+    #[hello(name = "Eggs")]
+    Egg { count: T },
+}
+
+#[derive(HelloMacro)]
+enum Never {}
+
+fn main() {
+    Breakfast::<u8>::hello_macro();
+    Never::hello_macro();
+    assert_eq!("Howdy, Macro! My name is Pancakes!", Breakfast::<u8>::Pancakes.greeting());
+    assert_eq!("Bonjour, Macro! My name is French Toast!", Breakfast::<u8>::Toast(2).greeting());
+    assert_eq!("Howdy, Macro! My name is Eggs!", Breakfast::Egg { count: 3 }.greeting());
+}
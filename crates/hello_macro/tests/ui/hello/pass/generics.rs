@@ -0,0 +1,25 @@
+use std::fmt::Debug;
+
+use hello_macro::HelloMacro;
+use hello_macro_derive::HelloMacro;
+
+#[derive(HelloMacro)]
+struct Wrapper<T>(T);
+
+#[derive(HelloMacro)]
+struct Bounded<'a, T: Debug + ?Sized, const N: usize>
+where
+    T: PartialEq,
+{
+    items: [&'a T; N],
+}
+
+fn main() {
+    Wrapper::<String>::hello_macro();
+    assert_eq!("Hello, Macro! My name is Wrapper!", Wrapper(1u8).greeting());
+
+    let x = 1;
+    let bounded = Bounded { items: [&x, &x] };
+    assert_eq!("Hello, Macro! My name is Bounded!", bounded.greeting());
+    assert_eq!(2, bounded.items.len());
+}
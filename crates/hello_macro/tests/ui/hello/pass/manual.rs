@@ -0,0 +1,18 @@
+use hello_macro::HelloMacro;
+
+// 手写的实现只需要hello_macro
+struct Pancakes;
+
+impl HelloMacro for Pancakes {
+    fn hello_macro() {
+        println!("Hello, Macro! My name is Pancakes!");
+    }
+}
+
+fn main() {
+    Pancakes::hello_macro();
+    // 默认的greeting使用完整的类型名
+    let greeting = Pancakes.greeting();
+    assert!(greeting.starts_with("Hello, Macro! My name is "), "{}", greeting);
+    assert!(greeting.ends_with("::Pancakes!"), "{}", greeting);
+}
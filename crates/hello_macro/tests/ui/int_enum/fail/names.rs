@@ -0,0 +1,16 @@
+use hello_macro_derive::IntEnum;
+
+#[derive(IntEnum)]
+#[repr(u8)]
+#[int_enum(name = "channel")]
+enum Channel {
+    #[int_enum(name = "Email")]
+    Phone,
+    Email,
+    #[int_enum(nmae = "fax")]
+    Fax,
+    #[int_enum(name = "")]
+    Letter,
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use hello_macro_derive::IntEnum;
+
+#[derive(IntEnum)]
+enum Channel {
+    Phone,
+    Email,
+}
+
+#[derive(IntEnum)]
+#[repr(C)]
+enum Level {
+    Low,
+    High,
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use hello_macro_derive::IntEnum;
+
+#[derive(IntEnum)]
+#[repr(u8)]
+enum Message {
+    Quit,
+    Move { x: i32, y: i32 },
+    Write(String),
+}
+
+#[derive(IntEnum)]
+struct Channel(u8);
+
+fn main() {}
@@ -0,0 +1,56 @@
+use hello_macro::{IntEnumError, ParseEnumError};
+use hello_macro_derive::IntEnum;
+
+#[derive(Debug, Clone, Copy, PartialEq, IntEnum)]
+#[repr(u8)]
+enum Channel {
+    Phone = 1,
+    #[int_enum(name = "e-mail")]
+    Email = 2,
+    Letter = 10,
+    // 隐式的判别值在前一个的基础上加1
+    Fax,
+}
+
+#[derive(Debug, PartialEq, IntEnum)]
+#[repr(i64)]
+enum Level {
+    Low = -1,
+    High = 1 << 40,
+}
+
+fn main() {
+    assert_eq!(Ok(Channel::Email), Channel::try_from(2));
+    assert_eq!(Ok(Channel::Fax), 11u8.try_into());
+    assert_eq!(
+        Err(IntEnumError {
+            target: "Channel",
+            value: 3,
+            valid: &[1, 2, 10, 11]
+        }),
+        Channel::try_from(3)
+    );
+    assert_eq!(
+        "invalid value 3 for `Channel`, expected one of 1, 2, 10, 11",
+        Channel::try_from(3).unwrap_err().to_string()
+    );
+    assert_eq!(10u8, Channel::Letter.into());
+
+    assert_eq!("e-mail", Channel::Email.to_string());
+    assert_eq!("Phone ", format!("{:<6}", Channel::Phone));
+    assert_eq!(Ok(Channel::Email), "e-mail".parse());
+    let err: ParseEnumError = "Email".parse::<Channel>().unwrap_err();
+    assert_eq!(
+        "unknown `Channel` variant \"Email\", expected one of Phone, e-mail, Letter, Fax",
+        err.to_string()
+    );
+
+    assert_eq!([Channel::Phone, Channel::Email, Channel::Letter, Channel::Fax], Channel::ALL);
+    for channel in Channel::ALL {
+        assert_eq!(Ok(channel), Channel::try_from(u8::from(channel)));
+        assert_eq!(Ok(channel), channel.to_string().parse());
+    }
+
+    assert_eq!(Ok(Level::High), Level::try_from(1i64 << 40));
+    assert_eq!(-1i64, Level::Low.into());
+}
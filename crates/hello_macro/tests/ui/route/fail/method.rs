@@ -0,0 +1,9 @@
+use hello_macro_derive::route;
+
+#[route(get, "/")]
+async fn lowercase() {}
+
+#[route(GTE, "/")]
+async fn unknown() {}
+
+fn main() {}
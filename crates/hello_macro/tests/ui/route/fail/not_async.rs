@@ -0,0 +1,8 @@
+use hello_macro_derive::route;
+
+#[route(GET, "/")]
+fn index() -> &'static str {
+    "index"
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use axum::extract::Path;
+use hello_macro_derive::route;
+
+#[route(GET, "/users/:id")]
+async fn misspelled(Path(user_id): Path<u32>) -> String {
+    user_id.to_string()
+}
+
+#[route(GET, "/users/:id")]
+async fn never_extracted() {}
+
+#[route(GET, "/users/:id/posts/:post_id")]
+async fn count(Path((id,)): Path<(u32,)>) -> String {
+    id.to_string()
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use hello_macro_derive::route;
+
+#[route(GET, "users")]
+async fn no_slash() {}
+
+#[route(GET, "/users//posts")]
+async fn empty_segment() {}
+
+#[route(GET, "/assets/*path/raw")]
+async fn wildcard() {}
+
+#[route(GET, "/users/:1d")]
+async fn bad_name() {}
+
+fn main() {}
@@ -0,0 +1,54 @@
+use axum::body::{to_bytes, Body};
+use axum::extract::{Path, State};
+use axum::http::{Method, Request, StatusCode};
+use axum::Router;
+use hello_macro_derive::{route, routes};
+use tower::ServiceExt;
+
+#[route(GET, "/")]
+async fn index() -> &'static str {
+    "index"
+}
+
+#[route(GET, "/users/:id/posts/:post_id")]
+async fn post(Path((id, post_id)): Path<(u32, u32)>) -> String {
+    format!("user {} post {}", id, post_id)
+}
+
+#[route(POST, "/counter/:step")]
+async fn count(State(start): State<u32>, Path(step): Path<u32>) -> String {
+    (start + step).to_string()
+}
+
+mod assets {
+    use super::*;
+
+    #[route(GET, "/assets/*path")]
+    pub async fn file(Path(path): Path<String>) -> String {
+        path
+    }
+}
+
+async fn call(router: Router, method: Method, uri: &str) -> (StatusCode, String) {
+    let request = Request::builder().method(method).uri(uri).body(Body::empty()).unwrap();
+    let response = router.oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, String::from_utf8(body.to_vec()).unwrap())
+}
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() {
+    let router: Router = routes![index, post, count, assets::file].with_state(1);
+
+    let ok = |body: &str| (StatusCode::OK, body.to_string());
+    assert_eq!(ok("index"), call(router.clone(), Method::GET, "/").await);
+    assert_eq!(ok("user 7 post 42"), call(router.clone(), Method::GET, "/users/7/posts/42").await);
+    assert_eq!(ok("3"), call(router.clone(), Method::POST, "/counter/2").await);
+    assert_eq!(ok("css/site.css"), call(router.clone(), Method::GET, "/assets/css/site.css").await);
+
+    let (status, _) = call(router.clone(), Method::POST, "/").await;
+    assert_eq!(StatusCode::METHOD_NOT_ALLOWED, status);
+    let (status, _) = call(router, Method::GET, "/users/7").await;
+    assert_eq!(StatusCode::NOT_FOUND, status);
+}
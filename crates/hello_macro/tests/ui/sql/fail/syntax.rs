@@ -0,0 +1,10 @@
+use hello_macro_derive::sql;
+
+fn main() {
+    let _ = sql!(select * form user);
+    let _ = sql!(selct * from user);
+    let _ = sql!(delete from user where id == 1);
+    let _ = sql!(delete from user;);
+    let _ = sql!(delete from user where id =);
+    let _ = sql!(select * from user where id in [1]);
+}
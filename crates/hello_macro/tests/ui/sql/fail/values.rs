@@ -0,0 +1,6 @@
+use hello_macro_derive::sql;
+
+fn main() {
+    let _ = sql!(insert into user (id, name) values (?));
+    let _ = sql!(select * from user limit 1u8);
+}
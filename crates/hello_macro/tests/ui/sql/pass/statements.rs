@@ -0,0 +1,24 @@
+use hello_macro::{SqlQuery, StatementKind};
+use hello_macro_derive::sql;
+
+// 占位符的个数是类型的一部分
+const FIND: SqlQuery<2> = sql!(select id, name from user where id = ? or name like ?);
+const INSERT: SqlQuery<2> = sql!(insert into user (id, name) values (?, ?));
+
+fn main() {
+    assert_eq!("SELECT id, name FROM user WHERE id = ? OR name LIKE ?", FIND.sql());
+    assert_eq!(StatementKind::Insert, INSERT.kind());
+    let update = sql!(update user set name = ? where id <> 1 and age >= ?);
+    assert_eq!("UPDATE user SET name = ? WHERE id <> 1 AND age >= ?", update.to_string());
+    assert_eq!(0, sql!(delete from user where id is null).bind_count());
+}
+
+trait BindCount {
+    fn bind_count(&self) -> usize;
+}
+
+impl<const N: usize> BindCount for SqlQuery<N> {
+    fn bind_count(&self) -> usize {
+        N
+    }
+}
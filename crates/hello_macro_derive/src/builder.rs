@@ -0,0 +1,208 @@
+//!
+//! `#[derive(Builder)]`
+//!
+//! 为结构体`Xxx`生成`XxxBuilder`，每个字段一个同名的setter，setter消耗并返回builder，可以链式调用。
+//! `Option<T>`字段的setter接受`T`，不设置时为None；其他字段必须设置，否则`build()`返回`BuilderError`
+//! `builder()`、setter和`build()`的可见性和结构体相同，需要校验的字段(比如密码的哈希)不要用Builder构造
+//!
+//! 字段上的`#[builder(...)]`：
+//! - `default`或`default = expr`：不设置时使用`Default::default()`或者`expr`
+//! - `into`：setter接受`impl Into<T>`
+//! - `each = "item"`：为集合生成逐个添加元素的setter，集合需要实现`Default`和`Extend`，不设置时为空集合
+//!
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{
+    Attribute, Data, DeriveInput, Error, Expr, Fields, GenericArgument, Ident, LitStr, PathArguments, Result, Token,
+    Type,
+};
+
+/// values of the `#[builder(...)]` attributes on one field
+#[derive(Default)]
+struct FieldAttr {
+    /// `Some(None)` for a bare `default`
+    default: Option<Option<Expr>>,
+    into: bool,
+    each: Option<Ident>,
+}
+
+impl FieldAttr {
+    fn parse(attrs: &[Attribute], errors: &mut Vec<Error>) -> FieldAttr {
+        let mut field = FieldAttr::default();
+        for attr in attrs.iter().filter(|a| a.path().is_ident("builder")) {
+            let result = attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("default") {
+                    if field.default.is_some() {
+                        return Err(meta.error("duplicate `default`"));
+                    }
+                    let expr = match meta.input.peek(Token![=]) {
+                        true => Some(meta.value()?.parse()?),
+                        false => None,
+                    };
+                    field.default = Some(expr);
+                } else if meta.path.is_ident("into") {
+                    if field.into {
+                        return Err(meta.error("duplicate `into`"));
+                    }
+                    field.into = true;
+                } else if meta.path.is_ident("each") {
+                    if field.each.is_some() {
+                        return Err(meta.error("duplicate `each`"));
+                    }
+                    let name: LitStr = meta.value()?.parse()?;
+                    let ident: Ident = name.parse().map_err(|_| {
+                        Error::new(name.span(), format!("`{}` is not a valid method name", name.value()))
+                    })?;
+                    if ident == "build" {
+                        return Err(Error::new(name.span(), "`build` is reserved for the build method"));
+                    }
+                    field.each = Some(ident);
+                } else {
+                    return Err(meta.error("unknown `builder` attribute, expected `default`, `into` or `each`"));
+                }
+                Ok(())
+            });
+            errors.extend(result.err());
+        }
+        field
+    }
+}
+
+/// `T` if `ty` is `Option<T>`
+fn option_inner(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) if segment.ident == "Option" && args.args.len() == 1 => {
+            match args.args.first() {
+                Some(GenericArgument::Type(inner)) => Some(inner),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+pub(crate) fn impl_builder(ast: &DeriveInput) -> Result<TokenStream> {
+    let name = &ast.ident;
+    let fields = match &ast.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new_spanned(
+                    &data.fields,
+                    "`Builder` needs a struct with named fields",
+                ))
+            }
+        },
+        Data::Enum(data) => {
+            return Err(Error::new(
+                data.enum_token.span,
+                "`Builder` can only be derived for structs",
+            ))
+        }
+        Data::Union(data) => {
+            return Err(Error::new(
+                data.union_token.span,
+                "`Builder` can only be derived for structs",
+            ))
+        }
+    };
+
+    let vis = &ast.vis;
+    let mut errors = Vec::new();
+    let mut storage = Vec::new();
+    let mut setters = Vec::new();
+    let mut values = Vec::new();
+    for field in fields {
+        let ident = field.ident.as_ref().expect("named field");
+        let ty = &field.ty;
+        let attr = FieldAttr::parse(&field.attrs, &mut errors);
+        let option = option_inner(ty);
+        if let (Some(each), Some(_)) = (&attr.each, option) {
+            errors.push(Error::new(each.span(), "`each` needs a collection, not an `Option`"));
+            continue;
+        }
+        let value_ty = option.unwrap_or(ty);
+        storage.push(quote!(#ident: ::core::option::Option<#value_ty>));
+
+        // each和字段同名时只生成逐个添加的setter
+        if attr.each.as_ref() != Some(ident) {
+            let (param_ty, convert) = match attr.into {
+                true => (quote!(impl ::core::convert::Into<#value_ty>), quote!(.into())),
+                false => (quote!(#value_ty), quote!()),
+            };
+            setters.push(quote! {
+                #vis fn #ident(mut self, #ident: #param_ty) -> Self {
+                    self.#ident = ::core::option::Option::Some(#ident #convert);
+                    self
+                }
+            });
+        }
+        if let Some(each) = &attr.each {
+            setters.push(quote! {
+                #vis fn #each(mut self, #each: <#ty as ::core::iter::IntoIterator>::Item) -> Self {
+                    let collection = self.#ident.get_or_insert_with(::core::default::Default::default);
+                    ::core::iter::Extend::extend(collection, ::core::iter::once(#each));
+                    self
+                }
+            });
+        }
+
+        let (target, field_name) = (name.to_string(), ident.to_string());
+        let fallback = match (attr.default, &attr.each, option) {
+            (Some(Some(expr)), _, _) => quote!(#expr),
+            (Some(None), _, _) | (None, Some(_), _) => quote!(::core::default::Default::default()),
+            (None, None, Some(_)) => quote!(::core::option::Option::None),
+            (None, None, None) => quote! {
+                return ::core::result::Result::Err(::hello_macro::BuilderError::MissingField {
+                    target: #target,
+                    field: #field_name,
+                })
+            },
+        };
+        let found = match option {
+            Some(_) => quote!(::core::option::Option::Some(value)),
+            None => quote!(value),
+        };
+        values.push(quote! {
+            #ident: match self.#ident {
+                ::core::option::Option::Some(value) => #found,
+                ::core::option::Option::None => #fallback,
+            }
+        });
+    }
+    crate::combine_errors(errors)?;
+
+    let builder = format_ident!("{}Builder", name);
+    let generics = &ast.generics;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let idents = fields.iter().map(|f| &f.ident);
+    let builder_doc = format!("builder of [`{}`], created by [`{}::builder`]", name, name);
+    Ok(quote! {
+        #[doc = #builder_doc]
+        #vis struct #builder #generics #where_clause {
+            #(#storage,)*
+        }
+
+        impl #impl_generics #name #ty_generics #where_clause {
+            #vis fn builder() -> #builder #ty_generics {
+                #builder {
+                    #(#idents: ::core::option::Option::None,)*
+                }
+            }
+        }
+
+        impl #impl_generics #builder #ty_generics #where_clause {
+            #(#setters)*
+
+            #vis fn build(self) -> ::core::result::Result<#name #ty_generics, ::hello_macro::BuilderError> {
+                ::core::result::Result::Ok(#name {
+                    #(#values,)*
+                })
+            }
+        }
+    })
+}
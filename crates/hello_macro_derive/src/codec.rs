@@ -0,0 +1,248 @@
+//!
+//! `#[derive(Encode)]`和`#[derive(Decode)]`
+//!
+//! struct按声明顺序编码每个字段，enum先编码variant的下标(从0开始)再编码字段，编码格式见`hello_macro::Encode`
+//!
+//! 字段上的`#[encode(...)]`：
+//! - `skip`：不编码，解码时使用`Default::default()`
+//! - `since = N`：只有版本号>=N时才编码，解码更老版本的数据时使用`Default::default()`
+//!
+//! 泛型参数会加上`Encode`或`Decode`约束
+//!
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{parse_quote, Attribute, Data, DeriveInput, Error, Fields, Generics, Ident, LitInt, Path, Result};
+
+/// how a field takes part in the encoding
+enum FieldMode {
+    Always,
+    Skip,
+    Since(u32),
+}
+
+fn field_mode(attrs: &[Attribute], errors: &mut Vec<Error>) -> FieldMode {
+    let mut mode = FieldMode::Always;
+    for attr in attrs.iter().filter(|a| a.path().is_ident("encode")) {
+        let result = attr.parse_nested_meta(|meta| {
+            if !matches!(mode, FieldMode::Always) {
+                return Err(meta.error("a field can only have one of `skip` and `since`"));
+            }
+            if meta.path.is_ident("skip") {
+                mode = FieldMode::Skip;
+            } else if meta.path.is_ident("since") {
+                let version: LitInt = meta.value()?.parse()?;
+                mode = FieldMode::Since(version.base10_parse()?);
+            } else {
+                return Err(meta.error("unknown `encode` attribute, expected `skip` or `since`"));
+            }
+            Ok(())
+        });
+        errors.extend(result.err());
+    }
+    mode
+}
+
+/// `#[encode]` is only meaningful on fields
+fn reject_attrs(attrs: &[Attribute], target: &str, errors: &mut Vec<Error>) {
+    for attr in attrs.iter().filter(|a| a.path().is_ident("encode")) {
+        errors.push(Error::new_spanned(
+            attr,
+            format!("`#[encode]` goes on fields, not on {}", target),
+        ));
+    }
+}
+
+/// one field of a struct or a variant, bound to `binding` when matching on `self`
+struct CodecField {
+    member: TokenStream,
+    binding: Ident,
+    mode: FieldMode,
+}
+
+fn codec_fields(fields: &Fields, errors: &mut Vec<Error>) -> Vec<CodecField> {
+    fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            let mode = field_mode(&field.attrs, errors);
+            let (member, binding) = match &field.ident {
+                Some(ident) => (quote!(#ident), format_ident!("__{}", ident)),
+                None => {
+                    let index = syn::Index::from(i);
+                    (quote!(#index), format_ident!("__{}", i))
+                }
+            };
+            CodecField { member, binding, mode }
+        })
+        .collect()
+}
+
+/// every type parameter bounded by `bound`
+fn add_bound(generics: &Generics, bound: Path) -> Generics {
+    let mut generics = generics.clone();
+    for param in generics.type_params_mut() {
+        param.bounds.push(parse_quote!(#bound));
+    }
+    generics
+}
+
+/// one item for a struct, one item for each variant of an enum
+struct Item<'a> {
+    /// `None` for a struct
+    variant: Option<&'a Ident>,
+    fields: &'a Fields,
+    codec: Vec<CodecField>,
+}
+
+fn items<'a>(ast: &'a DeriveInput, derive: &str) -> Result<Vec<Item<'a>>> {
+    let mut errors = Vec::new();
+    reject_attrs(&ast.attrs, "the type", &mut errors);
+    let items = match &ast.data {
+        Data::Struct(data) => vec![Item {
+            variant: None,
+            fields: &data.fields,
+            codec: codec_fields(&data.fields, &mut errors),
+        }],
+        Data::Enum(data) => data
+            .variants
+            .iter()
+            .map(|variant| {
+                reject_attrs(&variant.attrs, "variants", &mut errors);
+                Item {
+                    variant: Some(&variant.ident),
+                    fields: &variant.fields,
+                    codec: codec_fields(&variant.fields, &mut errors),
+                }
+            })
+            .collect(),
+        Data::Union(data) => {
+            return Err(Error::new(
+                data.union_token.span,
+                format!("`{}` cannot be derived for unions", derive),
+            ))
+        }
+    };
+    crate::combine_errors(errors)?;
+    Ok(items)
+}
+
+/// `{ a: __a, b: __b }` or `{ 0: __0, 1: __1 }` binding the fields of a struct or a variant
+fn pattern(fields: &Fields, codec: &[CodecField]) -> TokenStream {
+    let members = codec.iter().map(|f| &f.member);
+    let bindings = codec.iter().map(|f| &f.binding);
+    match fields {
+        Fields::Unit => quote!(),
+        _ => quote!({ #(#members: #bindings),* }),
+    }
+}
+
+pub(crate) fn impl_encode(ast: &DeriveInput) -> Result<TokenStream> {
+    let name = &ast.ident;
+    let items = items(ast, "Encode")?;
+    let arms = items.iter().enumerate().map(|(tag, item)| {
+        let writes = item.codec.iter().map(|field| {
+            let binding = &field.binding;
+            match field.mode {
+                FieldMode::Always => quote!(::hello_macro::Encode::encode(#binding, encoder);),
+                FieldMode::Skip => quote!(),
+                FieldMode::Since(version) => quote! {
+                    if encoder.version() >= #version {
+                        ::hello_macro::Encode::encode(#binding, encoder);
+                    }
+                },
+            }
+        });
+        let pattern = pattern(item.fields, &item.codec);
+        match item.variant {
+            Some(ident) => {
+                let tag = tag as u64;
+                quote! {
+                    #name::#ident #pattern => {
+                        encoder.write_varint(#tag);
+                        #(#writes)*
+                    }
+                }
+            }
+            None => quote!(#name #pattern => { #(#writes)* }),
+        }
+    });
+    let arms: Vec<_> = arms.collect();
+    // 没有variant的enum不能对引用做空的match
+    let body = match arms.is_empty() {
+        true => quote!(match *self {}),
+        false => quote!(match self { #(#arms)* }),
+    };
+
+    let generics = add_bound(&ast.generics, parse_quote!(::hello_macro::Encode));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::hello_macro::Encode for #name #ty_generics #where_clause {
+            #[allow(unused_variables)]
+            fn encode(&self, encoder: &mut ::hello_macro::Encoder) {
+                #body
+            }
+        }
+    })
+}
+
+pub(crate) fn impl_decode(ast: &DeriveInput) -> Result<TokenStream> {
+    let name = &ast.ident;
+    let items = items(ast, "Decode")?;
+    let construct = |path: TokenStream, item: &Item| {
+        let reads = item.codec.iter().map(|field| {
+            let member = &field.member;
+            let value = match field.mode {
+                FieldMode::Always => quote!(::hello_macro::Decode::decode(decoder)?),
+                FieldMode::Skip => quote!(::core::default::Default::default()),
+                FieldMode::Since(version) => quote! {
+                    if decoder.version() >= #version {
+                        ::hello_macro::Decode::decode(decoder)?
+                    } else {
+                        ::core::default::Default::default()
+                    }
+                },
+            };
+            quote!(#member: #value)
+        });
+        match item.fields {
+            Fields::Unit => path,
+            _ => quote!(#path { #(#reads),* }),
+        }
+    };
+    let body = match &ast.data {
+        Data::Struct(_) => {
+            let value = construct(quote!(#name), &items[0]);
+            quote!(::core::result::Result::Ok(#value))
+        }
+        _ => {
+            let arms = items.iter().enumerate().map(|(tag, item)| {
+                let ident = item.variant.expect("enum variant");
+                let value = construct(quote!(#name::#ident), item);
+                let tag = tag as u64;
+                quote!(#tag => ::core::result::Result::Ok(#value),)
+            });
+            let target = name.to_string();
+            quote! {
+                match decoder.read_varint()? {
+                    #(#arms)*
+                    tag => ::core::result::Result::Err(::hello_macro::DecodeError::UnknownTag {
+                        target: #target,
+                        tag,
+                    }),
+                }
+            }
+        }
+    };
+
+    let generics = add_bound(&ast.generics, parse_quote!(::hello_macro::Decode));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::hello_macro::Decode for #name #ty_generics #where_clause {
+            fn decode(
+                decoder: &mut ::hello_macro::Decoder<'_>,
+            ) -> ::core::result::Result<Self, ::hello_macro::DecodeError> {
+                #body
+            }
+        }
+    })
+}
@@ -0,0 +1,229 @@
+//!
+//! `#[derive(ErrorCode)]`
+//!
+//! 用于错误enum，每个variant必须用`#[code(10000)]`指定一个唯一的错误码，生成：
+//! - `code()`，返回variant的错误码
+//! - `CODES`常量，按声明顺序列出所有错误码和`Enum::Variant`，可以传给`hello_macro::code_table`输出码表，
+//!   或者传给`hello_macro::assert_unique_codes`检查多个crate的错误码没有冲突
+//! - `Display`，格式为`code: 10000, message: ...`，message由`#[code(10000, message = "...")]`指定，
+//!   其中可以用`{field}`引用命名字段；没有指定时，只有一个字段的variant显示该字段，否则显示variant名
+//! - `Error`，`source()`返回`#[from]`字段
+//! - 每个`#[from]`字段的`From`
+//!
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{Attribute, Data, DeriveInput, Error, Field, Fields, Ident, LitInt, LitStr, Result, Token};
+
+/// values of the `#[code(...)]` attribute on one variant
+struct CodeAttr {
+    code: u32,
+    span: Span,
+    message: Option<LitStr>,
+}
+
+impl CodeAttr {
+    fn parse(variant: &Ident, attrs: &[Attribute], errors: &mut Vec<Error>) -> Option<CodeAttr> {
+        let mut code = None;
+        for attr in attrs.iter().filter(|a| a.path().is_ident("code")) {
+            let result = attr.parse_args_with(|input: syn::parse::ParseStream| {
+                let lit: LitInt = input.parse()?;
+                let mut message = None;
+                if input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+                    let key: Ident = input.parse()?;
+                    if key != "message" {
+                        return Err(Error::new(key.span(), "unknown `code` attribute, expected `message`"));
+                    }
+                    input.parse::<Token![=]>()?;
+                    message = Some(input.parse::<LitStr>()?);
+                    input.parse::<Option<Token![,]>>()?;
+                }
+                Ok(CodeAttr {
+                    code: lit.base10_parse()?,
+                    span: lit.span(),
+                    message,
+                })
+            });
+            match result {
+                Ok(_) if code.is_some() => errors.push(Error::new_spanned(attr, "duplicate `#[code]`")),
+                Ok(parsed) => code = Some(parsed),
+                Err(err) => errors.push(err),
+            }
+        }
+        if !attrs.iter().any(|a| a.path().is_ident("code")) {
+            errors.push(Error::new(
+                variant.span(),
+                format!("variant `{}` needs an error code such as `#[code(10000)]`", variant),
+            ));
+        }
+        code
+    }
+}
+
+/// the `#[from]` field of a variant
+fn from_field<'a>(fields: &'a Fields, errors: &mut Vec<Error>) -> Option<&'a Field> {
+    let mut from = None;
+    for field in fields {
+        for attr in field.attrs.iter().filter(|a| a.path().is_ident("from")) {
+            if let Err(err) = attr.meta.require_path_only() {
+                errors.push(err);
+            } else if fields.len() != 1 {
+                errors.push(Error::new_spanned(
+                    attr,
+                    "`#[from]` needs a variant with exactly one field",
+                ));
+            } else {
+                from = Some(field);
+            }
+        }
+    }
+    from
+}
+
+pub(crate) fn impl_error_code(ast: &DeriveInput) -> Result<TokenStream> {
+    let name = &ast.ident;
+    let data = match &ast.data {
+        Data::Enum(data) => data,
+        Data::Struct(data) => {
+            return Err(Error::new(
+                data.struct_token.span,
+                "`ErrorCode` can only be derived for enums",
+            ))
+        }
+        Data::Union(data) => {
+            return Err(Error::new(
+                data.union_token.span,
+                "`ErrorCode` can only be derived for enums",
+            ))
+        }
+    };
+    if !ast.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &ast.generics,
+            "`ErrorCode` cannot be derived for generic enums",
+        ));
+    }
+    if data.variants.is_empty() {
+        return Err(Error::new(name.span(), "`ErrorCode` needs at least one variant"));
+    }
+
+    let mut errors = Vec::new();
+    for attr in ast.attrs.iter().filter(|a| a.path().is_ident("code")) {
+        errors.push(Error::new_spanned(attr, "`#[code]` goes on variants, not on the enum"));
+    }
+    let mut used: Vec<(u32, &Ident)> = Vec::new();
+    let mut from_types: Vec<(String, &Ident)> = Vec::new();
+    let (mut codes, mut entries, mut displays, mut sources, mut froms) =
+        (Vec::new(), Vec::new(), Vec::new(), Vec::new(), Vec::new());
+    for variant in &data.variants {
+        let ident = &variant.ident;
+        let code = CodeAttr::parse(ident, &variant.attrs, &mut errors);
+        let from = from_field(&variant.fields, &mut errors);
+        let Some(code) = code else {
+            continue;
+        };
+        // 重复的错误码指向后出现的那个
+        if let Some((_, first)) = used.iter().find(|(c, _)| *c == code.code) {
+            errors.push(Error::new(
+                code.span,
+                format!("duplicate error code {}, already used by `{}`", code.code, first),
+            ));
+        }
+        used.push((code.code, ident));
+
+        let value = code.code;
+        codes.push(quote!(Self::#ident { .. } => #value,));
+        let entry = format!("{}::{}", name, ident);
+        entries.push(quote!(::hello_macro::ErrorCodeEntry { code: #value, name: #entry }));
+
+        // 单字段的variant统一绑定为value，命名字段绑定为字段名，message中才能用{field}引用。
+        // formatter命名为__formatter，避免被名为f的字段遮蔽
+        let single = variant.fields.len() == 1;
+        let (pattern, message) = match (&code.message, &variant.fields) {
+            (Some(message), Fields::Named(fields)) => {
+                let idents = fields.named.iter().map(|f| &f.ident);
+                (quote!({ #(#idents),* }), quote!(::core::format_args!(#message)))
+            }
+            (Some(message), _) => (quote!({ .. }), quote!(::core::format_args!(#message))),
+            (None, Fields::Named(fields)) if single => {
+                let field = &fields.named[0].ident;
+                (quote!({ #field: value }), quote!(value))
+            }
+            (None, Fields::Unnamed(_)) if single => (quote!((value)), quote!(value)),
+            (None, _) => {
+                let ident = ident.to_string();
+                (quote!({ .. }), quote!(#ident))
+            }
+        };
+        displays.push(quote! {
+            Self::#ident #pattern => ::core::write!(__formatter, "code: {}, message: {}", #value, #message),
+        });
+
+        if let Some(field) = from {
+            let ty = &field.ty;
+            let type_name = quote!(#ty).to_string();
+            if let Some((_, first)) = from_types.iter().find(|(t, _)| *t == type_name) {
+                errors.push(Error::new_spanned(
+                    ty,
+                    format!("`#[from]` type is already used by `{}`", first),
+                ));
+            }
+            from_types.push((type_name, ident));
+            let construct = match &field.ident {
+                Some(field) => quote!(#name::#ident { #field: value }),
+                None => quote!(#name::#ident(value)),
+            };
+            let member = match &field.ident {
+                Some(field) => quote!({ #field: value }),
+                None => quote!((value)),
+            };
+            sources.push(quote!(Self::#ident #member => ::core::option::Option::Some(value),));
+            froms.push(quote! {
+                impl ::core::convert::From<#ty> for #name {
+                    fn from(value: #ty) -> Self {
+                        #construct
+                    }
+                }
+            });
+        }
+    }
+    crate::combine_errors(errors)?;
+
+    let count = entries.len();
+    let source_fallback = match sources.len() < count {
+        true => quote!(_ => ::core::option::Option::None,),
+        false => quote!(),
+    };
+    Ok(quote! {
+        impl #name {
+            /// the code and name of every variant in declaration order
+            pub const CODES: &'static [::hello_macro::ErrorCodeEntry] = &[#(#entries),*];
+
+            /// the error code of this variant
+            pub fn code(&self) -> u32 {
+                match self {
+                    #(#codes)*
+                }
+            }
+        }
+
+        impl ::core::fmt::Display for #name {
+            #[allow(unused_variables)]
+            fn fmt(&self, __formatter: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                match self {
+                    #(#displays)*
+                }
+            }
+        }
+
+        impl ::std::error::Error for #name {
+            fn source(&self) -> ::core::option::Option<&(dyn ::std::error::Error + 'static)> {
+                match self {
+                    #(#sources)*
+                    #source_fallback
+                }
+            }
+        }
+
+        #(#froms)*
+    })
+}
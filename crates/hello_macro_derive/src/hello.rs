@@ -0,0 +1,111 @@
+//!
+//! `#[derive(HelloMacro)]`
+//!
+//! 问候语是`"{greeting}, Macro! My name is {name}!"`，greeting默认为`Hello`，name默认为类型名，
+//! 都可以用`#[hello(name = "...", greeting = "...")]`覆盖。enum的每个variant可以有自己的问候语，
+//! 没有指定的部分使用variant名和enum上的greeting
+//!
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Attribute, Data, DeriveInput, Error, Fields, LitStr, Result};
+
+/// values of the `#[hello(...)]` attributes on one item
+#[derive(Default)]
+struct HelloAttr {
+    name: Option<LitStr>,
+    greeting: Option<LitStr>,
+}
+
+impl HelloAttr {
+    fn parse(attrs: &[Attribute], errors: &mut Vec<Error>) -> HelloAttr {
+        let mut hello = HelloAttr::default();
+        for attr in attrs.iter().filter(|a| a.path().is_ident("hello")) {
+            let result = attr.parse_nested_meta(|meta| {
+                let slot = if meta.path.is_ident("name") {
+                    &mut hello.name
+                } else if meta.path.is_ident("greeting") {
+                    &mut hello.greeting
+                } else {
+                    return Err(meta.error("unknown `hello` attribute, expected `name` or `greeting`"));
+                };
+                let value: LitStr = meta.value()?.parse()?;
+                if value.value().trim().is_empty() {
+                    return Err(Error::new(value.span(), "value cannot be empty"));
+                }
+                if slot.is_some() {
+                    return Err(meta.error("duplicate `hello` attribute"));
+                }
+                *slot = Some(value);
+                Ok(())
+            });
+            errors.extend(result.err());
+        }
+        hello
+    }
+
+    fn message(&self, default_name: String, default_greeting: &str) -> String {
+        let name = self.name.as_ref().map_or(default_name, LitStr::value);
+        let greeting = self
+            .greeting
+            .as_ref()
+            .map_or(default_greeting.to_string(), LitStr::value);
+        format!("{}, Macro! My name is {}!", greeting, name)
+    }
+}
+
+/// `#[hello]` is only meaningful on the type and on enum variants
+fn reject_on_fields(fields: &Fields, errors: &mut Vec<Error>) {
+    for attr in fields.iter().flat_map(|f| &f.attrs) {
+        if attr.path().is_ident("hello") {
+            errors.push(Error::new_spanned(attr, "`#[hello]` is not allowed on fields"));
+        }
+    }
+}
+
+pub(crate) fn impl_hello_macro(ast: &DeriveInput) -> Result<TokenStream> {
+    let mut errors = Vec::new();
+    let name = &ast.ident;
+    let hello = HelloAttr::parse(&ast.attrs, &mut errors);
+    let greeting = hello.greeting.as_ref().map_or(String::from("Hello"), LitStr::value);
+    let message = hello.message(name.to_string(), &greeting);
+
+    let body = match &ast.data {
+        Data::Struct(data) => {
+            reject_on_fields(&data.fields, &mut errors);
+            quote!(::std::string::String::from(#message))
+        }
+        Data::Enum(data) => {
+            let arms = data.variants.iter().map(|variant| {
+                let message =
+                    HelloAttr::parse(&variant.attrs, &mut errors).message(variant.ident.to_string(), &greeting);
+                reject_on_fields(&variant.fields, &mut errors);
+                let ident = &variant.ident;
+                quote!(Self::#ident { .. } => ::std::string::String::from(#message),)
+            });
+            let arms: Vec<_> = arms.collect();
+            quote!(match *self { #(#arms)* })
+        }
+        Data::Union(data) => {
+            return Err(Error::new(
+                data.union_token.span,
+                "`HelloMacro` cannot be derived for unions",
+            ));
+        }
+    };
+    crate::combine_errors(errors)?;
+
+    // 泛型参数和where子句原样带到impl上
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+    // quote!宏提供了参数替换机制，比如#name会用name变量的值替换
+    Ok(quote! {
+        impl #impl_generics ::hello_macro::HelloMacro for #name #ty_generics #where_clause {
+            fn hello_macro() {
+                println!("{}", #message);
+            }
+
+            fn greeting(&self) -> ::std::string::String {
+                #body
+            }
+        }
+    })
+}
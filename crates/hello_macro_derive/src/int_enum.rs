@@ -0,0 +1,178 @@
+//!
+//! `#[derive(IntEnum)]`
+//!
+//! 用于只有unit variant并且标注了`#[repr(整数类型)]`的enum，生成：
+//! - `TryFrom<repr>`，失败时返回列出所有合法值的`hello_macro::IntEnumError`
+//! - `From<Enum> for repr`
+//! - `Display`和`FromStr`，使用variant名或者`#[int_enum(name = "...")]`指定的名字
+//! - `ALL`常量，按声明顺序列出所有variant
+//!
+//! 判别值可以显式写出也可以隐式递增，生成的代码都用`Self::X as repr`取值，所以不需要在宏里计算
+//!
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Attribute, Data, DeriveInput, Error, Fields, Ident, LitStr, Result};
+
+const INT_TYPES: [&str; 12] = [
+    "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+];
+
+/// the integer type in `#[repr(...)]`
+fn repr(attrs: &[Attribute]) -> Result<Option<Ident>> {
+    let mut int = None;
+    for attr in attrs.iter().filter(|a| a.path().is_ident("repr")) {
+        attr.parse_nested_meta(|meta| {
+            if let Some(ident) = meta.path.get_ident() {
+                if INT_TYPES.contains(&ident.to_string().as_str()) {
+                    int = Some(ident.clone());
+                }
+            }
+            // repr(C)、repr(align(4))之类的不关心，跳过括号里的内容
+            if meta.input.peek(syn::token::Paren) {
+                meta.input.parse::<proc_macro2::TokenTree>()?;
+            }
+            Ok(())
+        })?;
+    }
+    Ok(int)
+}
+
+/// the name in `#[int_enum(name = "...")]`
+fn variant_name(attrs: &[Attribute], errors: &mut Vec<Error>) -> Option<LitStr> {
+    let mut name = None;
+    for attr in attrs.iter().filter(|a| a.path().is_ident("int_enum")) {
+        let result = attr.parse_nested_meta(|meta| {
+            if !meta.path.is_ident("name") {
+                return Err(meta.error("unknown `int_enum` attribute, expected `name`"));
+            }
+            let value: LitStr = meta.value()?.parse()?;
+            if value.value().is_empty() {
+                return Err(Error::new(value.span(), "name cannot be empty"));
+            }
+            if name.is_some() {
+                return Err(meta.error("duplicate `name`"));
+            }
+            name = Some(value);
+            Ok(())
+        });
+        errors.extend(result.err());
+    }
+    name
+}
+
+pub(crate) fn impl_int_enum(ast: &DeriveInput) -> Result<TokenStream> {
+    let name = &ast.ident;
+    let data = match &ast.data {
+        Data::Enum(data) => data,
+        Data::Struct(data) => {
+            return Err(Error::new(
+                data.struct_token.span,
+                "`IntEnum` can only be derived for enums",
+            ))
+        }
+        Data::Union(data) => {
+            return Err(Error::new(
+                data.union_token.span,
+                "`IntEnum` can only be derived for enums",
+            ))
+        }
+    };
+    let Some(repr) = repr(&ast.attrs)? else {
+        return Err(Error::new(
+            name.span(),
+            "`IntEnum` needs an integer representation such as `#[repr(i32)]`",
+        ));
+    };
+    if !ast.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &ast.generics,
+            "`IntEnum` cannot be derived for generic enums",
+        ));
+    }
+
+    let mut errors = Vec::new();
+    for attr in ast.attrs.iter().filter(|a| a.path().is_ident("int_enum")) {
+        errors.push(Error::new_spanned(
+            attr,
+            "`#[int_enum]` goes on variants, not on the enum",
+        ));
+    }
+    let mut variants = Vec::new();
+    let mut names: Vec<(String, proc_macro2::Span)> = Vec::new();
+    for variant in &data.variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            errors.push(Error::new_spanned(
+                &variant.fields,
+                "`IntEnum` variants cannot have fields",
+            ));
+        }
+        let (display, span) = match variant_name(&variant.attrs, &mut errors) {
+            Some(lit) => (lit.value(), lit.span()),
+            None => (variant.ident.to_string(), variant.ident.span()),
+        };
+        if names.iter().any(|(n, _)| *n == display) {
+            errors.push(Error::new(span, format!("duplicate name `{}`", display)));
+        }
+        names.push((display, span));
+        variants.push(&variant.ident);
+    }
+    crate::combine_errors(errors)?;
+
+    let target = name.to_string();
+    let count = variants.len();
+    let names: Vec<_> = names.into_iter().map(|(n, _)| n).collect();
+    Ok(quote! {
+        impl #name {
+            /// every variant in declaration order
+            pub const ALL: [#name; #count] = [#(#name::#variants),*];
+        }
+
+        impl ::core::convert::TryFrom<#repr> for #name {
+            type Error = ::hello_macro::IntEnumError<#repr>;
+
+            fn try_from(value: #repr) -> ::core::result::Result<Self, Self::Error> {
+                const VALID: &[#repr] = &[#(#name::#variants as #repr),*];
+                #(
+                    if value == #name::#variants as #repr {
+                        return ::core::result::Result::Ok(#name::#variants);
+                    }
+                )*
+                ::core::result::Result::Err(::hello_macro::IntEnumError {
+                    target: #target,
+                    value,
+                    valid: VALID,
+                })
+            }
+        }
+
+        impl ::core::convert::From<#name> for #repr {
+            fn from(value: #name) -> #repr {
+                value as #repr
+            }
+        }
+
+        impl ::core::fmt::Display for #name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                f.pad(match self {
+                    #(#name::#variants => #names,)*
+                })
+            }
+        }
+
+        impl ::core::str::FromStr for #name {
+            type Err = ::hello_macro::ParseEnumError;
+
+            fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
+                const VALID: &[&str] = &[#(#names),*];
+                match s {
+                    #(#names => ::core::result::Result::Ok(#name::#variants),)*
+                    _ => ::core::result::Result::Err(::hello_macro::ParseEnumError {
+                        target: #target,
+                        name: ::std::string::String::from(s),
+                        valid: VALID,
+                    }),
+                }
+            }
+        }
+    })
+}
@@ -27,41 +27,206 @@
 //                                  v
 //                   proc_macro::TokenStream (to compiler)
 use proc_macro::TokenStream;
-use quote::quote;
-use syn;
+use syn::{parse_macro_input, DeriveInput};
 
-#[proc_macro_derive(HelloMacro)]
+mod builder;
+mod codec;
+mod error_code;
+mod hello;
+mod int_enum;
+mod route;
+mod sql;
+
+///
+/// implement `hello_macro::HelloMacro`, generics and where-clauses are carried over to the impl
+///
+/// `#[hello(name = "...", greeting = "...")]`可以写在类型和enum的variant上，覆盖问候语中的名字和问候词
+///
+#[proc_macro_derive(HelloMacro, attributes(hello))]
 pub fn hello_macro_derive(input: TokenStream) -> TokenStream {
     // Construct a representation of Rust code as a syntax tree
-    // that we can manipulate
-    let ast = syn::parse(input).unwrap();
+    // that we can manipulate，解析失败时parse_macro_input!直接返回带span的编译错误
+    let ast = parse_macro_input!(input as DeriveInput);
 
     // Build the trait implementation
-    impl_hello_macro(&ast)
+    hello::impl_hello_macro(&ast)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
 }
 
-fn impl_hello_macro(ast: &syn::DeriveInput) -> TokenStream {
-    let name = &ast.ident;
-    // quote!宏提供了参数替换机制，比如#name会用name变量的值替换
-    let gen = quote! {
-        impl HelloMacro for #name {
-            fn hello_macro() {
-                // stringify!宏将接收到的表达式转换为string literal。即&'static str
-                println!("Hello, Macro! My name is {}!", stringify!(#name));
-            }
-        }
-    };
-    gen.into()
+///
+/// generate a fluent `XxxBuilder` for a struct with named fields, see `hello_macro::BuilderError`
+///
+/// `Option`字段可以不设置，其他字段没有设置时`build()`返回错误，字段上可以用
+/// `#[builder(default)]`、`#[builder(default = expr)]`、`#[builder(into)]`和`#[builder(each = "item")]`
+///
+/// ```ignore
+/// #[derive(Builder)]
+/// struct Order {
+///     #[builder(into)]
+///     customer: String,
+///     #[builder(each = "item")]
+///     items: Vec<String>,
+///     note: Option<String>,
+/// }
+///
+/// let order = Order::builder().customer("libai").item("tea".to_string()).build()?;
+/// ```
+///
+#[proc_macro_derive(Builder, attributes(builder))]
+pub fn builder_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    builder::impl_builder(&ast)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+///
+/// conversions between a fieldless `#[repr(int)]` enum and its integer, plus `Display`, `FromStr` and `ALL`
+///
+/// `TryFrom<repr>`失败时返回`hello_macro::IntEnumError`，`FromStr`失败时返回`hello_macro::ParseEnumError`，
+/// 显示的名字默认是variant名，可以用`#[int_enum(name = "...")]`修改
+///
+/// ```ignore
+/// #[derive(Debug, Clone, Copy, IntEnum)]
+/// #[repr(u8)]
+/// enum Channel {
+///     Phone = 1,
+///     #[int_enum(name = "e-mail")]
+///     Email = 2,
+/// }
+///
+/// assert_eq!(Ok(Channel::Email), Channel::try_from(2));
+/// assert_eq!("e-mail", Channel::Email.to_string());
+/// ```
+///
+#[proc_macro_derive(IntEnum, attributes(int_enum))]
+pub fn int_enum_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    int_enum::impl_int_enum(&ast)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+///
+/// numeric error codes for an error enum: `code()`, a `CODES` table, `Display`, `Error` and `From` for `#[from]` fields
+///
+/// 每个variant用`#[code(N)]`指定错误码，重复的错误码在编译期报错；`#[code(N, message = "...")]`指定显示的消息，
+/// 消息中可以用`{field}`引用命名字段
+///
+/// ```ignore
+/// #[derive(Debug, ErrorCode)]
+/// enum AppError {
+///     #[code(10000)]
+///     Io(#[from] std::io::Error),
+///     #[code(10002, message = "user {id} not found")]
+///     UserNotFound { id: u32 },
+/// }
+///
+/// assert_eq!(10002, AppError::UserNotFound { id: 1 }.code());
+/// print!("{}", hello_macro::code_table(&[AppError::CODES]));
+/// ```
+///
+#[proc_macro_derive(ErrorCode, attributes(code, from))]
+pub fn error_code_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    error_code::impl_error_code(&ast)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+///
+/// write a struct or an enum with `hello_macro::to_bytes` in a compact binary format
+///
+/// 整数用varint，字符串和Vec带长度前缀，enum先写variant下标；
+/// 字段上的`#[encode(skip)]`不编码，`#[encode(since = N)]`只在版本号>=N时编码
+///
+/// ```ignore
+/// #[derive(Encode, Decode)]
+/// struct Login {
+///     user: String,
+///     #[encode(since = 2)]
+///     device: Option<String>,
+/// }
+///
+/// let bytes = hello_macro::to_bytes(&login, 2);
+/// let login: Login = hello_macro::from_bytes(&bytes)?;
+/// ```
+///
+#[proc_macro_derive(Encode, attributes(encode))]
+pub fn encode_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    codec::impl_encode(&ast)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// read a value written by `#[derive(Encode)]`, truncated or malformed input returns `hello_macro::DecodeError`
+#[proc_macro_derive(Decode, attributes(encode))]
+pub fn decode_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    codec::impl_decode(&ast)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
 }
 
 // attribute-like宏，attr对应第一部分，也就是GET,"/"，item对应第二部分，也就是fn index() {}
+///
+/// register an async fn as an axum handler of `METHOD` on `"/path"`, mount it with [routes!]
+///
+/// method和path写错时编译报错，path中的`:param`必须被handler的`Path`参数按名字取出
+///
+/// ```ignore
+/// #[route(GET, "/users/:id")]
+/// async fn user(Path(id): Path<u32>) -> String {
+///     format!("user {}", id)
+/// }
+///
+/// let router: axum::Router = routes![user];
+/// ```
+///
 #[proc_macro_attribute]
 pub fn route(attr: TokenStream, item: TokenStream) -> TokenStream {
-    todo!()
+    let handler = proc_macro2::TokenStream::from(item.clone());
+    match route::expand_route(attr.into(), item.into()) {
+        Ok(expanded) => expanded.into(),
+        // 出错时保留原来的函数，避免调用处再报一堆找不到函数的错误
+        Err(err) => {
+            let mut out = err.into_compile_error();
+            out.extend(handler);
+            out.into()
+        }
+    }
+}
+
+/// an `axum::Router` with every listed `#[route]` handler mounted
+#[proc_macro]
+pub fn routes(input: TokenStream) -> TokenStream {
+    route::expand_routes(input.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
 }
 
 // function-like宏
+///
+/// a `hello_macro::SqlQuery<N>` of a SQL statement checked at compile time, `N` is the number of `?` placeholders
+///
+/// 支持SELECT、INSERT、UPDATE、DELETE和WHERE条件，语法错误在编译期报告并指向出错的token
+///
 #[proc_macro]
 pub fn sql(input: TokenStream) -> TokenStream {
-    todo!()
+    sql::expand_sql(input.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// every error in `errors` combined into one, so that the compiler reports all of them at once
+fn combine_errors(errors: Vec<syn::Error>) -> syn::Result<()> {
+    match errors.into_iter().reduce(|mut all, e| {
+        all.combine(e);
+        all
+    }) {
+        Some(err) => Err(err),
+        None => Ok(()),
+    }
 }
@@ -0,0 +1,362 @@
+//!
+//! `#[route(METHOD, "/path")]` and `routes![...]` for axum
+//!
+//! `#[route]`在编译期检查method和path，并检查path中的`:param`和handler的`Path`参数是否一致，
+//! 然后在handler旁边生成一个隐藏的注册函数`__route_<handler>`，`routes![...]`依次调用这些函数得到[axum::Router]
+//!
+use std::ops::Range;
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{
+    parse::{Parse, ParseStream, Parser},
+    punctuated::Punctuated,
+    spanned::Spanned,
+    Error, FnArg, Ident, ItemFn, LitStr, Pat, PathArguments, Result, Token, Type,
+};
+
+/// methods accepted by the attribute, each maps to the lowercase function in `axum::routing`
+const METHODS: [&str; 8] = ["GET", "POST", "PUT", "DELETE", "PATCH", "HEAD", "OPTIONS", "TRACE"];
+
+/// `METHOD, "/path"`
+struct RouteArgs {
+    method: Ident,
+    path: LitStr,
+}
+
+impl Parse for RouteArgs {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let method = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let path = input.parse()?;
+        input.parse::<Option<Token![,]>>()?;
+        Ok(RouteArgs { method, path })
+    }
+}
+
+pub(crate) fn expand_route(attr: TokenStream, item: TokenStream) -> Result<TokenStream> {
+    let args: RouteArgs = syn::parse2(attr)?;
+    let handler: ItemFn = syn::parse2(item)?;
+    let routing = routing_fn(&args.method)?;
+    let path = args.path.value();
+    let params = path_params(&path).map_err(|(range, msg)| path_error(&args.path, range, msg))?;
+    let state = check_handler(&handler, &params, &args.path)?;
+
+    let name = &handler.sig.ident;
+    let vis = &handler.vis;
+    let register = register_ident(name);
+    // 用到State<T>时只能注册到Router<T>上，否则对任意state都可以注册
+    let (generics, state, bounds) = match state {
+        Some(ty) => (quote!(), quote!(#ty), quote!()),
+        None => (
+            quote!(<S>),
+            quote!(S),
+            quote!(where S: ::core::clone::Clone + ::core::marker::Send + ::core::marker::Sync + 'static),
+        ),
+    };
+    Ok(quote! {
+        #handler
+
+        #[doc(hidden)]
+        #vis fn #register #generics(router: ::axum::Router<#state>) -> ::axum::Router<#state> #bounds {
+            router.route(#path, ::axum::routing::#routing(#name))
+        }
+    })
+}
+
+pub(crate) fn expand_routes(input: TokenStream) -> Result<TokenStream> {
+    let handlers = Punctuated::<syn::Path, Token![,]>::parse_terminated.parse2(input)?;
+    let registers = handlers.into_iter().map(|mut path| {
+        let last = path.segments.last_mut().expect("parsed path is never empty");
+        last.ident = register_ident(&last.ident);
+        path
+    });
+    Ok(quote! {{
+        let router = ::axum::Router::new();
+        #(let router = #registers(router);)*
+        router
+    }})
+}
+
+fn register_ident(handler: &Ident) -> Ident {
+    format_ident!("__route_{}", handler, span = handler.span())
+}
+
+fn routing_fn(method: &Ident) -> Result<Ident> {
+    let name = method.to_string();
+    if METHODS.contains(&name.as_str()) {
+        return Ok(Ident::new(&name.to_lowercase(), method.span()));
+    }
+    let upper = name.to_uppercase();
+    let msg = if METHODS.contains(&upper.as_str()) {
+        format!("HTTP method must be uppercase, did you mean `{}`?", upper)
+    } else {
+        format!("unknown HTTP method `{}`, expected one of {}", name, METHODS.join(", "))
+    };
+    Err(Error::new(method.span(), msg))
+}
+
+///
+/// names of the `:param` and `*wildcard` segments in order
+///
+/// 路径必须以`/`开头，除了末尾以外不能有空的段，`*wildcard`只能是最后一段。出错时返回出错部分在path中的范围
+///
+fn path_params(path: &str) -> std::result::Result<Vec<&str>, (Range<usize>, String)> {
+    let Some(rest) = path.strip_prefix('/') else {
+        return Err((
+            0..path.len(),
+            format!("route path must start with `/`, found {:?}", path),
+        ));
+    };
+    if let Some((i, c)) = path
+        .char_indices()
+        .find(|(_, c)| c.is_whitespace() || "?#{}".contains(*c))
+    {
+        return Err((i..i + c.len_utf8(), format!("invalid character {:?} in route path", c)));
+    }
+    let segments: Vec<&str> = rest.split('/').collect();
+    let mut params = Vec::new();
+    // 当前段在path中的起始位置，跳过开头的`/`
+    let mut start = 1;
+    for (i, segment) in segments.iter().enumerate() {
+        let range = start..start + segment.len();
+        start = range.end + 1;
+        let last = i + 1 == segments.len();
+        let name = match segment.as_bytes().first() {
+            None if last => continue,
+            // 空段指向前后两个`/`
+            None => {
+                return Err((
+                    range.start - 1..range.start + 1,
+                    String::from("empty segment in route path"),
+                ))
+            }
+            Some(b':') => &segment[1..],
+            Some(b'*') if last => &segment[1..],
+            Some(b'*') => return Err((range, format!("wildcard `{}` must be the last segment", segment))),
+            Some(_) if segment.contains([':', '*']) => {
+                return Err((range, format!("`:` and `*` must start a segment, found `{}`", segment)))
+            }
+            Some(_) => continue,
+        };
+        if !is_ident(name) {
+            return Err((range, format!("invalid parameter name {:?} in `{}`", name, segment)));
+        }
+        if params.contains(&name) {
+            return Err((range, format!("duplicate path parameter `{}`", name)));
+        }
+        params.push(name);
+    }
+    Ok(params)
+}
+
+/// an error at `range` of the path, the whole literal is marked when the compiler cannot point inside it
+fn path_error(lit: &LitStr, range: Range<usize>, msg: String) -> Error {
+    // 只有没有转义的普通字符串，字面量中的偏移才等于path中的偏移加上开头的引号
+    let token = lit.token();
+    let span = match token.to_string() == format!("{:?}", lit.value()) {
+        true => token.subspan(range.start + 1..range.end + 1),
+        false => None,
+    };
+    Error::new(span.unwrap_or_else(|| lit.span()), msg)
+}
+
+fn is_ident(s: &str) -> bool {
+    let mut chars = s.chars();
+    chars.next().is_some_and(|c| c == '_' || c.is_ascii_alphabetic())
+        && chars.all(|c| c == '_' || c.is_ascii_alphanumeric())
+}
+
+/// the last segment name and generic arguments of a type like `axum::extract::Path<T>`
+fn extractor(ty: &Type) -> Option<(String, &PathArguments)> {
+    match ty {
+        Type::Path(p) => p.path.segments.last().map(|s| (s.ident.to_string(), &s.arguments)),
+        _ => None,
+    }
+}
+
+///
+/// check the handler signature against the path parameters, returns the `State<T>` type if any
+///
+/// `Path(id)`和`Path((id, name))`按顺序比较变量名，`Path(Params { id, name })`比较字段名，变量名开头的`_`会被忽略。
+/// 多个参数时`Path(params)`可能是结构体或者map，不做检查
+///
+fn check_handler(handler: &ItemFn, params: &[&str], path: &LitStr) -> Result<Option<Type>> {
+    let sig = &handler.sig;
+    if sig.asyncness.is_none() {
+        return Err(Error::new(sig.fn_token.span, "route handler must be an `async fn`"));
+    }
+    if !sig.generics.params.is_empty() {
+        return Err(Error::new_spanned(&sig.generics, "route handler cannot be generic"));
+    }
+    let mut state = None;
+    let mut extracted = false;
+    for arg in &sig.inputs {
+        let arg = match arg {
+            FnArg::Receiver(r) => return Err(Error::new_spanned(r, "route handler cannot take `self`")),
+            FnArg::Typed(arg) => arg,
+        };
+        match extractor(&arg.ty) {
+            Some((name, PathArguments::AngleBracketed(generic))) if name == "State" => {
+                if let Some(syn::GenericArgument::Type(ty)) = generic.args.first() {
+                    state = Some(ty.clone());
+                }
+            }
+            Some((name, _)) if name == "Path" => {
+                if params.is_empty() {
+                    let msg = format!("route {:?} has no path parameters to extract", path.value());
+                    return Err(Error::new_spanned(arg, msg));
+                }
+                extracted = true;
+                match &*arg.pat {
+                    Pat::TupleStruct(p) if p.elems.len() == 1 => check_bindings(&p.elems[0], params)?,
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+    }
+    if !extracted && !params.is_empty() {
+        let msg = format!(
+            "path parameter `:{}` is never extracted, add a `Path` argument to `{}`",
+            params[0], sig.ident
+        );
+        return Err(Error::new(path.span(), msg));
+    }
+    Ok(state)
+}
+
+fn check_bindings(pat: &Pat, params: &[&str]) -> Result<()> {
+    match pat {
+        Pat::Ident(p) if params.len() == 1 => check_name(&p.ident, params[0]),
+        Pat::Tuple(t) => {
+            if t.elems.len() != params.len() {
+                let msg = format!(
+                    "route has {} path parameters but the pattern binds {}",
+                    params.len(),
+                    t.elems.len()
+                );
+                return Err(Error::new_spanned(t, msg));
+            }
+            for (elem, &param) in t.elems.iter().zip(params) {
+                if let Pat::Ident(p) = elem {
+                    check_name(&p.ident, param)?;
+                }
+            }
+            Ok(())
+        }
+        Pat::Struct(s) => {
+            let mut fields = Vec::new();
+            for field in &s.fields {
+                let name = match &field.member {
+                    syn::Member::Named(ident) => ident.to_string(),
+                    syn::Member::Unnamed(index) => index.index.to_string(),
+                };
+                if !params.contains(&name.as_str()) {
+                    let msg = format!(
+                        "no `:{}` in route path, expected one of `:{}`",
+                        name,
+                        params.join("`, `:")
+                    );
+                    return Err(Error::new(field.member.span(), msg));
+                }
+                fields.push(name);
+            }
+            match params.iter().find(|p| !fields.iter().any(|f| f == *p)) {
+                Some(missing) if s.rest.is_none() => Err(Error::new_spanned(
+                    s,
+                    format!("path parameter `:{}` is not bound", missing),
+                )),
+                _ => Ok(()),
+            }
+        }
+        _ => Ok(()),
+    }
+}
+
+fn check_name(ident: &Ident, param: &str) -> Result<()> {
+    let name = ident.to_string();
+    if name.trim_start_matches('_') == param {
+        return Ok(());
+    }
+    Err(Error::new(
+        ident.span(),
+        format!("`{}` does not match `:{}` in the route path", name, param),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_path() {
+        assert_eq!(Ok(vec![]), path_params("/"));
+        assert_eq!(Ok(vec![]), path_params("/users/"));
+        assert_eq!(Ok(vec!["id", "post_id"]), path_params("/users/:id/posts/:post_id"));
+        assert_eq!(Ok(vec!["rest"]), path_params("/assets/*rest"));
+        let err = |path| path_params(path).unwrap_err();
+        assert_eq!(0..5, err("users").0);
+        assert!(err("users").1.contains("must start with `/`"));
+        assert_eq!((2..4, String::from("empty segment in route path")), err("/a//b"));
+        assert_eq!(2..3, err("/a b").0);
+        assert!(err("/a b").1.contains("invalid character ' '"));
+        assert_eq!(1..6, err("/*rest/x").0);
+        assert!(err("/*rest/x").1.contains("must be the last segment"));
+        assert!(err("/user:id").1.contains("must start a segment"));
+        assert_eq!(3..6, err("/a/:1d").0);
+        assert!(err("/a/:1d").1.contains("invalid parameter name"));
+        assert!(err("/:").1.contains("invalid parameter name"));
+        assert_eq!(5..8, err("/:id/:id").0);
+        assert!(err("/:id/:id").1.contains("duplicate"));
+    }
+
+    fn expand_err(attr: TokenStream, item: TokenStream) -> String {
+        expand_route(attr, item).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn check_method_and_handler() {
+        let index = quote!(
+            async fn index() {}
+        );
+        assert!(expand_route(quote!(GET, "/"), index.clone()).is_ok());
+        assert!(expand_err(quote!(GTE, "/"), index.clone()).contains("unknown HTTP method `GTE`"));
+        assert!(expand_err(quote!(get, "/"), index.clone()).contains("did you mean `GET`"));
+        assert!(expand_err(quote!(GET, "/:id"), index).contains("`:id` is never extracted"));
+        assert!(expand_err(
+            quote!(GET, "/"),
+            quote!(
+                fn index() {}
+            )
+        )
+        .contains("must be an `async fn`"));
+
+        let user = quote!(
+            async fn user(Path((id, name)): Path<(u32, String)>) {}
+        );
+        assert!(expand_route(quote!(GET, "/users/:id/:name"), user.clone()).is_ok());
+        assert!(expand_err(quote!(GET, "/users/:id/:nmae"), user.clone()).contains("match `:nmae`"));
+        assert!(expand_err(quote!(GET, "/users/:id"), user.clone()).contains("binds 2"));
+        assert!(expand_err(quote!(GET, "/users"), user).contains("no path parameters"));
+
+        let fields = quote!(
+            async fn user(Path(Params { id, .. }): Path<Params>) {}
+        );
+        assert!(expand_route(quote!(POST, "/users/:id/:name"), fields.clone()).is_ok());
+        assert!(expand_err(quote!(POST, "/users/:uid/:name"), fields).contains("no `:id` in route path"));
+    }
+
+    #[test]
+    fn state_type() {
+        let expanded = expand_route(
+            quote!(PUT, "/counter"),
+            quote!(
+                async fn incr(State(counter): State<AppState>) {}
+            ),
+        )
+        .unwrap()
+        .to_string();
+        assert!(expanded.contains("fn __route_incr (router : :: axum :: Router < AppState >)"));
+    }
+}
@@ -0,0 +1,576 @@
+//!
+//! `sql!(...)`: a SQL subset checked at compile time
+//!
+//! 先把rust的token流转成SQL的token，再用递归下降解析，支持的语法：
+//!
+//! ```text
+//! statement := select | insert | update | delete
+//! select    := SELECT [DISTINCT] (* | item {, item}) FROM name [WHERE expr]
+//!              [ORDER BY name [ASC | DESC] {, name [ASC | DESC]}] [LIMIT value [OFFSET value]]
+//! item      := (name | function '(' (* | name) ')') [AS ident]
+//! insert    := INSERT INTO name '(' ident {, ident} ')' VALUES row {, row}
+//! row       := '(' operand {, operand} ')'
+//! update    := UPDATE name SET ident = operand {, ident = operand} [WHERE expr]
+//! delete    := DELETE FROM name [WHERE expr]
+//! expr      := and {OR and}
+//! and       := not {AND not}
+//! not       := NOT not | '(' expr ')' | predicate
+//! predicate := operand (cmp operand | IS [NOT] NULL | [NOT] IN '(' operand {, operand} ')'
+//!              | [NOT] LIKE operand | [NOT] BETWEEN operand AND operand)
+//! operand   := name | value
+//! value     := ? | string | number | TRUE | FALSE | NULL
+//! name      := ident [. ident]
+//! ```
+//!
+//! 关键字不区分大小写，输出的语句中关键字统一大写，字符串用rust的`"..."`写，输出时换成SQL的`'...'`
+//!
+use proc_macro2::{Delimiter, Ident, Span, TokenStream, TokenTree};
+use quote::quote;
+use syn::{Error, Lit, Result};
+
+const KEYWORDS: [&str; 29] = [
+    "SELECT", "DISTINCT", "FROM", "WHERE", "ORDER", "BY", "ASC", "DESC", "LIMIT", "OFFSET", "AS", "INSERT", "INTO",
+    "VALUES", "UPDATE", "SET", "DELETE", "AND", "OR", "NOT", "IS", "NULL", "IN", "LIKE", "BETWEEN", "TRUE", "FALSE",
+    "JOIN", "ON",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+enum Kind {
+    Word(String),
+    Punct(&'static str),
+    Str(String),
+    Number(String),
+    Open,
+    Close,
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: Kind,
+    span: Span,
+}
+
+impl Token {
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(&self.kind, Kind::Word(w) if w.eq_ignore_ascii_case(keyword))
+    }
+
+    fn describe(&self) -> String {
+        match &self.kind {
+            Kind::Word(w) | Kind::Number(w) => format!("`{}`", w),
+            Kind::Punct(p) => format!("`{}`", p),
+            Kind::Str(s) => format!("{:?}", s),
+            Kind::Open => String::from("`(`"),
+            Kind::Close => String::from("`)`"),
+        }
+    }
+}
+
+pub(crate) fn expand_sql(input: TokenStream) -> Result<TokenStream> {
+    let mut tokens = Vec::new();
+    tokenize(input, &mut tokens)?;
+    let mut parser = Parser {
+        tokens,
+        pos: 0,
+        sql: String::new(),
+        params: 0,
+    };
+    let kind = parser.statement()?;
+    let (sql, params) = (parser.sql, parser.params);
+    Ok(quote! {
+        ::hello_macro::SqlQuery::<#params>::__new(::hello_macro::StatementKind::#kind, #sql)
+    })
+}
+
+fn tokenize(input: TokenStream, out: &mut Vec<Token>) -> Result<()> {
+    let mut iter = input.into_iter().peekable();
+    while let Some(tree) = iter.next() {
+        let span = tree.span();
+        let kind = match tree {
+            TokenTree::Group(g) => match g.delimiter() {
+                Delimiter::Parenthesis => {
+                    out.push(Token {
+                        kind: Kind::Open,
+                        span: g.span_open(),
+                    });
+                    tokenize(g.stream(), out)?;
+                    Kind::Close
+                }
+                // macro_rules!传进来的片段会包在不可见的group里
+                Delimiter::None => {
+                    tokenize(g.stream(), out)?;
+                    continue;
+                }
+                _ => return Err(Error::new(span, "only parentheses are allowed in SQL")),
+            },
+            TokenTree::Ident(ident) => Kind::Word(ident.to_string()),
+            TokenTree::Literal(lit) => match Lit::new(lit) {
+                Lit::Str(s) => Kind::Str(s.value()),
+                Lit::Int(i) if i.suffix().is_empty() => Kind::Number(i.base10_digits().to_string()),
+                Lit::Float(f) if f.suffix().is_empty() => Kind::Number(f.base10_digits().to_string()),
+                Lit::Int(_) | Lit::Float(_) => return Err(Error::new(span, "number suffixes are not allowed in SQL")),
+                _ => return Err(Error::new(span, "expected a string or number literal")),
+            },
+            TokenTree::Punct(p) => {
+                let next = match iter.peek() {
+                    // `<>`不是rust的运算符，经过macro_rules!转发后会丢掉Joint，所以不看spacing
+                    Some(TokenTree::Punct(next)) => next.as_char(),
+                    _ => ' ',
+                };
+                let op = match (p.as_char(), next) {
+                    ('=', '=') => return Err(Error::new(span, "use `=` to compare in SQL")),
+                    ('<', '=') => "<=",
+                    ('>', '=') => ">=",
+                    ('!', '=') => "!=",
+                    ('<', '>') => "<>",
+                    ('=', _) => "=",
+                    ('<', _) => "<",
+                    ('>', _) => ">",
+                    ('*', _) => "*",
+                    (',', _) => ",",
+                    ('.', _) => ".",
+                    ('?', _) => "?",
+                    ('-', _) => "-",
+                    (c, _) => return Err(Error::new(span, format!("unexpected `{}` in SQL", c))),
+                };
+                if op.len() == 2 {
+                    iter.next();
+                }
+                Kind::Punct(op)
+            }
+        };
+        out.push(Token { kind, span });
+    }
+    Ok(())
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    /// the normalized statement
+    sql: String,
+    /// number of `?`
+    params: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    /// an error at the next token, or at the last one if the input is exhausted
+    fn error(&self, expected: &str) -> Error {
+        match self.peek() {
+            Some(token) => Error::new(token.span, format!("expected {}, found {}", expected, token.describe())),
+            None => {
+                let span = self.tokens.last().map_or_else(Span::call_site, |t| t.span);
+                Error::new(span, format!("expected {}, found end of input", expected))
+            }
+        }
+    }
+
+    /// append to the statement, separated by a space unless after `(` or before `,`/`)`
+    fn emit(&mut self, s: &str) {
+        if !(self.sql.is_empty() || self.sql.ends_with('(') || s == "," || s == ")") {
+            self.sql.push(' ');
+        }
+        self.sql.push_str(s);
+    }
+
+    fn peek_keyword(&self, keyword: &str) -> bool {
+        self.peek().is_some_and(|t| t.is_keyword(keyword))
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        let found = self.peek_keyword(keyword);
+        if found {
+            self.pos += 1;
+            self.emit(keyword);
+        }
+        found
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> Result<()> {
+        match self.eat_keyword(keyword) {
+            true => Ok(()),
+            false => Err(self.error(&format!("`{}`", keyword))),
+        }
+    }
+
+    fn eat(&mut self, kind: &Kind) -> bool {
+        let found = self.peek().is_some_and(|t| t.kind == *kind);
+        if found {
+            self.pos += 1;
+            self.emit(match kind {
+                Kind::Open => "(",
+                Kind::Close => ")",
+                Kind::Punct(p) => p,
+                _ => unreachable!("only punctuation is eaten"),
+            });
+        }
+        found
+    }
+
+    fn expect(&mut self, kind: Kind, expected: &str) -> Result<()> {
+        match self.eat(&kind) {
+            true => Ok(()),
+            false => Err(self.error(expected)),
+        }
+    }
+
+    fn statement(&mut self) -> Result<TokenStream> {
+        let kind = if self.eat_keyword("SELECT") {
+            self.select()?;
+            quote!(Select)
+        } else if self.eat_keyword("INSERT") {
+            self.insert()?;
+            quote!(Insert)
+        } else if self.eat_keyword("UPDATE") {
+            self.update()?;
+            quote!(Update)
+        } else if self.eat_keyword("DELETE") {
+            self.expect_keyword("FROM")?;
+            self.name("a table name")?;
+            self.filter()?;
+            quote!(Delete)
+        } else {
+            return Err(self.error("`SELECT`, `INSERT`, `UPDATE` or `DELETE`"));
+        };
+        match self.peek() {
+            None => Ok(kind),
+            Some(_) => Err(self.error("end of statement")),
+        }
+    }
+
+    fn select(&mut self) -> Result<()> {
+        self.eat_keyword("DISTINCT");
+        if !self.eat(&Kind::Punct("*")) {
+            loop {
+                self.select_item()?;
+                if !self.eat(&Kind::Punct(",")) {
+                    break;
+                }
+            }
+        }
+        self.expect_keyword("FROM")?;
+        self.name("a table name")?;
+        self.filter()?;
+        if self.eat_keyword("ORDER") {
+            self.expect_keyword("BY")?;
+            loop {
+                self.name("a column name")?;
+                let _ = self.eat_keyword("ASC") || self.eat_keyword("DESC");
+                if !self.eat(&Kind::Punct(",")) {
+                    break;
+                }
+            }
+        }
+        if self.eat_keyword("LIMIT") {
+            self.value()?;
+            if self.eat_keyword("OFFSET") {
+                self.value()?;
+            }
+        }
+        Ok(())
+    }
+
+    fn select_item(&mut self) -> Result<()> {
+        let is_call = matches!(self.tokens.get(self.pos + 1), Some(Token { kind: Kind::Open, .. }));
+        if is_call {
+            // count(*)之类的聚合函数，函数名按关键字处理
+            let function = self.word("a function name")?;
+            self.pos += 1;
+            self.emit(&format!("{}(", function.to_uppercase()));
+            if !self.eat(&Kind::Punct("*")) {
+                self.name("a column name or `*`")?;
+            }
+            self.expect(Kind::Close, "`)`")?;
+        } else {
+            self.name("a column name or `*`")?;
+        }
+        if self.eat_keyword("AS") {
+            self.ident("an alias")?;
+        }
+        Ok(())
+    }
+
+    fn insert(&mut self) -> Result<()> {
+        self.expect_keyword("INTO")?;
+        self.name("a table name")?;
+        self.expect(Kind::Open, "`(` before the column list")?;
+        let mut columns = 0;
+        loop {
+            self.ident("a column name")?;
+            columns += 1;
+            if !self.eat(&Kind::Punct(",")) {
+                break;
+            }
+        }
+        self.expect(Kind::Close, "`,` or `)`")?;
+        self.expect_keyword("VALUES")?;
+        loop {
+            let open = self.peek().map(|t| t.span);
+            self.expect(Kind::Open, "`(` before the values")?;
+            let mut values = 0;
+            loop {
+                self.operand()?;
+                values += 1;
+                if !self.eat(&Kind::Punct(",")) {
+                    break;
+                }
+            }
+            let close = self.peek().map(|t| t.span);
+            self.expect(Kind::Close, "`,` or `)`")?;
+            if values != columns {
+                let msg = format!("expected {} values to match the columns, found {}", columns, values);
+                // 从`(`到`)`整个值列表都标出来
+                let group = [open, close]
+                    .map(|span| TokenTree::Ident(Ident::new("values", span.expect("`(` and `)` were just parsed"))));
+                return Err(Error::new_spanned(TokenStream::from_iter(group), msg));
+            }
+            if !self.eat(&Kind::Punct(",")) {
+                return Ok(());
+            }
+        }
+    }
+
+    fn update(&mut self) -> Result<()> {
+        self.name("a table name")?;
+        self.expect_keyword("SET")?;
+        loop {
+            self.ident("a column name")?;
+            self.expect(Kind::Punct("="), "`=`")?;
+            self.operand()?;
+            if !self.eat(&Kind::Punct(",")) {
+                break;
+            }
+        }
+        self.filter()
+    }
+
+    /// optional `WHERE expr`
+    fn filter(&mut self) -> Result<()> {
+        match self.eat_keyword("WHERE") {
+            true => self.or_expr(),
+            false => Ok(()),
+        }
+    }
+
+    fn or_expr(&mut self) -> Result<()> {
+        self.and_expr()?;
+        while self.eat_keyword("OR") {
+            self.and_expr()?;
+        }
+        Ok(())
+    }
+
+    fn and_expr(&mut self) -> Result<()> {
+        self.not_expr()?;
+        while self.eat_keyword("AND") {
+            self.not_expr()?;
+        }
+        Ok(())
+    }
+
+    fn not_expr(&mut self) -> Result<()> {
+        if self.eat_keyword("NOT") {
+            return self.not_expr();
+        }
+        if self.eat(&Kind::Open) {
+            self.or_expr()?;
+            return self.expect(Kind::Close, "`)`");
+        }
+        self.predicate()
+    }
+
+    fn predicate(&mut self) -> Result<()> {
+        self.operand()?;
+        for op in ["=", "!=", "<>", "<=", ">=", "<", ">"] {
+            if self.eat(&Kind::Punct(op)) {
+                return self.operand();
+            }
+        }
+        if self.eat_keyword("IS") {
+            self.eat_keyword("NOT");
+            return self.expect_keyword("NULL");
+        }
+        self.eat_keyword("NOT");
+        if self.eat_keyword("IN") {
+            self.expect(Kind::Open, "`(`")?;
+            loop {
+                self.operand()?;
+                if !self.eat(&Kind::Punct(",")) {
+                    break;
+                }
+            }
+            self.expect(Kind::Close, "`,` or `)`")
+        } else if self.eat_keyword("LIKE") {
+            self.operand()
+        } else if self.eat_keyword("BETWEEN") {
+            self.operand()?;
+            self.expect_keyword("AND")?;
+            self.operand()
+        } else {
+            Err(self.error("a comparison operator, `IS`, `IN`, `LIKE` or `BETWEEN`"))
+        }
+    }
+
+    fn operand(&mut self) -> Result<()> {
+        match self.peek().map(|t| &t.kind) {
+            Some(Kind::Word(w)) if !is_keyword(w) => self.name("a column name"),
+            _ => self.value(),
+        }
+    }
+
+    fn value(&mut self) -> Result<()> {
+        for keyword in ["NULL", "TRUE", "FALSE"] {
+            if self.eat_keyword(keyword) {
+                return Ok(());
+            }
+        }
+        let negative = self.peek().is_some_and(|t| t.kind == Kind::Punct("-"));
+        let token = self.tokens.get(self.pos + negative as usize);
+        let text = match token.map(|t| &t.kind) {
+            Some(Kind::Punct("?")) if !negative => {
+                self.params += 1;
+                String::from("?")
+            }
+            Some(Kind::Str(s)) if !negative => format!("'{}'", s.replace('\'', "''")),
+            Some(Kind::Number(n)) if negative => format!("-{}", n),
+            Some(Kind::Number(n)) => n.clone(),
+            _ => return Err(self.error("a value or `?`")),
+        };
+        self.pos += 1 + negative as usize;
+        self.emit(&text);
+        Ok(())
+    }
+
+    /// consume an identifier that is not a keyword without emitting it
+    fn word(&mut self, expected: &str) -> Result<String> {
+        match self.peek().map(|t| &t.kind) {
+            Some(Kind::Word(w)) if !is_keyword(w) => {
+                let w = w.clone();
+                self.pos += 1;
+                Ok(w)
+            }
+            _ => Err(self.error(expected)),
+        }
+    }
+
+    /// an identifier emitted as written
+    fn ident(&mut self, expected: &str) -> Result<()> {
+        let ident = self.word(expected)?;
+        self.emit(&ident);
+        Ok(())
+    }
+
+    /// `ident` or `ident.ident`
+    fn name(&mut self, expected: &str) -> Result<()> {
+        let mut name = self.word(expected)?;
+        if self.peek().is_some_and(|t| t.kind == Kind::Punct(".")) {
+            self.pos += 1;
+            name.push('.');
+            name.push_str(&self.word("a name after `.`")?);
+        }
+        self.emit(&name);
+        Ok(())
+    }
+}
+
+fn is_keyword(word: &str) -> bool {
+    KEYWORDS.iter().any(|k| k.eq_ignore_ascii_case(word))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(input: TokenStream) -> Result<(String, usize)> {
+        let mut tokens = Vec::new();
+        tokenize(input, &mut tokens)?;
+        let mut parser = Parser {
+            tokens,
+            pos: 0,
+            sql: String::new(),
+            params: 0,
+        };
+        parser.statement()?;
+        Ok((parser.sql, parser.params))
+    }
+
+    fn ok(input: TokenStream, sql: &str, params: usize) {
+        assert_eq!((String::from(sql), params), parse(input).unwrap());
+    }
+
+    fn err(input: TokenStream) -> String {
+        parse(input).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn statements() {
+        ok(
+            quote!(select * from user where id=1),
+            "SELECT * FROM user WHERE id = 1",
+            0,
+        );
+        ok(
+            quote!(SELECT DISTINCT u.name AS n, count(*) FROM users WHERE (age >= ? OR vip = TRUE) AND name NOT LIKE "o'k" ORDER BY u.name DESC, id LIMIT 10 OFFSET ?),
+            "SELECT DISTINCT u.name AS n, COUNT(*) FROM users WHERE (age >= ? OR vip = TRUE) AND name NOT LIKE 'o''k' ORDER BY u.name DESC, id LIMIT 10 OFFSET ?",
+            2,
+        );
+        ok(
+            quote!(insert into user (id, name) values (?, ?), (-1, "x")),
+            "INSERT INTO user (id, name) VALUES (?, ?), (-1, 'x')",
+            2,
+        );
+        ok(
+            quote!(update user set name = ?, age = age where id in (1, ?) and deleted_at is not null),
+            "UPDATE user SET name = ?, age = age WHERE id IN (1, ?) AND deleted_at IS NOT NULL",
+            2,
+        );
+        ok(
+            quote!(delete from user where not id between ? and 3.5 or name <> ?),
+            "DELETE FROM user WHERE NOT id BETWEEN ? AND 3.5 OR name <> ?",
+            2,
+        );
+    }
+
+    #[test]
+    fn syntax_errors() {
+        assert_eq!("expected `FROM`, found `form`", err(quote!(select * form user)));
+        assert_eq!(
+            "expected `SELECT`, `INSERT`, `UPDATE` or `DELETE`, found `selct`",
+            err(quote!(selct * from user))
+        );
+        assert_eq!(
+            "expected a value or `?`, found end of input",
+            err(quote!(delete from user where id =))
+        );
+        assert_eq!("use `=` to compare in SQL", err(quote!(delete from user where id == 1)));
+        assert_eq!("unexpected `;` in SQL", err(quote!(delete from user;)));
+        assert_eq!(
+            "expected end of statement, found `limit`",
+            err(quote!(delete from user limit 1))
+        );
+        assert_eq!(
+            "expected a table name, found `where`",
+            err(quote!(delete from where id = 1))
+        );
+        assert_eq!(
+            "expected 2 values to match the columns, found 1",
+            err(quote!(insert into user (id, name) values (?)))
+        );
+        assert_eq!(
+            "number suffixes are not allowed in SQL",
+            err(quote!(select * from t limit 1u8))
+        );
+        assert_eq!(
+            "only parentheses are allowed in SQL",
+            err(quote!(select * from t where id in [1]))
+        );
+        assert_eq!(
+            "expected a comparison operator, `IS`, `IN`, `LIKE` or `BETWEEN`, found `1`",
+            err(quote!(select * from t where id 1))
+        );
+    }
+}